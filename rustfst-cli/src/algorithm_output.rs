@@ -0,0 +1,35 @@
+use std::io::Write;
+
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::stdio::{create_output, write_fst};
+
+/// Result of an algorithm run by the CLI, written to the output path of the command.
+/// The path `-` stands for stdout.
+pub trait AlgorithmOutput {
+    fn write_output(&self, path: &str) -> Result<()>;
+}
+
+impl<W: SerializableSemiring> AlgorithmOutput for VectorFst<W> {
+    fn write_output(&self, path: &str) -> Result<()> {
+        write_fst(self, path)
+    }
+}
+
+/// Used by the algorithms whose output type depends on their parameters.
+impl AlgorithmOutput for Box<dyn AlgorithmOutput> {
+    fn write_output(&self, path: &str) -> Result<()> {
+        self.as_ref().write_output(path)
+    }
+}
+
+impl AlgorithmOutput for String {
+    fn write_output(&self, path: &str) -> Result<()> {
+        let mut output = create_output(path)?;
+        output.write_all(self.as_bytes())?;
+        output.flush()?;
+        Ok(())
+    }
+}
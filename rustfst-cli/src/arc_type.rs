@@ -0,0 +1,67 @@
+use std::fs::File;
+use std::io::Read;
+
+use anyhow::{bail, Context, Result};
+
+use rustfst::prelude::*;
+
+use crate::stdio::{is_stdio, stdin_data};
+
+/// Number of bytes read from a file to find its arc type : the magic number and the fst and
+/// arc type names of a binary fst fit in it.
+const HEADER_PREFIX_LEN: u64 = 1024;
+
+/// Semirings the CLI can run the algorithms with.
+pub trait CliSemiring:
+    SerializableSemiring
+    + WeaklyDivisibleSemiring
+    + WeightQuantize
+    + Semiring<Type = f32, ReverseWeight = Self>
+    + 'static
+{
+}
+
+impl CliSemiring for TropicalWeight {}
+impl CliSemiring for LogWeight {}
+
+/// Arc types supported by the CLI, named as in OpenFST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcType {
+    Standard,
+    Log,
+}
+
+impl ArcType {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "standard" | "tropical" => Ok(ArcType::Standard),
+            "log" => Ok(ArcType::Log),
+            _ => bail!("Unsupported arc type : {}", name),
+        }
+    }
+
+    /// Reads the arc type from the header of the binary fst at `path`, `-` being stdin.
+    pub fn from_binary_fst(path: &str) -> Result<Self> {
+        let tr_type = if is_stdio(path) {
+            read_bin_fst_tr_type(stdin_data()?)
+        } else {
+            let file = File::open(path).with_context(|| format!("Can't open file : {:?}", path))?;
+            let mut data = vec![];
+            file.take(HEADER_PREFIX_LEN)
+                .read_to_end(&mut data)
+                .with_context(|| format!("Can't read file : {:?}", path))?;
+            read_bin_fst_tr_type(&data)
+        }
+        .with_context(|| format!("Can't read the header of the binary fst : {:?}", path))?;
+        Self::from_name(&tr_type)
+    }
+
+    /// Arc type of the command : the `--arc_type` flag if provided, otherwise the one of the
+    /// binary fst at `path`.
+    pub fn resolve(arc_type: Option<&str>, path: &str) -> Result<Self> {
+        match arc_type {
+            Some(arc_type) => Self::from_name(arc_type),
+            None => Self::from_binary_fst(path),
+        }
+    }
+}
@@ -9,6 +9,23 @@ use log::{debug, info};
 
 use rustfst::prelude::*;
 
+use crate::algorithm_output::AlgorithmOutput;
+use crate::arc_type::{ArcType, CliSemiring};
+use crate::stdio::{check_single_stdin, read_fst};
+
+/// Runs `algorithm` with the semiring matching the `--arc_type` flag, or the arc type of its
+/// first binary input. Both inputs must have the same arc type.
+pub fn run_binary<A>(algorithm: &A, m: &ArgMatches) -> Result<()>
+where
+    A: BinaryFstAlgorithm<TropicalWeight> + BinaryFstAlgorithm<LogWeight>,
+{
+    let path_in = BinaryFstAlgorithm::<TropicalWeight>::get_path_in_1(algorithm);
+    match ArcType::resolve(m.get_one::<String>("arc_type").map(|s| s.as_str()), path_in)? {
+        ArcType::Standard => BinaryFstAlgorithm::<TropicalWeight>::run_cli_or_bench(algorithm, m),
+        ArcType::Log => BinaryFstAlgorithm::<LogWeight>::run_cli_or_bench(algorithm, m),
+    }
+}
+
 fn duration_to_seconds(duration: &Duration) -> f64 {
     duration.as_secs() as f64 + duration.subsec_nanos() as f64 * 1.0e-9
 }
@@ -22,31 +39,30 @@ fn standard_deviation(data: &[f64]) -> f64 {
     b.sqrt()
 }
 
-pub trait BinaryFstAlgorithm {
+pub trait BinaryFstAlgorithm<W: CliSemiring> {
+    type Output: AlgorithmOutput;
+
     fn get_path_in_1(&self) -> &str;
     fn get_path_in_2(&self) -> &str;
     fn get_path_out(&self) -> &str;
     fn get_algorithm_name(&self) -> String;
 
-    fn read(&self) -> Result<(VectorFst<TropicalWeight>, VectorFst<TropicalWeight>)> {
+    fn read(&self) -> Result<(VectorFst<W>, VectorFst<W>)> {
+        check_single_stdin(&[self.get_path_in_1(), self.get_path_in_2()])?;
         Ok((
-            VectorFst::<TropicalWeight>::read(self.get_path_in_1())?,
-            VectorFst::<TropicalWeight>::read(self.get_path_in_2())?,
+            read_fst(self.get_path_in_1())?,
+            read_fst(self.get_path_in_2())?,
         ))
     }
 
-    fn run_algorithm(
-        &self,
-        fst_1: VectorFst<TropicalWeight>,
-        fst_2: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>>;
+    fn run_algorithm(&self, fst_1: VectorFst<W>, fst_2: VectorFst<W>) -> Result<Self::Output>;
 
-    fn write(&self, fst: &VectorFst<TropicalWeight>) -> Result<()> {
-        fst.write(self.get_path_out())
+    fn write(&self, output: &Self::Output) -> Result<()> {
+        output.write_output(self.get_path_out())
     }
 
     fn run_cli_or_bench(&self, m: &ArgMatches) -> Result<()> {
-        if m.contains_id("bench") {
+        if m.get_flag("bench") {
             // Run bench
             self.run_bench(
                 m.get_one::<String>("n_warm_ups").unwrap().parse().unwrap(),
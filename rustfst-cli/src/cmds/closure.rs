@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use rustfst::algorithms::closure::{closure, ClosureType};
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct ClosureAlgorithm {
+    path_in: String,
+    path_out: String,
+    closure_plus: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ClosureAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "closure".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        let closure_type = if self.closure_plus {
+            ClosureType::ClosurePlus
+        } else {
+            ClosureType::ClosureStar
+        };
+        closure(&mut fst, closure_type);
+        Ok(fst)
+    }
+}
+
+impl ClosureAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, closure_plus: bool) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            closure_plus,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::stdio::read_text;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct CompileAlgorithm {
+    path_in: String,
+    path_out: String,
+    config: TextFstParseConfig,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for CompileAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "compile".to_string()
+    }
+
+    fn read(&self) -> Result<VectorFst<W>> {
+        VectorFst::<W>::from_text_string_with_config(
+            &read_text(self.path_in.as_str())?,
+            &self.config,
+        )
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        Ok(fst)
+    }
+}
+
+fn read_symt(path: Option<&str>) -> Result<Option<Arc<SymbolTable>>> {
+    path.map(|p| SymbolTable::read_text(p).map(Arc::new))
+        .transpose()
+}
+
+impl CompileAlgorithm {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        ssymbols: Option<&str>,
+        acceptor: bool,
+        keep_isymbols: bool,
+        keep_osymbols: bool,
+        allow_unknown_symbols: bool,
+    ) -> Result<Self> {
+        let config = TextFstParseConfig::new(
+            read_symt(isymbols)?,
+            read_symt(osymbols)?,
+            read_symt(ssymbols)?,
+        )
+        .with_acceptor(acceptor)
+        .with_keep_isymbols(keep_isymbols)
+        .with_keep_osymbols(keep_osymbols)
+        .with_allow_unknown_symbols(allow_unknown_symbols);
+        Ok(Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            config,
+        })
+    }
+}
@@ -4,8 +4,8 @@ use rustfst::algorithms::compose::{
     compose, ComposeFst, ComposeFstOpOptions, LabelReachableData, MatcherFst,
 };
 use rustfst::fst_impls::VectorFst;
-use rustfst::semirings::TropicalWeight;
 
+use crate::arc_type::CliSemiring;
 use crate::binary_fst_algorithm::BinaryFstAlgorithm;
 use rustfst::algorithms::compose::compose_filters::{
     AltSequenceComposeFilterBuilder, ComposeFilterBuilder,
@@ -48,7 +48,9 @@ impl MatcherFlagsTrait for DefaultLabelLookAheadMatcherFlags {
     }
 }
 
-impl BinaryFstAlgorithm for ComposeAlgorithm {
+impl<W: CliSemiring> BinaryFstAlgorithm<W> for ComposeAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in_1(&self) -> &str {
         &self.path_in_1
     }
@@ -65,14 +67,10 @@ impl BinaryFstAlgorithm for ComposeAlgorithm {
         "compose".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        fst_1: VectorFst<TropicalWeight>,
-        mut fst_2: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, fst_1: VectorFst<W>, mut fst_2: VectorFst<W>) -> Result<VectorFst<W>> {
         match self.compose_type {
             ComposeType::Default => {
-                compose::<TropicalWeight, VectorFst<_>, VectorFst<_>, _, _, _>(&fst_1, &fst_2)
+                compose::<W, VectorFst<_>, VectorFst<_>, _, _, _>(&fst_1, &fst_2)
             }
             ComposeType::LookAhead => {
                 type TLaFst<'a, S, F> = MatcherFst<
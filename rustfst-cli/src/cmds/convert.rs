@@ -0,0 +1,51 @@
+use anyhow::{bail, Result};
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::stdio::write_fst;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct ConvertAlgorithm {
+    path_in: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ConvertAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "convert".to_string()
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output> {
+        Ok(fst)
+    }
+
+    fn write(&self, fst: &Self::Output) -> Result<()> {
+        match self.fst_type.as_str() {
+            "vector" => write_fst(fst, self.path_out.as_str()),
+            "const" => write_fst(&ConstFst::from(fst.clone()), self.path_out.as_str()),
+            _ => bail!("Unknown fst type : {}", self.fst_type),
+        }
+    }
+}
+
+impl ConvertAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+
+use rustfst::algorithms::encode::{decode, EncodeTable};
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct DecodeAlgorithm {
+    path_in: String,
+    path_encoder: String,
+    path_out: String,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for DecodeAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "decode".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        let encode_table = EncodeTable::read(&self.path_encoder)?;
+        decode(&mut fst, encode_table)?;
+        Ok(fst)
+    }
+}
+
+impl DecodeAlgorithm {
+    pub fn new(path_in: &str, path_encoder: &str, path_out: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_encoder: path_encoder.to_string(),
+            path_out: path_out.to_string(),
+        }
+    }
+}
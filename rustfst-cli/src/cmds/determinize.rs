@@ -3,6 +3,7 @@ use anyhow::Result;
 use rustfst::algorithms::determinize::{DeterminizeConfig, DeterminizeType};
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct DeterminizeAlgorithm {
@@ -11,7 +12,9 @@ pub struct DeterminizeAlgorithm {
     det_type: DeterminizeType,
 }
 
-impl UnaryFstAlgorithm for DeterminizeAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for DeterminizeAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -24,7 +27,7 @@ impl UnaryFstAlgorithm for DeterminizeAlgorithm {
         "determinize".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let det_config = DeterminizeConfig::default().with_det_type(self.det_type);
         let fst = determinize::determinize_with_config(&fst, det_config)?;
         Ok(fst)
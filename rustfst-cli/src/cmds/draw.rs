@@ -0,0 +1,103 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::stdio::create_output;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct DrawAlgorithm {
+    path_in: String,
+    path_out: String,
+    isymt: Option<Arc<SymbolTable>>,
+    osymt: Option<Arc<SymbolTable>>,
+    // No path can be highlighted from the command line, so the config doesn't depend on the
+    // arc type and is copied for the semiring of the FST.
+    config: DrawingConfig,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for DrawAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "draw".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        if let Some(isymt) = &self.isymt {
+            fst.set_input_symbols(Arc::clone(isymt));
+        }
+        if let Some(osymt) = &self.osymt {
+            fst.set_output_symbols(Arc::clone(osymt));
+        }
+        Ok(fst)
+    }
+
+    fn write(&self, fst: &Self::Output) -> Result<()> {
+        let mut output = create_output(self.path_out.as_str())?;
+        fst.draw_to_writer(&mut output, &self.drawing_config())?;
+        output.flush()?;
+        Ok(())
+    }
+}
+
+impl DrawAlgorithm {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        ssymbols: Option<&str>,
+        config: DrawingConfig,
+    ) -> Result<Self> {
+        Ok(Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymt: isymbols
+                .map(|p| SymbolTable::read_text(p).map(Arc::new))
+                .transpose()?,
+            osymt: osymbols
+                .map(|p| SymbolTable::read_text(p).map(Arc::new))
+                .transpose()?,
+            config: DrawingConfig {
+                ssymbols: ssymbols
+                    .map(|p| SymbolTable::read_text(p).map(Arc::new))
+                    .transpose()?,
+                ..config
+            },
+        })
+    }
+
+    fn drawing_config<W: Semiring>(&self) -> DrawingConfig<W> {
+        let config = &self.config;
+        DrawingConfig {
+            vertical: config.vertical,
+            size: config.size,
+            title: config.title.clone(),
+            portrait: config.portrait,
+            ranksep: config.ranksep,
+            nodesep: config.nodesep,
+            fontsize: config.fontsize,
+            acceptor: config.acceptor,
+            show_weight_one: config.show_weight_one,
+            print_weight: config.print_weight,
+            ssymbols: config.ssymbols.clone(),
+            max_states: config.max_states,
+            highlight_path: None,
+            highlight_color: config.highlight_color.clone(),
+            float_precision: config.float_precision,
+            allow_negative_labels: config.allow_negative_labels,
+        }
+    }
+}
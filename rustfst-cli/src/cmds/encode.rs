@@ -0,0 +1,65 @@
+use anyhow::Result;
+
+use rustfst::algorithms::encode::{encode_with_table, EncodeTable, EncodeType};
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct EncodeAlgorithm {
+    path_in: String,
+    path_encoder: String,
+    path_out: String,
+    encode_labels: bool,
+    encode_weights: bool,
+    encode_reuse: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for EncodeAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "encode".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        if self.encode_reuse {
+            // The encoder file is left untouched, as with fstencode.
+            let encode_table = EncodeTable::read(&self.path_encoder)?;
+            encode_with_table(&mut fst, encode_table)?;
+        } else {
+            let encode_type = EncodeType::from_bools(self.encode_weights, self.encode_labels)?;
+            let encode_table = encode_with_table(&mut fst, EncodeTable::new(encode_type))?;
+            encode_table.write(&self.path_encoder)?;
+        }
+        Ok(fst)
+    }
+}
+
+impl EncodeAlgorithm {
+    pub fn new(
+        path_in: &str,
+        path_encoder: &str,
+        path_out: &str,
+        encode_labels: bool,
+        encode_weights: bool,
+        encode_reuse: bool,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_encoder: path_encoder.to_string(),
+            path_out: path_out.to_string(),
+            encode_labels,
+            encode_weights,
+            encode_reuse,
+        }
+    }
+}
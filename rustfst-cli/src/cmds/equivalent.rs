@@ -0,0 +1,64 @@
+use anyhow::Result;
+
+use rustfst::algorithms::determinize::determinize;
+use rustfst::algorithms::encode::{encode_with_table, EncodeTable, EncodeType};
+use rustfst::algorithms::{connect, equivalent_with_config, EquivalentConfig};
+use rustfst::fst_impls::VectorFst;
+
+use crate::arc_type::CliSemiring;
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+
+pub struct EquivalentAlgorithm {
+    path_in_1: String,
+    path_in_2: String,
+    delta: f32,
+}
+
+impl<W: CliSemiring> BinaryFstAlgorithm<W> for EquivalentAlgorithm {
+    type Output = String;
+
+    fn get_path_in_1(&self) -> &str {
+        self.path_in_1.as_str()
+    }
+
+    fn get_path_in_2(&self) -> &str {
+        self.path_in_2.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        "-"
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "equivalent".to_string()
+    }
+
+    fn run_algorithm(&self, fst_1: VectorFst<W>, fst_2: VectorFst<W>) -> Result<Self::Output> {
+        // Like fstequivalent, both fsts are encoded with the same table, determinized and
+        // trimmed so that the comparison doesn't require deterministic inputs.
+        let (fst_1, encode_table) = prepare(fst_1, EncodeTable::new(EncodeType::EncodeLabels))?;
+        let (fst_2, _) = prepare(fst_2, encode_table)?;
+        let res = equivalent_with_config(&fst_1, &fst_2, EquivalentConfig::new(self.delta))?;
+        Ok(format!("{}\n", res))
+    }
+}
+
+fn prepare<W: CliSemiring>(
+    mut fst: VectorFst<W>,
+    encode_table: EncodeTable<W>,
+) -> Result<(VectorFst<W>, EncodeTable<W>)> {
+    let encode_table = encode_with_table(&mut fst, encode_table)?;
+    let mut fst: VectorFst<W> = determinize(&fst)?;
+    connect(&mut fst)?;
+    Ok((fst, encode_table))
+}
+
+impl EquivalentAlgorithm {
+    pub fn new(path_in_1: &str, path_in_2: &str, delta: &str) -> Self {
+        Self {
+            path_in_1: path_in_1.to_string(),
+            path_in_2: path_in_2.to_string(),
+            delta: delta.parse().unwrap(),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct InfoAlgorithm {
+    path_in: String,
+    path_out: String,
+    long: bool,
+    json: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for InfoAlgorithm {
+    type Output = String;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "info".to_string()
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output> {
+        let info = FstInfo::new(&fst, self.long)?;
+        if self.json {
+            Ok(format!("{}\n", serde_json::to_string_pretty(&info)?))
+        } else {
+            Ok(info.to_string())
+        }
+    }
+}
+
+impl InfoAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, long: bool, json: bool) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            long,
+            json,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct InvertAlgorithm {
@@ -9,7 +10,9 @@ pub struct InvertAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for InvertAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for InvertAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -22,10 +25,7 @@ impl UnaryFstAlgorithm for InvertAlgorithm {
         "invert".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         invert(&mut fst);
         Ok(fst)
     }
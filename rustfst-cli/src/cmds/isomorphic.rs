@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use rustfst::algorithms::{isomorphic_with_config, IsomorphicConfig};
+use rustfst::fst_impls::VectorFst;
+
+use crate::arc_type::CliSemiring;
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+
+pub struct IsomorphicAlgorithm {
+    path_in_1: String,
+    path_in_2: String,
+    delta: f32,
+}
+
+impl<W: CliSemiring> BinaryFstAlgorithm<W> for IsomorphicAlgorithm {
+    type Output = String;
+
+    fn get_path_in_1(&self) -> &str {
+        self.path_in_1.as_str()
+    }
+
+    fn get_path_in_2(&self) -> &str {
+        self.path_in_2.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        "-"
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "isomorphic".to_string()
+    }
+
+    fn run_algorithm(&self, fst_1: VectorFst<W>, fst_2: VectorFst<W>) -> Result<Self::Output> {
+        let res = isomorphic_with_config(&fst_1, &fst_2, IsomorphicConfig::new(self.delta))?;
+        Ok(format!("{}\n", res))
+    }
+}
+
+impl IsomorphicAlgorithm {
+    pub fn new(path_in_1: &str, path_in_2: &str, delta: &str) -> Self {
+        Self {
+            path_in_1: path_in_1.to_string(),
+            path_in_2: path_in_2.to_string(),
+            delta: delta.parse().unwrap(),
+        }
+    }
+}
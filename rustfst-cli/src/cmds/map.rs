@@ -1,8 +1,11 @@
 use anyhow::{bail, Result};
 use unsafe_unwrap::UnsafeUnwrap;
 
+use rustfst::algorithms::weight_converters::SimpleWeightConverter;
 use rustfst::prelude::*;
 
+use crate::algorithm_output::AlgorithmOutput;
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct MapAlgorithm {
@@ -12,7 +15,9 @@ pub struct MapAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for MapAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for MapAlgorithm {
+    type Output = Box<dyn AlgorithmOutput>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -25,10 +30,34 @@ impl UnaryFstAlgorithm for MapAlgorithm {
         format!("map {}", self.map_type)
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output> {
+        match self.map_type.as_str() {
+            "to_log" => {
+                let fst: VectorFst<LogWeight> =
+                    weight_convert(&fst, &mut SimpleWeightConverter {})?;
+                Ok(Box::new(fst))
+            }
+            "to_std" => {
+                let fst: VectorFst<TropicalWeight> =
+                    weight_convert(&fst, &mut SimpleWeightConverter {})?;
+                Ok(Box::new(fst))
+            }
+            _ => Ok(Box::new(self.map(fst)?)),
+        }
+    }
+}
+
+impl MapAlgorithm {
+    pub fn new(path_in: &str, map_type: &str, weight: Option<&str>, path_out: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            map_type: map_type.to_string(),
+            weight: weight.map(|f| f.parse().unwrap()),
+            path_out: path_out.to_string(),
+        }
+    }
+
+    fn map<W: CliSemiring>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         match self.map_type.as_str() {
             "tr_sum" | "arc_sum" => {
                 tr_sum(&mut fst);
@@ -88,14 +117,3 @@ impl UnaryFstAlgorithm for MapAlgorithm {
         }
     }
 }
-
-impl MapAlgorithm {
-    pub fn new(path_in: &str, map_type: &str, weight: Option<&str>, path_out: &str) -> Self {
-        Self {
-            path_in: path_in.to_string(),
-            map_type: map_type.to_string(),
-            weight: weight.map(|f| f.parse().unwrap()),
-            path_out: path_out.to_string(),
-        }
-    }
-}
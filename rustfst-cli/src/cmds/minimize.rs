@@ -1,5 +1,6 @@
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 use anyhow::Result;
 
@@ -9,7 +10,9 @@ pub struct MinimizeAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for MinimizeAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for MinimizeAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -22,10 +25,7 @@ impl UnaryFstAlgorithm for MinimizeAlgorithm {
         "minimize".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let config = MinimizeConfig::default().with_allow_nondet(self.allow_nondet);
         minimize_with_config(&mut fst, config)?;
         Ok(fst)
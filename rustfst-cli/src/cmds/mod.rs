@@ -1,14 +1,36 @@
+pub mod closure;
+pub mod compile;
 pub mod compose;
+pub mod concat;
 pub mod connect;
+pub mod convert;
+pub mod decode;
 pub mod determinize;
+pub mod draw;
+pub mod encode;
+pub mod equivalent;
+pub mod info;
 pub mod invert;
+pub mod isomorphic;
 pub mod map;
 pub mod minimize;
 pub mod optimize;
+pub mod print;
 pub mod project;
+pub mod prune;
 pub mod push;
+pub mod randgen;
+pub mod relabel;
+pub mod replace;
 pub mod reverse;
+pub mod reweight;
+pub mod rm_epsilon;
 pub mod rm_final_epsilon;
+pub mod shortest_distance;
 pub mod shortest_path;
+pub mod state_sort;
+pub mod synchronize;
 pub mod topsort;
 pub mod tr_sort;
+pub mod union;
+pub mod utils;
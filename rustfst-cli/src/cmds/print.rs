@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct PrintAlgorithm {
+    path_in: String,
+    path_out: String,
+    isymt: Option<Arc<SymbolTable>>,
+    osymt: Option<Arc<SymbolTable>>,
+    config: TextWriteConfig,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for PrintAlgorithm {
+    type Output = String;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "print".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        if let Some(isymt) = &self.isymt {
+            fst.set_input_symbols(Arc::clone(isymt));
+        }
+        if let Some(osymt) = &self.osymt {
+            fst.set_output_symbols(Arc::clone(osymt));
+        }
+        fst.text_with_config(&self.config)
+    }
+}
+
+impl PrintAlgorithm {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        ssymbols: Option<&str>,
+        config: TextWriteConfig,
+    ) -> Result<Self> {
+        Ok(Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymt: isymbols
+                .map(|p| SymbolTable::read_text(p).map(Arc::new))
+                .transpose()?,
+            osymt: osymbols
+                .map(|p| SymbolTable::read_text(p).map(Arc::new))
+                .transpose()?,
+            config: config.with_ssymbols(
+                ssymbols
+                    .map(|p| SymbolTable::read_text(p).map(Arc::new))
+                    .transpose()?,
+            ),
+        })
+    }
+}
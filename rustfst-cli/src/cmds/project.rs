@@ -2,6 +2,7 @@ use anyhow::Result;
 
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 #[derive(Debug)]
@@ -11,7 +12,9 @@ pub struct ProjectFstAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for ProjectFstAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ProjectFstAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -24,10 +27,7 @@ impl UnaryFstAlgorithm for ProjectFstAlgorithm {
         "project".into()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         dbg!(&self);
         project(&mut fst, self.project_type);
         Ok(fst)
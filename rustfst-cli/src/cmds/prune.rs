@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct PruneAlgorithm {
+    path_in: String,
+    path_out: String,
+    weight: f32,
+    nstate: Option<StateId>,
+    delta: f32,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for PruneAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "prune".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        let config = PruneConfig::new(W::new(self.weight))
+            .with_state_threshold(self.nstate)
+            .with_delta(self.delta);
+        prune_with_config(&mut fst, config)?;
+        Ok(fst)
+    }
+}
+
+impl PruneAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, weight: &str, nstate: &str, delta: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            weight: weight.parse().unwrap(),
+            nstate: nstate.parse().ok(),
+            delta: delta.parse().unwrap(),
+        }
+    }
+}
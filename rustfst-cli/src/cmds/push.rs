@@ -2,6 +2,7 @@ use anyhow::Result;
 
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct PushAlgorithm {
@@ -11,7 +12,9 @@ pub struct PushAlgorithm {
     reweight_type: ReweightType,
 }
 
-impl UnaryFstAlgorithm for PushAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for PushAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -24,7 +27,7 @@ impl UnaryFstAlgorithm for PushAlgorithm {
         "push".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         push(&fst, self.reweight_type, self.push_type)
     }
 }
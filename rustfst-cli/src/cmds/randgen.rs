@@ -0,0 +1,69 @@
+use anyhow::Result;
+
+use rustfst::algorithms::randgen::{randgen_with_config, RandGenConfig, RandGenSelector};
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct RandGenAlgorithm {
+    path_in: String,
+    path_out: String,
+    select: String,
+    seed: Option<u64>,
+    npath: usize,
+    max_length: usize,
+    weighted: bool,
+    remove_total_weight: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for RandGenAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        format!("randgen {}", self.select)
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output> {
+        let selector = RandGenSelector::from_name(self.select.as_str(), self.seed)?;
+        let config = RandGenConfig::new(selector)
+            .with_npath(self.npath)
+            .with_max_length(self.max_length)
+            .with_weighted(self.weighted)
+            .with_remove_total_weight(self.remove_total_weight);
+        randgen_with_config(&fst, config)
+    }
+}
+
+impl RandGenAlgorithm {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        select: &str,
+        seed: Option<&str>,
+        npath: &str,
+        max_length: Option<&str>,
+        weighted: bool,
+        remove_total_weight: bool,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            select: select.to_string(),
+            seed: seed.map(|s| s.parse().unwrap()),
+            npath: npath.parse().unwrap(),
+            max_length: max_length.map_or(usize::MAX, |s| s.parse().unwrap()),
+            weighted,
+            remove_total_weight,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+
+use crate::cmds::utils::read_pairs;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct RelabelAlgorithm {
+    path_in: String,
+    path_out: String,
+    relabel_ipairs: Option<String>,
+    relabel_opairs: Option<String>,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for RelabelAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "relabel".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        let ipairs = read_pairs(self.relabel_ipairs.as_deref())?;
+        let opairs = read_pairs(self.relabel_opairs.as_deref())?;
+        relabel_pairs(&mut fst, ipairs, opairs)?;
+        Ok(fst)
+    }
+}
+
+impl RelabelAlgorithm {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        relabel_ipairs: Option<&str>,
+        relabel_opairs: Option<&str>,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            relabel_ipairs: relabel_ipairs.map(|s| s.to_string()),
+            relabel_opairs: relabel_opairs.map(|s| s.to_string()),
+        }
+    }
+}
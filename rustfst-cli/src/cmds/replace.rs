@@ -0,0 +1,69 @@
+use anyhow::{bail, Result};
+
+use rustfst::algorithms::replace::replace;
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::stdio::{check_single_stdin, read_fst, STDIO_PATH};
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct ReplaceAlgorithm {
+    path_root: String,
+    root_label: Label,
+    rules: Vec<(String, Label)>,
+    path_out: String,
+    epsilon_on_replace: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ReplaceAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_root.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "replace".to_string()
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output> {
+        let mut fst_list = vec![(self.root_label, fst)];
+        for (path, label) in self.rules.iter() {
+            fst_list.push((*label, read_fst(path)?));
+        }
+        replace::<_, VectorFst<_>, _, _>(fst_list, self.root_label, self.epsilon_on_replace)
+    }
+}
+
+impl ReplaceAlgorithm {
+    /// `args` follows the OpenFST convention :
+    /// `root.fst rootlabel [rule1.fst label1 ...] [out.fst]`.
+    /// The output is written to stdout when `out.fst` is omitted.
+    pub fn new(args: &[&str], epsilon_on_replace: bool) -> Result<Self> {
+        if args.len() < 2 {
+            bail!("Usage : replace root.fst rootlabel [rule1.fst label1 ...] [out.fst]");
+        }
+        let (pairs, path_out) = if args.len() % 2 == 1 {
+            (&args[..args.len() - 1], args[args.len() - 1])
+        } else {
+            (args, STDIO_PATH)
+        };
+        let mut rules = vec![];
+        for pair in pairs.chunks(2) {
+            rules.push((pair[0].to_string(), pair[1].parse()?));
+        }
+        check_single_stdin(&rules.iter().map(|(p, _)| p.as_str()).collect::<Vec<_>>())?;
+        let (path_root, root_label) = rules.remove(0);
+        Ok(Self {
+            path_root,
+            root_label,
+            rules,
+            path_out: path_out.to_string(),
+            epsilon_on_replace,
+        })
+    }
+}
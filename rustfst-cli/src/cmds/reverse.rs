@@ -2,6 +2,7 @@ use rustfst::prelude::*;
 
 use anyhow::Result;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct ReverseAlgorithm {
@@ -9,7 +10,9 @@ pub struct ReverseAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for ReverseAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ReverseAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -22,7 +25,7 @@ impl UnaryFstAlgorithm for ReverseAlgorithm {
         "reverse".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         reverse(&fst)
     }
 }
@@ -0,0 +1,52 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::cmds::utils::read_potentials;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct ReweightAlgorithm {
+    path_in: String,
+    path_potentials: String,
+    path_out: String,
+    to_final: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ReweightAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "reweight".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        let potentials = read_potentials(self.path_potentials.as_str())?;
+        let reweight_type = if self.to_final {
+            ReweightType::ReweightToFinal
+        } else {
+            ReweightType::ReweightToInitial
+        };
+        reweight(&mut fst, &potentials, reweight_type)?;
+        Ok(fst)
+    }
+}
+
+impl ReweightAlgorithm {
+    pub fn new(path_in: &str, path_potentials: &str, path_out: &str, to_final: bool) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_potentials: path_potentials.to_string(),
+            path_out: path_out.to_string(),
+            to_final,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+
+use rustfst::algorithms::rm_epsilon::rm_epsilon;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct RmEpsilonAlgorithm {
+    path_in: String,
+    path_out: String,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for RmEpsilonAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "rmepsilon".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        rm_epsilon(&mut fst)?;
+        Ok(fst)
+    }
+}
+
+impl RmEpsilonAlgorithm {
+    pub fn new(path_in: &str, path_out: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct RmFinalEpsilonAlgorithm {
@@ -9,7 +10,9 @@ pub struct RmFinalEpsilonAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for RmFinalEpsilonAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for RmFinalEpsilonAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -22,10 +25,7 @@ impl UnaryFstAlgorithm for RmFinalEpsilonAlgorithm {
         "rm final epsilon".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         rm_final_epsilon(&mut fst)?;
         Ok(fst)
     }
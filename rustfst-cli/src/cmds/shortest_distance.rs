@@ -0,0 +1,47 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct ShortestDistanceAlgorithm {
+    path_in: String,
+    path_out: String,
+    reverse: bool,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ShortestDistanceAlgorithm {
+    type Output = String;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "shortestdistance".to_string()
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output> {
+        let distances = shortest_distance(&fst, self.reverse)?;
+        let mut res = String::new();
+        for (state, distance) in distances.iter().enumerate() {
+            res.push_str(&format!("{}\t{}\n", state, distance));
+        }
+        Ok(res)
+    }
+}
+
+impl ShortestDistanceAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, reverse: bool) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            reverse,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 use anyhow::Result;
 
@@ -10,7 +11,9 @@ pub struct ShortestPathAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for ShortestPathAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for ShortestPathAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -23,7 +26,7 @@ impl UnaryFstAlgorithm for ShortestPathAlgorithm {
         "shortest path".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let config = ShortestPathConfig::default()
             .with_nshortest(self.nshortest)
             .with_unique(self.unique);
@@ -0,0 +1,53 @@
+use anyhow::{bail, Result};
+
+use crate::cmds::utils::read_pairs;
+
+use rustfst::prelude::*;
+
+use crate::arc_type::CliSemiring;
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct StateSortAlgorithm {
+    path_in: String,
+    path_out: String,
+    order: String,
+}
+
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for StateSortAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "statesort".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<Self::Output> {
+        let pairs = read_pairs(Some(self.order.as_str()))?;
+        let mut order = vec![0; pairs.len()];
+        for (state, new_state) in pairs {
+            if state as usize >= order.len() {
+                bail!("State {} out of range in the order file", state);
+            }
+            order[state as usize] = new_state;
+        }
+        state_sort(&mut fst, &order)?;
+        Ok(fst)
+    }
+}
+
+impl StateSortAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, order: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            order: order.to_string(),
+        }
+    }
+}
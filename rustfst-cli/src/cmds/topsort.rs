@@ -2,6 +2,7 @@ use rustfst::prelude::*;
 
 use anyhow::Result;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct TopsortAlgorithm {
@@ -9,7 +10,9 @@ pub struct TopsortAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for TopsortAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for TopsortAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -22,10 +25,7 @@ impl UnaryFstAlgorithm for TopsortAlgorithm {
         "topsort".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         top_sort(&mut fst)?;
         Ok(fst)
     }
@@ -2,6 +2,7 @@ use anyhow::{bail, Result};
 
 use rustfst::prelude::*;
 
+use crate::arc_type::CliSemiring;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
 
 pub struct TrsortAlgorithm {
@@ -10,7 +11,9 @@ pub struct TrsortAlgorithm {
     path_out: String,
 }
 
-impl UnaryFstAlgorithm for TrsortAlgorithm {
+impl<W: CliSemiring> UnaryFstAlgorithm<W> for TrsortAlgorithm {
+    type Output = VectorFst<W>;
+
     fn get_path_in(&self) -> &str {
         self.path_in.as_str()
     }
@@ -23,10 +26,7 @@ impl UnaryFstAlgorithm for TrsortAlgorithm {
         "tr_sort".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         match self.sort_type.as_str() {
             "ilabel" => tr_sort(&mut fst, ILabelCompare {}),
             "olabel" => tr_sort(&mut fst, OLabelCompare {}),
@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use rustfst::algorithms::union::union;
+use rustfst::fst_impls::VectorFst;
+
+use crate::arc_type::CliSemiring;
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+
+pub struct UnionAlgorithm {
+    path_in_1: String,
+    path_in_2: String,
+    path_out: String,
+}
+
+impl<W: CliSemiring> BinaryFstAlgorithm<W> for UnionAlgorithm {
+    type Output = VectorFst<W>;
+
+    fn get_path_in_1(&self) -> &str {
+        self.path_in_1.as_str()
+    }
+
+    fn get_path_in_2(&self) -> &str {
+        self.path_in_2.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "union".to_string()
+    }
+
+    fn run_algorithm(&self, mut fst_1: VectorFst<W>, fst_2: VectorFst<W>) -> Result<Self::Output> {
+        union(&mut fst_1, &fst_2)?;
+        Ok(fst_1)
+    }
+}
+
+impl UnionAlgorithm {
+    pub fn new(path_in_1: &str, path_in_2: &str, path_out: &str) -> Self {
+        Self {
+            path_in_1: path_in_1.to_string(),
+            path_in_2: path_in_2.to_string(),
+            path_out: path_out.to_string(),
+        }
+    }
+}
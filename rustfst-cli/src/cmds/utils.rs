@@ -0,0 +1,56 @@
+use anyhow::{format_err, Context, Result};
+
+use rustfst::prelude::*;
+
+fn parse_field<T: std::str::FromStr>(
+    field: Option<&str>,
+    path: &str,
+    line_idx: usize,
+) -> Result<T> {
+    field
+        .and_then(|f| f.parse().ok())
+        .ok_or_else(|| format_err!("{} : invalid line {}", path, line_idx + 1))
+}
+
+/// Reads a file of whitespace separated pairs of labels, one pair per line.
+/// Returns an empty list when no path is provided.
+pub fn read_pairs(path: Option<&str>) -> Result<Vec<(Label, Label)>> {
+    let path = match path {
+        Some(p) => p,
+        None => return Ok(vec![]),
+    };
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Can't open file : {:?}", path))?;
+    let mut pairs = vec![];
+    for (line_idx, line) in content.lines().enumerate() {
+        let mut fields = line.split_whitespace();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let a = parse_field(fields.next(), path, line_idx)?;
+        let b = parse_field(fields.next(), path, line_idx)?;
+        pairs.push((a, b));
+    }
+    Ok(pairs)
+}
+
+/// Reads a file of whitespace separated pairs of state and weight, one pair per line.
+/// The states missing from the file have a zero weight.
+pub fn read_potentials<W: Semiring<Type = f32>>(path: &str) -> Result<Vec<W>> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Can't open file : {:?}", path))?;
+    let mut potentials = vec![];
+    for (line_idx, line) in content.lines().enumerate() {
+        let mut fields = line.split_whitespace();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let state: StateId = parse_field(fields.next(), path, line_idx)?;
+        let weight: f32 = parse_field(fields.next(), path, line_idx)?;
+        if potentials.len() <= state as usize {
+            potentials.resize(state as usize + 1, W::zero());
+        }
+        potentials[state as usize] = W::new(weight);
+    }
+    Ok(potentials)
+}
@@ -4,26 +4,52 @@ use anyhow::{format_err, Result};
 use clap::parser::ValueSource;
 use clap::{Arg, ArgAction, Command};
 use log::error;
+use rustfst::{DrawingConfig, MissingSymbolPolicy, TextWriteConfig};
 
-use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::arc_type::ArcType;
+use crate::binary_fst_algorithm::run_binary;
+use crate::cmds::closure::ClosureAlgorithm;
+use crate::cmds::compile::CompileAlgorithm;
 use crate::cmds::compose::ComposeAlgorithm;
+use crate::cmds::concat::ConcatAlgorithm;
 use crate::cmds::connect::ConnectAlgorithm;
+use crate::cmds::convert::ConvertAlgorithm;
+use crate::cmds::decode::DecodeAlgorithm;
 use crate::cmds::determinize::DeterminizeAlgorithm;
+use crate::cmds::draw::DrawAlgorithm;
+use crate::cmds::encode::EncodeAlgorithm;
+use crate::cmds::equivalent::EquivalentAlgorithm;
+use crate::cmds::info::InfoAlgorithm;
 use crate::cmds::invert::InvertAlgorithm;
+use crate::cmds::isomorphic::IsomorphicAlgorithm;
 use crate::cmds::map::MapAlgorithm;
 use crate::cmds::minimize::MinimizeAlgorithm;
 use crate::cmds::optimize::OptimizeAlgorithm;
+use crate::cmds::print::PrintAlgorithm;
 use crate::cmds::project::ProjectFstAlgorithm;
+use crate::cmds::prune::PruneAlgorithm;
 use crate::cmds::push::PushAlgorithm;
+use crate::cmds::randgen::RandGenAlgorithm;
+use crate::cmds::relabel::RelabelAlgorithm;
+use crate::cmds::replace::ReplaceAlgorithm;
 use crate::cmds::reverse::ReverseAlgorithm;
+use crate::cmds::reweight::ReweightAlgorithm;
+use crate::cmds::rm_epsilon::RmEpsilonAlgorithm;
 use crate::cmds::rm_final_epsilon::RmFinalEpsilonAlgorithm;
+use crate::cmds::shortest_distance::ShortestDistanceAlgorithm;
 use crate::cmds::shortest_path::ShortestPathAlgorithm;
+use crate::cmds::state_sort::StateSortAlgorithm;
+use crate::cmds::synchronize::SynchronizeAlgorithm;
 use crate::cmds::topsort::TopsortAlgorithm;
 use crate::cmds::tr_sort::TrsortAlgorithm;
-use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::cmds::union::UnionAlgorithm;
+use crate::unary_fst_algorithm::{run_unary, run_unary_with_arc_type};
 
+pub mod algorithm_output;
+pub mod arc_type;
 pub mod binary_fst_algorithm;
 pub mod cmds;
+pub mod stdio;
 pub mod unary_fst_algorithm;
 
 fn main() {
@@ -117,6 +143,8 @@ fn main() {
                     "quantize",
                     "rmweight",
                     "times",
+                    "to_log",
+                    "to_std",
                 ])
                 .default_value("identity")
                 .help("Map operation.")
@@ -192,6 +220,481 @@ fn main() {
     );
     app = app.subcommand(two_in_one_out_options(compose_cmd));
 
+    // Compile
+    let compile_cmd = Command::new("compile")
+        .about("Compiles a text fst, possibly with symbols, into a binary fst.")
+        .arg(
+            Arg::new("isymbols")
+                .long("isymbols")
+                .help("Input label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("osymbols")
+                .long("osymbols")
+                .help("Output label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("ssymbols")
+                .long("ssymbols")
+                .help("State label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("acceptor")
+                .long("acceptor")
+                .help("Input in acceptor format.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("keep_isymbols")
+                .long("keep_isymbols")
+                .help("Store the input label symbol table in the fst.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("keep_osymbols")
+                .long("keep_osymbols")
+                .help("Store the output label symbol table in the fst.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("allow_unknown_symbols")
+                .long("allow_unknown_symbols")
+                .help("Add the symbols missing from the symbol tables.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(one_in_one_out_options(compile_cmd));
+
+    // Print
+    let print_cmd = Command::new("print")
+        .about("Prints a binary fst in text format.")
+        .arg(
+            Arg::new("isymbols")
+                .long("isymbols")
+                .help("Input label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("osymbols")
+                .long("osymbols")
+                .help("Output label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("ssymbols")
+                .long("ssymbols")
+                .help("State label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("acceptor")
+                .long("acceptor")
+                .help("Output in acceptor format.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("numeric")
+                .long("numeric")
+                .help("Print numeric labels.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("show_weight_one")
+                .long("show_weight_one")
+                .help("Print/draw transition weights and final weights equal to One.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("missing_symbol")
+                .long("missing_symbol")
+                .help("Symbol to print when lookup fails (default raises error).")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_text_out_options(print_cmd));
+
+    // Draw
+    let draw_cmd = Command::new("draw")
+        .about("Draws a binary fst in the DOT format.")
+        .arg(
+            Arg::new("isymbols")
+                .long("isymbols")
+                .help("Input label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("osymbols")
+                .long("osymbols")
+                .help("Output label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("acceptor")
+                .long("acceptor")
+                .help("Input in acceptor format.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("title")
+                .long("title")
+                .help("Set figure title.")
+                .default_value("")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("portrait")
+                .long("portrait")
+                .help("Portrait mode (def: landscape).")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("vertical")
+                .long("vertical")
+                .help("Draw bottom-to-top instead of left-to-right.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("fontsize")
+                .long("fontsize")
+                .help("Set fontsize.")
+                .default_value("14")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("height")
+                .long("height")
+                .help("Set height.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("width")
+                .long("width")
+                .help("Set width.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("nodesep")
+                .long("nodesep")
+                .help("Set minimum separation between nodes (see dot documentation).")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("ranksep")
+                .long("ranksep")
+                .help("Set minimum separation between ranks (see dot documentation).")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("show_weight_one")
+                .long("show_weight_one")
+                .help("Print/draw transition weights and final weights equal to One.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ssymbols")
+                .long("ssymbols")
+                .help("State label symbol table.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("precision")
+                .long("precision")
+                .help("Set the number of decimals of the weights.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("max_states")
+                .long("max_states")
+                .help("Only draw this number of states.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("allow_negative_labels")
+                .long("allow_negative_labels")
+                .help("Allow negative labels (not recommended; may cause conflicts).")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(one_in_one_out_options(draw_cmd));
+
+    // Info
+    let info_cmd = Command::new("info")
+        .about("Prints out information about an fst.")
+        .arg(
+            Arg::new("long")
+                .long("long")
+                .help("Compute all the properties instead of reporting the ones stored in the fst.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Output the report in JSON.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(one_in_text_out_options(info_cmd));
+
+    // Union
+    let union_cmd = Command::new("union").about("Union algorithm.");
+    app = app.subcommand(two_in_one_out_options(union_cmd));
+
+    // Concat
+    let concat_cmd = Command::new("concat").about("Concat algorithm.");
+    app = app.subcommand(two_in_one_out_options(concat_cmd));
+
+    // Closure
+    let closure_cmd = Command::new("closure").about("Closure algorithm.").arg(
+        Arg::new("closure_plus")
+            .long("closure_plus")
+            .help("Do not add the empty path (T+ instead of T*).")
+            .action(ArgAction::SetTrue),
+    );
+    app = app.subcommand(one_in_one_out_options(closure_cmd));
+
+    // RmEpsilon
+    let rm_epsilon_cmd = Command::new("rmepsilon").about("RmEpsilon algorithm.");
+    app = app.subcommand(one_in_one_out_options(rm_epsilon_cmd));
+
+    // Replace
+    let replace_cmd = Command::new("replace")
+        .about("Replace algorithm.")
+        .arg(
+            Arg::new("args")
+                .help("root.fst rootlabel [rule1.fst label1 ...] [out.fst]")
+                .required(true)
+                .num_args(2..)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("epsilon_on_replace")
+                .long("epsilon_on_replace")
+                .help("Call/return transitions are epsilon transitions.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(bench_options(replace_cmd));
+
+    // Encode
+    let encode_cmd = Command::new("encode")
+        .about("Encodes the labels and/or weights of an fst as single labels.")
+        .arg(
+            Arg::new("encoder")
+                .long("encoder")
+                .required(true)
+                .help("Path to the encoder file, written unless --encode_reuse is set.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("encode_labels")
+                .long("encode_labels")
+                .help("Encode output labels.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("encode_weights")
+                .long("encode_weights")
+                .help("Encode weights.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("encode_reuse")
+                .long("encode_reuse")
+                .help("Re-use the existing encoder instead of creating a new one.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(one_in_one_out_options(encode_cmd));
+
+    // Decode
+    let decode_cmd = Command::new("decode")
+        .about("Decodes an fst encoded with the encode subcommand.")
+        .arg(
+            Arg::new("encoder")
+                .long("encoder")
+                .required(true)
+                .help("Path to the encoder file.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_one_out_options(decode_cmd));
+
+    // RandGen
+    let randgen_cmd = Command::new("randgen")
+        .about("Generates random paths through an fst.")
+        .arg(
+            Arg::new("select")
+                .long("select")
+                .value_parser(["uniform", "log_prob", "fast_log_prob"])
+                .default_value("uniform")
+                .help("Transition selector.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .help("Random seed.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("npath")
+                .long("npath")
+                .default_value("1")
+                .help("Number of paths to generate.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("max_length")
+                .long("max_length")
+                .help("Maximum path length.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("weighted")
+                .long("weighted")
+                .help("Output tree weighted by path count vs. unweighted paths.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("remove_total_weight")
+                .long("remove_total_weight")
+                .help("Remove total weight when output weighted.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(one_in_one_out_options(randgen_cmd));
+
+    // Relabel
+    let relabel_cmd = Command::new("relabel")
+        .about("Relabels the input and/or output labels of an fst.")
+        .arg(
+            Arg::new("relabel_ipairs")
+                .long("relabel_ipairs")
+                .help("Input relabel pairs (numeric).")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("relabel_opairs")
+                .long("relabel_opairs")
+                .help("Output relabel pairs (numeric).")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_one_out_options(relabel_cmd));
+
+    // ShortestDistance
+    let shortest_distance_cmd = Command::new("shortestdistance")
+        .about("Prints the shortest distance of each state.")
+        .arg(
+            Arg::new("reverse")
+                .long("reverse")
+                .help("Perform in the reverse direction.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(one_in_text_out_options(shortest_distance_cmd));
+
+    // Reweight
+    let reweight_cmd = Command::new("reweight")
+        .about("Reweight algorithm.")
+        .arg(
+            Arg::new("in.fst")
+                .help("Path to input fst file, `-` for stdin.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("potentials.txt")
+                .help("Path to the potentials file.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("out.fst")
+                .help("Path to output fst file. Written to stdout if omitted or `-`.")
+                .default_value("-")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("to_final")
+                .long("to_final")
+                .help("Push/reweight to final (vs. to initial) states.")
+                .action(ArgAction::SetTrue),
+        );
+    app = app.subcommand(bench_options(reweight_cmd));
+
+    // Equivalent
+    let equivalent_cmd = Command::new("equivalent")
+        .about("Determines if two fsts are equivalent. They are encoded, determinized and trimmed first.")
+        .arg(
+            Arg::new("delta")
+                .long("delta")
+                .default_value("0.0009765625")
+                .help("Comparison/quantization delta.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(two_in_options(equivalent_cmd));
+
+    // Isomorphic
+    let isomorphic_cmd = Command::new("isomorphic")
+        .about("Determines if two fsts are isomorphic.")
+        .arg(
+            Arg::new("delta")
+                .long("delta")
+                .default_value("0.0009765625")
+                .help("Comparison/quantization delta.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(two_in_options(isomorphic_cmd));
+
+    // StateSort
+    let state_sort_cmd = Command::new("statesort")
+        .about("Sorts the states of an fst.")
+        .arg(
+            Arg::new("order")
+                .long("order")
+                .help("File of pairs (state, new state).")
+                .required(true)
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_one_out_options(state_sort_cmd));
+
+    // Synchronize
+    let synchronize_cmd = Command::new("synchronize").about("Synchronize algorithm.");
+    app = app.subcommand(one_in_one_out_options(synchronize_cmd));
+
+    // Prune
+    let prune_cmd = Command::new("prune")
+        .about("Prune algorithm.")
+        .arg(
+            Arg::new("weight")
+                .long("weight")
+                .default_value("Infinity")
+                .help("Weight parameter.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("nstate")
+                .long("nstate")
+                .default_value("-1")
+                .help("State number parameter.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("delta")
+                .long("delta")
+                .default_value("0.0009765625")
+                .help("Comparison/quantization delta.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_one_out_options(prune_cmd));
+
+    // Convert
+    let convert_cmd = Command::new("convert")
+        .about("Converts an fst to another type.")
+        .arg(
+            Arg::new("fst_type")
+                .long("fst_type")
+                .value_parser(["vector", "const"])
+                .default_value("vector")
+                .help("Output fst type.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_one_out_options(convert_cmd));
+
     let matches = app.get_matches();
 
     let env = env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "debug");
@@ -202,119 +705,453 @@ fn main() {
 
     if let Err(e) = handle(matches) {
         error!("{:?}", e);
-        process::exit(exitcode::OK)
+        process::exit(exitcode::SOFTWARE)
     }
 }
 
 /// Handles the command-line input.
 fn handle(matches: clap::ArgMatches) -> Result<()> {
     match matches.subcommand() {
-        Some(("minimize", m)) => MinimizeAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.value_source("allow-nondet") == Some(ValueSource::CommandLine),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("determinize", m)) => DeterminizeAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-            m.get_one::<String>("det_type").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("connect", m)) => ConnectAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("tr_sort", m)) => TrsortAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("sort_type").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("optimize", m)) => OptimizeAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("project", m)) => ProjectFstAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.value_source("project-output") == Some(ValueSource::CommandLine),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("invert", m)) => InvertAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("topsort", m)) => TopsortAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("reverse", m)) => ReverseAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("map", m)) => MapAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("map_type").unwrap(),
-            m.get_one::<String>("weight").map(|s| s.as_str()),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("shortestpath", m)) => ShortestPathAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.value_source("unique") == Some(ValueSource::CommandLine),
-            m.get_one::<String>("nshortest").unwrap().parse().unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("rmfinalepsilon", m)) => RmFinalEpsilonAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-        )
-        .run_cli_or_bench(m),
-        Some(("push", m)) => PushAlgorithm::new(
-            m.get_one::<String>("in.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-            m.value_source("to_final") == Some(ValueSource::CommandLine),
-            m.value_source("push_weights") == Some(ValueSource::CommandLine),
-            m.value_source("push_labels") == Some(ValueSource::CommandLine),
-            m.value_source("remove_total_weight") == Some(ValueSource::CommandLine),
-            m.value_source("remove_common_affix") == Some(ValueSource::CommandLine),
-        )
-        .run_cli_or_bench(m),
-        Some(("compose", m)) => ComposeAlgorithm::new(
-            m.get_one::<String>("in_1.fst").unwrap(),
-            m.get_one::<String>("in_2.fst").unwrap(),
-            m.get_one::<String>("out.fst").unwrap(),
-            m.get_one::<String>("compose_type").unwrap(),
-        )
-        .run_cli_or_bench(m),
+        Some(("minimize", m)) => run_unary(
+            &MinimizeAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.value_source("allow-nondet") == Some(ValueSource::CommandLine),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("determinize", m)) => run_unary(
+            &DeterminizeAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("det_type").unwrap(),
+            ),
+            m,
+        ),
+        Some(("connect", m)) => run_unary(
+            &ConnectAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("tr_sort", m)) => run_unary(
+            &TrsortAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("sort_type").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("optimize", m)) => run_unary(
+            &OptimizeAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("project", m)) => run_unary(
+            &ProjectFstAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.value_source("project-output") == Some(ValueSource::CommandLine),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("invert", m)) => run_unary(
+            &InvertAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("topsort", m)) => run_unary(
+            &TopsortAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("reverse", m)) => run_unary(
+            &ReverseAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("map", m)) => run_unary(
+            &MapAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("map_type").unwrap(),
+                m.get_one::<String>("weight").map(|s| s.as_str()),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("shortestpath", m)) => run_unary(
+            &ShortestPathAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.value_source("unique") == Some(ValueSource::CommandLine),
+                m.get_one::<String>("nshortest").unwrap().parse().unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("rmfinalepsilon", m)) => run_unary(
+            &RmFinalEpsilonAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("push", m)) => run_unary(
+            &PushAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.value_source("to_final") == Some(ValueSource::CommandLine),
+                m.value_source("push_weights") == Some(ValueSource::CommandLine),
+                m.value_source("push_labels") == Some(ValueSource::CommandLine),
+                m.value_source("remove_total_weight") == Some(ValueSource::CommandLine),
+                m.value_source("remove_common_affix") == Some(ValueSource::CommandLine),
+            ),
+            m,
+        ),
+        Some(("compose", m)) => run_binary(
+            &ComposeAlgorithm::new(
+                m.get_one::<String>("in_1.fst").unwrap(),
+                m.get_one::<String>("in_2.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("compose_type").unwrap(),
+            ),
+            m,
+        ),
+        Some(("compile", m)) => run_unary_with_arc_type(
+            &CompileAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("isymbols").map(|s| s.as_str()),
+                m.get_one::<String>("osymbols").map(|s| s.as_str()),
+                m.get_one::<String>("ssymbols").map(|s| s.as_str()),
+                m.get_flag("acceptor"),
+                m.get_flag("keep_isymbols"),
+                m.get_flag("keep_osymbols"),
+                m.get_flag("allow_unknown_symbols"),
+            )?,
+            m,
+            ArcType::from_name(
+                m.get_one::<String>("arc_type")
+                    .map_or("standard", |s| s.as_str()),
+            )?,
+        ),
+        Some(("print", m)) => {
+            let config = TextWriteConfig {
+                use_symbols: !m.get_flag("numeric"),
+                acceptor: m.get_flag("acceptor"),
+                show_weight_one: m.get_flag("show_weight_one"),
+                missing_symbol_policy: match m.get_one::<String>("missing_symbol") {
+                    Some(symbol) => MissingSymbolPolicy::Symbol(symbol.to_string()),
+                    None => MissingSymbolPolicy::Error,
+                },
+                ..TextWriteConfig::default()
+            };
+            run_unary(
+                &PrintAlgorithm::new(
+                    m.get_one::<String>("in.fst").unwrap(),
+                    m.get_one::<String>("out.txt").unwrap(),
+                    m.get_one::<String>("isymbols").map(|s| s.as_str()),
+                    m.get_one::<String>("osymbols").map(|s| s.as_str()),
+                    m.get_one::<String>("ssymbols").map(|s| s.as_str()),
+                    config,
+                )?,
+                m,
+            )
+        }
+        Some(("draw", m)) => {
+            let config = DrawingConfig {
+                vertical: m.get_flag("vertical"),
+                size: match (m.get_one::<String>("width"), m.get_one::<String>("height")) {
+                    (Some(w), Some(h)) => Some((w.parse()?, h.parse()?)),
+                    _ => None,
+                },
+                title: m.get_one::<String>("title").unwrap().to_string(),
+                portrait: m.get_flag("portrait"),
+                ranksep: m
+                    .get_one::<String>("ranksep")
+                    .map(|s| s.parse())
+                    .transpose()?,
+                nodesep: m
+                    .get_one::<String>("nodesep")
+                    .map(|s| s.parse())
+                    .transpose()?,
+                fontsize: m.get_one::<String>("fontsize").unwrap().parse()?,
+                acceptor: m.get_flag("acceptor"),
+                show_weight_one: m.get_flag("show_weight_one"),
+                print_weight: true,
+                max_states: m
+                    .get_one::<String>("max_states")
+                    .map(|s| s.parse())
+                    .transpose()?,
+                float_precision: m
+                    .get_one::<String>("precision")
+                    .map(|s| s.parse())
+                    .transpose()?,
+                allow_negative_labels: m.get_flag("allow_negative_labels"),
+                ..DrawingConfig::default()
+            };
+            run_unary(
+                &DrawAlgorithm::new(
+                    m.get_one::<String>("in.fst").unwrap(),
+                    m.get_one::<String>("out.fst").unwrap(),
+                    m.get_one::<String>("isymbols").map(|s| s.as_str()),
+                    m.get_one::<String>("osymbols").map(|s| s.as_str()),
+                    m.get_one::<String>("ssymbols").map(|s| s.as_str()),
+                    config,
+                )?,
+                m,
+            )
+        }
+        Some(("info", m)) => run_unary(
+            &InfoAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.txt").unwrap(),
+                m.get_flag("long"),
+                m.get_flag("json"),
+            ),
+            m,
+        ),
+        Some(("union", m)) => run_binary(
+            &UnionAlgorithm::new(
+                m.get_one::<String>("in_1.fst").unwrap(),
+                m.get_one::<String>("in_2.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("concat", m)) => run_binary(
+            &ConcatAlgorithm::new(
+                m.get_one::<String>("in_1.fst").unwrap(),
+                m.get_one::<String>("in_2.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("closure", m)) => run_unary(
+            &ClosureAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_flag("closure_plus"),
+            ),
+            m,
+        ),
+        Some(("rmepsilon", m)) => run_unary(
+            &RmEpsilonAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("replace", m)) => {
+            let args: Vec<&str> = m
+                .get_many::<String>("args")
+                .unwrap()
+                .map(|s| s.as_str())
+                .collect();
+            run_unary(
+                &ReplaceAlgorithm::new(&args, m.get_flag("epsilon_on_replace"))?,
+                m,
+            )
+        }
+        Some(("encode", m)) => run_unary(
+            &EncodeAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("encoder").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_flag("encode_labels"),
+                m.get_flag("encode_weights"),
+                m.get_flag("encode_reuse"),
+            ),
+            m,
+        ),
+        Some(("decode", m)) => run_unary(
+            &DecodeAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("encoder").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("randgen", m)) => run_unary(
+            &RandGenAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("select").unwrap(),
+                m.get_one::<String>("seed").map(|s| s.as_str()),
+                m.get_one::<String>("npath").unwrap(),
+                m.get_one::<String>("max_length").map(|s| s.as_str()),
+                m.get_flag("weighted"),
+                m.get_flag("remove_total_weight"),
+            ),
+            m,
+        ),
+        Some(("relabel", m)) => run_unary(
+            &RelabelAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("relabel_ipairs").map(|s| s.as_str()),
+                m.get_one::<String>("relabel_opairs").map(|s| s.as_str()),
+            ),
+            m,
+        ),
+        Some(("shortestdistance", m)) => run_unary(
+            &ShortestDistanceAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.txt").unwrap(),
+                m.get_flag("reverse"),
+            ),
+            m,
+        ),
+        Some(("reweight", m)) => run_unary(
+            &ReweightAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("potentials.txt").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_flag("to_final"),
+            ),
+            m,
+        ),
+        Some(("equivalent", m)) => run_binary(
+            &EquivalentAlgorithm::new(
+                m.get_one::<String>("in_1.fst").unwrap(),
+                m.get_one::<String>("in_2.fst").unwrap(),
+                m.get_one::<String>("delta").unwrap(),
+            ),
+            m,
+        ),
+        Some(("isomorphic", m)) => run_binary(
+            &IsomorphicAlgorithm::new(
+                m.get_one::<String>("in_1.fst").unwrap(),
+                m.get_one::<String>("in_2.fst").unwrap(),
+                m.get_one::<String>("delta").unwrap(),
+            ),
+            m,
+        ),
+        Some(("statesort", m)) => run_unary(
+            &StateSortAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("order").unwrap(),
+            ),
+            m,
+        ),
+        Some(("synchronize", m)) => run_unary(
+            &SynchronizeAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+            ),
+            m,
+        ),
+        Some(("prune", m)) => run_unary(
+            &PruneAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("weight").unwrap(),
+                m.get_one::<String>("nstate").unwrap(),
+                m.get_one::<String>("delta").unwrap(),
+            ),
+            m,
+        ),
+        Some(("convert", m)) => run_unary(
+            &ConvertAlgorithm::new(
+                m.get_one::<String>("in.fst").unwrap(),
+                m.get_one::<String>("out.fst").unwrap(),
+                m.get_one::<String>("fst_type").unwrap(),
+            ),
+            m,
+        ),
         Some((s, _)) => Err(format_err!("Unknown subcommand {}.", s)),
         None => Err(format_err!("Unknown None")),
     }
 }
 
 fn one_in_one_out_options(command: Command) -> Command {
-    command
-        .version("1.0")
-        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+    let command = command
+        .arg(
+            Arg::new("in.fst")
+                .help("Path to input fst file. Read from stdin if omitted or `-`.")
+                .default_value("-")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("out.fst")
+                .help("Path to output fst file. Written to stdout if omitted or `-`.")
+                .default_value("-")
+                .action(ArgAction::Set),
+        );
+    bench_options(command)
+}
+
+fn one_in_text_out_options(command: Command) -> Command {
+    let command = command
         .arg(
             Arg::new("in.fst")
-                .help("Path to input fst file.")
+                .help("Path to input fst file. Read from stdin if omitted or `-`.")
+                .default_value("-")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("out.txt")
+                .help("Path to output text file. Written to stdout if omitted or `-`.")
+                .default_value("-")
+                .action(ArgAction::Set),
+        );
+    bench_options(command)
+}
+
+fn two_in_one_out_options(command: Command) -> Command {
+    let command = command
+        .arg(
+            Arg::new("in_1.fst")
+                .help("Path to the first input fst file, `-` for stdin.")
+                .required(true)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("in_2.fst")
+                .help("Path to the second input fst file, `-` for stdin.")
                 .required(true)
                 .action(ArgAction::Set),
         )
         .arg(
             Arg::new("out.fst")
-                .help("Path to output fst file.")
+                .help("Path to output fst file. Written to stdout if omitted or `-`.")
+                .default_value("-")
+                .action(ArgAction::Set),
+        );
+    bench_options(command)
+}
+
+fn two_in_options(command: Command) -> Command {
+    let command = command
+        .arg(
+            Arg::new("in_1.fst")
+                .help("Path to the first input fst file, `-` for stdin.")
                 .required(true)
                 .action(ArgAction::Set),
-        ).arg(
+        )
+        .arg(
+            Arg::new("in_2.fst")
+                .help("Path to the second input fst file, `-` for stdin.")
+                .required(true)
+                .action(ArgAction::Set),
+        );
+    bench_options(command)
+}
+
+fn bench_options(command: Command) -> Command {
+    command
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
             Arg::new("bench")
                 .long("bench")
                 .help("Whether to run multiple times the algorithm in order to have a reliable time measurement.")
@@ -332,54 +1169,14 @@ fn one_in_one_out_options(command: Command) -> Command {
                 .help("Number of warm ups run before the actual benchmark.")
                 .action(ArgAction::Set)
         ).arg(
-        Arg::new("export-markdown")
-            .long("export-markdown")
-            .action(ArgAction::Set)
-    )
-}
-
-fn two_in_one_out_options(command: Command) -> clap::Command {
-    command
-        .version("1.0")
-        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
-        .arg(
-            Arg::new("in_1.fst")
-                .help("Path to the first input fst file.")
-                .required(true)
-                .action(ArgAction::Set),
-
-        )
-        .arg(
-            Arg::new("in_2.fst")
-                .help("Path to the second input fst file.")
-                .required(true)
-                .action(ArgAction::Set),
-        )
-        .arg(
-            Arg::new("out.fst")
-                .help("Path to output fst file.")
-                .required(true)
-                .action(ArgAction::Set),
+            Arg::new("export-markdown")
+                .long("export-markdown")
+                .action(ArgAction::Set)
         ).arg(
-        Arg::new("bench")
-            .long("bench")
-            .help("Whether to run multiple times the algorithm in order to have a reliable time measurement.")
-            .action(ArgAction::Set)
-    ).arg(
-        Arg::new("n_iters")
-            .long("n_iters")
-            .default_value("10")
-            .help("Number of iterations to run for the benchmark.")
-            .action(ArgAction::Set)
-    ).arg(
-        Arg::new("n_warm_ups")
-            .long("n_warm_ups")
-            .default_value("3")
-            .help("Number of warm ups run before the actual benchmark.")
-            .action(ArgAction::Set)
-    ).arg(
-        Arg::new("export-markdown")
-            .long("export-markdown")
-            .action(ArgAction::Set)
-    )
+            Arg::new("arc_type")
+                .long("arc_type")
+                .value_parser(["standard", "tropical", "log"])
+                .help("Arc type of the fsts. Read from the header of the binary input if omitted, `standard` for text input.")
+                .action(ArgAction::Set)
+        )
 }
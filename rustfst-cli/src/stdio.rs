@@ -0,0 +1,88 @@
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+
+use rustfst::prelude::*;
+
+/// Path standing for stdin when reading and stdout when writing.
+pub const STDIO_PATH: &str = "-";
+
+pub fn is_stdio(path: &str) -> bool {
+    path == STDIO_PATH
+}
+
+/// Content of stdin. It is read at once and kept in memory as it can be needed several times,
+/// e.g to read the arc type in the header of a binary fst before parsing it.
+pub fn stdin_data() -> Result<&'static [u8]> {
+    static STDIN_DATA: OnceLock<Vec<u8>> = OnceLock::new();
+    if let Some(data) = STDIN_DATA.get() {
+        return Ok(data);
+    }
+    let mut data = vec![];
+    let stdin = std::io::stdin();
+    stdin
+        .lock()
+        .read_to_end(&mut data)
+        .context("Can't read stdin")?;
+    Ok(STDIN_DATA.get_or_init(|| data))
+}
+
+/// Reads a binary fst from a file, or from stdin if `path` is `-`.
+pub fn read_fst<W, F>(path: &str) -> Result<F>
+where
+    W: SerializableSemiring,
+    F: SerializableFst<W>,
+{
+    if is_stdio(path) {
+        F::load_from_reader(stdin_data()?)
+    } else {
+        F::read(path)
+    }
+}
+
+/// Writes a binary fst to a file, or to stdout if `path` is `-`.
+pub fn write_fst<W, F>(fst: &F, path: &str) -> Result<()>
+where
+    W: SerializableSemiring,
+    F: SerializableFst<W>,
+{
+    if is_stdio(path) {
+        let stdout = std::io::stdout();
+        let mut writer = BufWriter::new(stdout.lock());
+        fst.store(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    } else {
+        fst.write(path)
+    }
+}
+
+/// Reads a whole text file, or stdin if `path` is `-`.
+pub fn read_text(path: &str) -> Result<String> {
+    if is_stdio(path) {
+        String::from_utf8(stdin_data()?.to_vec()).context("stdin is not valid UTF-8")
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("Can't open file : {:?}", path))
+    }
+}
+
+/// Opens a file for writing, or stdout if `path` is `-`.
+pub fn create_output(path: &str) -> Result<Box<dyn Write>> {
+    if is_stdio(path) {
+        Ok(Box::new(BufWriter::new(std::io::stdout())))
+    } else {
+        let file =
+            File::create(path).with_context(|| format!("Cannot create file : {:?}", path))?;
+        Ok(Box::new(BufWriter::new(file)))
+    }
+}
+
+/// Only one of the inputs of a command can be read from stdin.
+pub fn check_single_stdin(paths: &[&str]) -> Result<()> {
+    if paths.iter().filter(|p| is_stdio(p)).count() > 1 {
+        bail!("At most one input can be read from stdin");
+    }
+    Ok(())
+}
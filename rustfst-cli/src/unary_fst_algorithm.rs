@@ -9,6 +9,31 @@ use log::{debug, info};
 
 use rustfst::prelude::*;
 
+use crate::algorithm_output::AlgorithmOutput;
+use crate::arc_type::{ArcType, CliSemiring};
+use crate::stdio::read_fst;
+
+/// Runs `algorithm` with the semiring matching the `--arc_type` flag, or the arc type of its
+/// binary input.
+pub fn run_unary<A>(algorithm: &A, m: &ArgMatches) -> Result<()>
+where
+    A: UnaryFstAlgorithm<TropicalWeight> + UnaryFstAlgorithm<LogWeight>,
+{
+    let path_in = UnaryFstAlgorithm::<TropicalWeight>::get_path_in(algorithm);
+    let arc_type = ArcType::resolve(m.get_one::<String>("arc_type").map(|s| s.as_str()), path_in)?;
+    run_unary_with_arc_type(algorithm, m, arc_type)
+}
+
+pub fn run_unary_with_arc_type<A>(algorithm: &A, m: &ArgMatches, arc_type: ArcType) -> Result<()>
+where
+    A: UnaryFstAlgorithm<TropicalWeight> + UnaryFstAlgorithm<LogWeight>,
+{
+    match arc_type {
+        ArcType::Standard => UnaryFstAlgorithm::<TropicalWeight>::run_cli_or_bench(algorithm, m),
+        ArcType::Log => UnaryFstAlgorithm::<LogWeight>::run_cli_or_bench(algorithm, m),
+    }
+}
+
 fn duration_to_seconds(duration: &Duration) -> f64 {
     duration.as_secs() as f64 + duration.subsec_nanos() as f64 * 1.0e-9
 }
@@ -22,23 +47,25 @@ fn standard_deviation(data: &[f64]) -> f64 {
     b.sqrt()
 }
 
-pub trait UnaryFstAlgorithm {
+pub trait UnaryFstAlgorithm<W: CliSemiring> {
+    type Output: AlgorithmOutput;
+
     fn get_path_in(&self) -> &str;
     fn get_path_out(&self) -> &str;
     fn get_algorithm_name(&self) -> String;
 
-    fn read(&self) -> Result<VectorFst<TropicalWeight>> {
-        VectorFst::<TropicalWeight>::read(self.get_path_in())
+    fn read(&self) -> Result<VectorFst<W>> {
+        read_fst(self.get_path_in())
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>>;
+    fn run_algorithm(&self, fst: VectorFst<W>) -> Result<Self::Output>;
 
-    fn write(&self, fst: &VectorFst<TropicalWeight>) -> Result<()> {
-        fst.write(self.get_path_out())
+    fn write(&self, output: &Self::Output) -> Result<()> {
+        output.write_output(self.get_path_out())
     }
 
     fn run_cli_or_bench(&self, m: &ArgMatches) -> Result<()> {
-        if m.contains_id("bench") {
+        if m.get_flag("bench") {
             // Run bench
             self.run_bench(
                 m.get_one::<String>("n_warm_ups").unwrap().parse().unwrap(),
@@ -56,21 +83,21 @@ pub trait UnaryFstAlgorithm {
         // Parsing
         debug!("Parsing...");
         let parsing_start = Instant::now();
-        let mut fst = self.read()?;
+        let fst = self.read()?;
         let duration_parsing = parsing_start.elapsed();
         debug!("Duration parsing : {:?}", &duration_parsing);
 
         // Algorithm
         debug!("Running algorithm...");
         let algo_start = Instant::now();
-        fst = self.run_algorithm(fst)?;
+        let output = self.run_algorithm(fst)?;
         let duration_algo = algo_start.elapsed();
         debug!("Duration running algorithm : {:?}", &duration_algo);
 
         // Serialization
         debug!("Serialization...");
         let serialization_start = Instant::now();
-        self.write(&fst)?;
+        self.write(&output)?;
         let duration_serialization = serialization_start.elapsed();
         debug!("Duration serialization : {:?}", &duration_serialization);
 
@@ -99,17 +126,17 @@ pub trait UnaryFstAlgorithm {
         for i in 0..(n_warm_ups + n_iters) {
             // Parsing
             let parsing_start = Instant::now();
-            let mut fst = self.read()?;
+            let fst = self.read()?;
             let duration_parsing = parsing_start.elapsed();
 
             // Algorithm
             let algo_start = Instant::now();
-            fst = self.run_algorithm(fst)?;
+            let output = self.run_algorithm(fst)?;
             let duration_algo = algo_start.elapsed();
 
             // Serialization
             let serialization_start = Instant::now();
-            self.write(&fst)?;
+            self.write(&output)?;
             let duration_serialization = serialization_start.elapsed();
 
             if i >= n_warm_ups {
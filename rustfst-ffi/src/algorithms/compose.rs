@@ -2,6 +2,8 @@ use anyhow::{anyhow, Result};
 
 use super::EnumConversionError;
 use crate::fst::CFst;
+use crate::progress::CProgress;
+use crate::resource_limits::CResourceLimits;
 use crate::{get, wrap, CLabel, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
@@ -10,6 +12,7 @@ use rustfst::algorithms::compose::{
     compose, compose_with_config, ComposeConfig, ComposeFilterEnum, MatcherConfig,
     SigmaMatcherConfig,
 };
+use rustfst::algorithms::{Progress, ResourceLimits};
 use rustfst::fst_impls::VectorFst;
 use rustfst::semirings::TropicalWeight;
 use rustfst::Label;
@@ -160,13 +163,37 @@ impl CReprOf<MatcherConfig> for CMatcherConfig {
     }
 }
 
-#[derive(AsRust, CReprOf, CDrop, RawPointerConverter, Debug)]
-#[target_type(ComposeConfig)]
+#[derive(RawPointerConverter, Debug)]
 pub struct CComposeConfig {
     pub compose_filter: CComposeFilterEnum,
     pub connect: bool,
     pub matcher1_config: CMatcherConfig,
     pub matcher2_config: CMatcherConfig,
+    pub compat_symbols: bool,
+    pub progress: Option<Progress>,
+    pub limits: ResourceLimits,
+}
+
+impl AsRust<ComposeConfig> for CComposeConfig {
+    fn as_rust(&self) -> Result<ComposeConfig, AsRustError> {
+        Ok(ComposeConfig {
+            compose_filter: self.compose_filter.as_rust()?,
+            connect: self.connect,
+            matcher1_config: self.matcher1_config.as_rust()?,
+            matcher2_config: self.matcher2_config.as_rust()?,
+            compat_symbols: self.compat_symbols,
+            progress: self.progress.clone().unwrap_or_default(),
+            limits: self.limits,
+        })
+    }
+}
+
+impl CDrop for CComposeConfig {
+    fn do_drop(&mut self) -> Result<(), CDropError> {
+        self.matcher1_config.do_drop()?;
+        self.matcher2_config.do_drop()?;
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -231,6 +258,8 @@ pub unsafe extern "C" fn fst_compose_config_new(
     connect: bool,
     matcher1_config: *const CMatcherConfig,
     matcher2_config: *const CMatcherConfig,
+    progress: *const CProgress,
+    limits: *const CResourceLimits,
     config: *mut *const CComposeConfig,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
@@ -261,6 +290,9 @@ pub unsafe extern "C" fn fst_compose_config_new(
             matcher2_config,
             compose_filter: CComposeFilterEnum(compose_filter),
             connect,
+            compat_symbols: false,
+            progress: unsafe { CProgress::from_nullable(progress)? },
+            limits: unsafe { CResourceLimits::from_nullable(limits)? },
         };
         unsafe { *config = compose_config.into_raw_pointer() };
         Ok(())
@@ -2,14 +2,18 @@ use anyhow::{anyhow, Result};
 
 use super::EnumConversionError;
 use crate::fst::CFst;
-use crate::{get, wrap, RUSTFST_FFI_RESULT};
+use crate::progress::CProgress;
+use crate::resource_limits::CResourceLimits;
+use crate::{get, wrap, CLabel, CStateId, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
 use rustfst::algorithms::determinize::{
     determinize, determinize_with_config, DeterminizeConfig, DeterminizeType,
 };
+use rustfst::algorithms::{Progress, ResourceLimits};
 use rustfst::fst_impls::VectorFst;
 use rustfst::semirings::TropicalWeight;
+use rustfst::{Label, StateId, EPS_LABEL};
 
 #[derive(RawPointerConverter)]
 pub struct CDeterminizeType(usize);
@@ -42,11 +46,39 @@ impl CReprOf<DeterminizeType> for CDeterminizeType {
     }
 }
 
-#[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
-#[target_type(DeterminizeConfig)]
+#[derive(RawPointerConverter)]
 pub struct CDeterminizeConfig {
     delta: f32,
     det_type: CDeterminizeType,
+    weight_threshold: Option<TropicalWeight>,
+    state_threshold: Option<StateId>,
+    subsequential_label: Label,
+    partial_on_state_threshold: bool,
+    progress: Option<Progress>,
+    limits: ResourceLimits,
+}
+
+impl AsRust<DeterminizeConfig<TropicalWeight>> for CDeterminizeConfig {
+    fn as_rust(&self) -> Result<DeterminizeConfig<TropicalWeight>, AsRustError> {
+        let config = DeterminizeConfig::default()
+            .with_delta(self.delta)
+            .with_det_type(self.det_type.as_rust()?)
+            .with_weight_threshold(self.weight_threshold)
+            .with_state_threshold(self.state_threshold)
+            .with_subsequential_label(self.subsequential_label)
+            .with_partial_on_state_threshold(self.partial_on_state_threshold)
+            .with_limits(self.limits);
+        Ok(match &self.progress {
+            Some(progress) => config.with_progress(progress.clone()),
+            None => config,
+        })
+    }
+}
+
+impl CDrop for CDeterminizeConfig {
+    fn do_drop(&mut self) -> Result<(), CDropError> {
+        Ok(())
+    }
 }
 
 /// # Safety
@@ -56,18 +88,91 @@ pub struct CDeterminizeConfig {
 pub unsafe extern "C" fn fst_determinize_config_new(
     delta: libc::c_float,
     det_type: libc::size_t,
+    progress: *const CProgress,
+    limits: *const CResourceLimits,
     config: *mut *const CDeterminizeConfig,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let determinize_config = CDeterminizeConfig {
             delta,
             det_type: CDeterminizeType(det_type),
+            weight_threshold: None,
+            state_threshold: None,
+            subsequential_label: EPS_LABEL,
+            partial_on_state_threshold: false,
+            progress: unsafe { CProgress::from_nullable(progress)? },
+            limits: unsafe { CResourceLimits::from_nullable(limits)? },
         };
         unsafe { *config = determinize_config.into_raw_pointer() };
         Ok(())
     })
 }
 
+/// Only keeps the paths whose weight is below the weight of the shortest path times
+/// `weight_threshold`.
+///
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_determinize_config_set_weight_threshold(
+    config: *mut CDeterminizeConfig,
+    weight_threshold: libc::c_float,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let config = unsafe {
+            <CDeterminizeConfig as ffi_convert::RawBorrowMut<CDeterminizeConfig>>::raw_borrow_mut(
+                config,
+            )?
+        };
+        config.weight_threshold = Some(TropicalWeight::new(weight_threshold));
+        Ok(())
+    })
+}
+
+/// Bounds the number of states of the output. The determinization fails when it is reached,
+/// unless `partial_on_state_threshold` is set.
+///
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_determinize_config_set_state_threshold(
+    config: *mut CDeterminizeConfig,
+    state_threshold: CStateId,
+    partial_on_state_threshold: bool,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let config = unsafe {
+            <CDeterminizeConfig as ffi_convert::RawBorrowMut<CDeterminizeConfig>>::raw_borrow_mut(
+                config,
+            )?
+        };
+        config.state_threshold = Some(state_threshold as StateId);
+        config.partial_on_state_threshold = partial_on_state_threshold;
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_determinize_config_set_subsequential_label(
+    config: *mut CDeterminizeConfig,
+    subsequential_label: CLabel,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let config = unsafe {
+            <CDeterminizeConfig as ffi_convert::RawBorrowMut<CDeterminizeConfig>>::raw_borrow_mut(
+                config,
+            )?
+        };
+        config.subsequential_label = subsequential_label as Label;
+        Ok(())
+    })
+}
+
 /// # Safety
 ///
 /// The pointers should be valid.
@@ -1,19 +1,36 @@
 use anyhow::anyhow;
 
 use crate::fst::CFst;
+use crate::progress::CProgress;
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::*;
-use rustfst::algorithms::{minimize_with_config, MinimizeConfig};
+use rustfst::algorithms::{minimize_with_config, MinimizeConfig, Progress};
 use rustfst::fst_impls::VectorFst;
 use rustfst::prelude::minimize;
 use rustfst::semirings::TropicalWeight;
 
-#[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
-#[target_type(MinimizeConfig)]
+#[derive(RawPointerConverter)]
 pub struct CMinimizeConfig {
     delta: f32,
     allow_nondet: bool,
+    progress: Option<Progress>,
+}
+
+impl AsRust<MinimizeConfig> for CMinimizeConfig {
+    fn as_rust(&self) -> Result<MinimizeConfig, AsRustError> {
+        let config = MinimizeConfig::new(self.delta, self.allow_nondet);
+        Ok(match &self.progress {
+            Some(progress) => config.with_progress(progress.clone()),
+            None => config,
+        })
+    }
+}
+
+impl CDrop for CMinimizeConfig {
+    fn do_drop(&mut self) -> Result<(), CDropError> {
+        Ok(())
+    }
 }
 
 /// # Safety
@@ -23,12 +40,14 @@ pub struct CMinimizeConfig {
 pub unsafe extern "C" fn fst_minimize_config_new(
     delta: libc::c_float,
     allow_nondet: bool,
+    progress: *const CProgress,
     ptr: *mut *const CMinimizeConfig,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
         let config = CMinimizeConfig {
             delta,
             allow_nondet,
+            progress: unsafe { CProgress::from_nullable(progress)? },
         };
         unsafe { *ptr = config.into_raw_pointer() };
         Ok(())
@@ -1,7 +1,9 @@
+use std::ffi::CStr;
+
 use anyhow::anyhow;
-use ffi_convert::RawPointerConverter;
+use ffi_convert::{AsRust, RawPointerConverter};
 
-use rustfst::algorithms::randgen::{randgen_with_config, RandGenConfig, UniformTrSelector};
+use rustfst::algorithms::randgen::{randgen_with_config, RandGenConfig, RandGenSelector};
 use rustfst::prelude::{TropicalWeight, VectorFst};
 
 use crate::fst::as_fst;
@@ -17,6 +19,7 @@ pub unsafe extern "C" fn fst_randgen(
     ptr: *const CFst,
     npath: libc::size_t,
     seed: libc::size_t,
+    select: *const libc::c_char,
     max_length: libc::size_t,
     weight: bool,
     remove_total_weight: bool,
@@ -26,7 +29,9 @@ pub unsafe extern "C" fn fst_randgen(
         let ifst = get!(CFst, ptr);
         let ifst = as_fst!(VectorFst<TropicalWeight>, ifst);
 
-        let config = RandGenConfig::new(UniformTrSelector::from_seed(seed as u64))
+        let select = unsafe { CStr::from_ptr(select) }.as_rust()?;
+        let selector = RandGenSelector::from_name(&select, Some(seed as u64))?;
+        let config = RandGenConfig::new(selector)
             .with_npath(npath)
             .with_weighted(weight)
             .with_max_length(max_length)
@@ -1,11 +1,12 @@
 use anyhow::{anyhow, Result};
 
 use crate::fst::CFst;
+use crate::resource_limits::CResourceLimits;
 use crate::CLabel;
 use crate::{get, wrap, RUSTFST_FFI_RESULT};
 
 use ffi_convert::RawPointerConverter;
-use rustfst::algorithms::replace::replace;
+use rustfst::algorithms::replace::{replace_with_options, ReplaceFstOptions};
 use rustfst::prelude::{Label, TropicalWeight, VectorFst};
 
 #[repr(C)]
@@ -24,6 +25,7 @@ pub unsafe extern "C" fn fst_replace(
     fst_list_ptr: *mut CLabelFstPair,
     fst_list_ptr_len: libc::size_t,
     epsilon_on_replace: bool,
+    limits: *const CResourceLimits,
     replaced_fst: *mut *const CFst,
 ) -> RUSTFST_FFI_RESULT {
     wrap(|| {
@@ -40,12 +42,12 @@ pub unsafe extern "C" fn fst_replace(
                 Ok((pair.label as Label, vec_fst))
             })
             .collect::<Result<Vec<(CLabel, &VectorFst<TropicalWeight>)>>>()?;
-        let res_fst: VectorFst<TropicalWeight> = replace::<
-            TropicalWeight,
-            VectorFst<TropicalWeight>,
-            _,
-            _,
-        >(fst_list, root, epsilon_on_replace)?;
+        let opts = ReplaceFstOptions::new(root, epsilon_on_replace)
+            .with_limits(unsafe { CResourceLimits::from_nullable(limits)? });
+        let res_fst: VectorFst<TropicalWeight> =
+            replace_with_options::<TropicalWeight, VectorFst<TropicalWeight>, _, _>(
+                fst_list, opts,
+            )?;
         unsafe { *replaced_fst = CFst(Box::new(res_fst)).into_raw_pointer() };
         Ok(())
     })
@@ -1,12 +1,59 @@
 use anyhow::anyhow;
 
 use crate::fst::CFst;
+use crate::progress::CProgress;
 use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
 
-use rustfst::algorithms::rm_epsilon::rm_epsilon;
+use ffi_convert::*;
+use rustfst::algorithms::rm_epsilon::{rm_epsilon, rm_epsilon_with_config, RmEpsilonConfig};
+use rustfst::algorithms::Progress;
 use rustfst::fst_impls::VectorFst;
 use rustfst::semirings::TropicalWeight;
 
+#[derive(RawPointerConverter)]
+pub struct CRmEpsilonConfig {
+    connect: bool,
+    delta: f32,
+    progress: Option<Progress>,
+}
+
+impl AsRust<RmEpsilonConfig> for CRmEpsilonConfig {
+    fn as_rust(&self) -> Result<RmEpsilonConfig, AsRustError> {
+        let config = RmEpsilonConfig::new(self.connect, self.delta);
+        Ok(match &self.progress {
+            Some(progress) => config.with_progress(progress.clone()),
+            None => config,
+        })
+    }
+}
+
+impl CDrop for CRmEpsilonConfig {
+    fn do_drop(&mut self) -> Result<(), CDropError> {
+        Ok(())
+    }
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_rm_epsilon_config_new(
+    connect: bool,
+    delta: libc::c_float,
+    progress: *const CProgress,
+    ptr: *mut *const CRmEpsilonConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let config = CRmEpsilonConfig {
+            connect,
+            delta,
+            progress: unsafe { CProgress::from_nullable(progress)? },
+        };
+        unsafe { *ptr = config.into_raw_pointer() };
+        Ok(())
+    })
+}
+
 /// # Safety
 ///
 /// The pointers should be valid.
@@ -21,3 +68,25 @@ pub unsafe extern "C" fn fst_rm_epsilon(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
         Ok(())
     })
 }
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_rm_epsilon_with_config(
+    ptr: *mut CFst,
+    config: *const CRmEpsilonConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+
+        let config = unsafe {
+            <CRmEpsilonConfig as ffi_convert::RawBorrow<CRmEpsilonConfig>>::raw_borrow(config)?
+        };
+        rm_epsilon_with_config(vec_fst, config.as_rust()?)?;
+        Ok(())
+    })
+}
@@ -233,6 +233,7 @@ pub unsafe fn vec_fst_draw(
             acceptor: acceptor > 0,
             show_weight_one: show_weight_one > 0,
             print_weight: print_weight > 0,
+            ..DrawingConfig::default()
         };
 
         vec_fst.draw(unsafe { CStr::from_ptr(fname).as_rust()? }, &drawing_config)?;
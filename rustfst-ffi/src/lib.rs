@@ -3,6 +3,8 @@
 pub mod algorithms;
 pub mod fst;
 pub mod iterators;
+pub mod progress;
+pub mod resource_limits;
 pub mod string_path;
 pub mod string_paths_iterator;
 pub mod symbol_table;
@@ -34,6 +36,66 @@ pub enum RUSTFST_FFI_RESULT {
     RUSTFST_FFI_RESULT_OK = 0,
     /// The function returned an error
     RUSTFST_FFI_RESULT_KO = 1,
+    /// A state is not a state of the FST
+    RUSTFST_FFI_RESULT_STATE_OUT_OF_RANGE = 2,
+    /// The division is not defined in the semiring
+    RUSTFST_FFI_RESULT_UNSUPPORTED_DIVISION = 3,
+    /// Division by the zero of the semiring
+    RUSTFST_FFI_RESULT_DIVISION_BY_ZERO = 4,
+    /// The operation only supports acceptors
+    RUSTFST_FFI_RESULT_NOT_ACCEPTOR = 5,
+    /// The FST doesn't have the properties required by the operation
+    RUSTFST_FFI_RESULT_MISSING_FST_PROPERTIES = 6,
+    /// The semiring doesn't have the properties required by the operation
+    RUSTFST_FFI_RESULT_MISSING_SEMIRING_PROPERTIES = 7,
+    /// A binary FST, symbol table or encode table can't be read
+    RUSTFST_FFI_RESULT_READ_ERROR = 8,
+    /// The algorithm has been cancelled
+    RUSTFST_FFI_RESULT_CANCELLED = 9,
+    /// The output of the algorithm exceeds its resource limits
+    RUSTFST_FFI_RESULT_RESOURCE_LIMIT_EXCEEDED = 10,
+    /// A line of an FST in text format can't be parsed
+    RUSTFST_FFI_RESULT_TEXT_PARSE_ERROR = 11,
+    /// The output of the determinization exceeds its state threshold
+    RUSTFST_FFI_RESULT_DETERMINIZE_STATE_THRESHOLD = 12,
+}
+
+impl RUSTFST_FFI_RESULT {
+    /// Error code of an error returned by rustfst, `RUSTFST_FFI_RESULT_KO` if it isn't typed.
+    fn from_error(error: &anyhow::Error) -> Self {
+        match rustfst::Error::find(error) {
+            Some(rustfst::Error::StateOutOfRange { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_STATE_OUT_OF_RANGE
+            }
+            Some(rustfst::Error::UnsupportedDivision { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_UNSUPPORTED_DIVISION
+            }
+            Some(rustfst::Error::DivisionByZero) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_DIVISION_BY_ZERO
+            }
+            Some(rustfst::Error::NotAcceptor { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_NOT_ACCEPTOR
+            }
+            Some(rustfst::Error::MissingFstProperties { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_MISSING_FST_PROPERTIES
+            }
+            Some(rustfst::Error::MissingSemiringProperties { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_MISSING_SEMIRING_PROPERTIES
+            }
+            Some(rustfst::Error::Read(_)) => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_READ_ERROR,
+            Some(rustfst::Error::Cancelled) => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_CANCELLED,
+            Some(rustfst::Error::ResourceLimitExceeded { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_RESOURCE_LIMIT_EXCEEDED
+            }
+            Some(rustfst::Error::TextParse { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_TEXT_PARSE_ERROR
+            }
+            Some(rustfst::Error::DeterminizeStateThreshold { .. }) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_DETERMINIZE_STATE_THRESHOLD
+            }
+            Some(_) | None => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_KO,
+        }
+    }
 }
 
 thread_local! {
@@ -48,8 +110,9 @@ pub fn wrap<F: FnOnce() -> Result<()>>(func: F) -> RUSTFST_FFI_RESULT {
             if std::env::var("AMSTRAM_FFI_ERROR_STDERR").is_ok() {
                 eprintln!("{}", msg);
             }
+            let result = RUSTFST_FFI_RESULT::from_error(&e);
             LAST_ERROR.with(|p| *p.borrow_mut() = Some(msg));
-            RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_KO
+            result
         }
     }
 }
@@ -0,0 +1,87 @@
+use ffi_convert::RawPointerConverter;
+
+use rustfst::algorithms::Progress;
+
+use crate::{get, wrap, RUSTFST_FFI_RESULT};
+
+#[derive(RawPointerConverter)]
+pub struct CProgress(pub(crate) Progress);
+
+/// Called with the number of steps done by the algorithm.
+pub type CProgressCallback = Option<unsafe extern "C" fn(steps: libc::size_t)>;
+
+impl CProgress {
+    /// Progress to use from a nullable pointer passed to a config constructor.
+    ///
+    /// # Safety
+    ///
+    /// The pointer should be null or valid.
+    pub(crate) unsafe fn from_nullable(ptr: *const CProgress) -> anyhow::Result<Option<Progress>> {
+        if ptr.is_null() {
+            return Ok(None);
+        }
+        let progress = get!(CProgress, ptr);
+        Ok(Some(progress.clone()))
+    }
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn progress_new(
+    callback: CProgressCallback,
+    interval: libc::size_t,
+    ptr: *mut *const CProgress,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let mut progress = Progress::new().with_interval(interval);
+        if let Some(callback) = callback {
+            progress = progress.with_callback(move |steps| unsafe { callback(steps) });
+        }
+        unsafe { *ptr = CProgress(progress).into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn progress_cancel(ptr: *const CProgress) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let progress = get!(CProgress, ptr);
+        progress.cancel();
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn progress_is_cancelled(
+    ptr: *const CProgress,
+    is_cancelled: *mut libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let progress = get!(CProgress, ptr);
+        unsafe { *is_cancelled = progress.is_cancelled() as libc::size_t };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn progress_destroy(ptr: *mut CProgress) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
@@ -0,0 +1,100 @@
+use ffi_convert::RawPointerConverter;
+
+use rustfst::algorithms::ResourceLimits;
+
+use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+
+#[derive(RawPointerConverter)]
+pub struct CResourceLimits(pub(crate) ResourceLimits);
+
+impl CResourceLimits {
+    /// Limits to use from a nullable pointer passed to a config constructor, unlimited if null.
+    ///
+    /// # Safety
+    ///
+    /// The pointer should be null or valid.
+    pub(crate) unsafe fn from_nullable(
+        ptr: *const CResourceLimits,
+    ) -> anyhow::Result<ResourceLimits> {
+        if ptr.is_null() {
+            return Ok(ResourceLimits::default());
+        }
+        let limits = get!(CResourceLimits, ptr);
+        Ok(*limits)
+    }
+}
+
+/// Creates limits without any bound.
+///
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn resource_limits_new(
+    ptr: *mut *const CResourceLimits,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        unsafe { *ptr = CResourceLimits(ResourceLimits::new()).into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn resource_limits_set_max_states(
+    ptr: *mut CResourceLimits,
+    max_states: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let limits = get_mut!(CResourceLimits, ptr);
+        limits.max_states = Some(max_states);
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn resource_limits_set_max_trs(
+    ptr: *mut CResourceLimits,
+    max_trs: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let limits = get_mut!(CResourceLimits, ptr);
+        limits.max_trs = Some(max_trs);
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn resource_limits_set_max_memory_bytes(
+    ptr: *mut CResourceLimits,
+    max_memory_bytes: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let limits = get_mut!(CResourceLimits, ptr);
+        limits.max_memory_bytes = Some(max_memory_bytes);
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn resource_limits_destroy(ptr: *mut CResourceLimits) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
@@ -198,6 +198,25 @@ pub unsafe extern "C" fn symt_num_symbols(
     })
 }
 
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn symt_get_nth_key(
+    symt: *const CSymbolTable,
+    pos: libc::size_t,
+    key: *mut libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let symt = get!(CSymbolTable, symt);
+        let res = symt
+            .get_nth_key(pos)
+            .ok_or_else(|| format_err!("No symbol at position {}", pos))?;
+        unsafe { *key = res as libc::size_t };
+        Ok(())
+    })
+}
+
 /// # Safety
 ///
 /// The pointers should be valid.
@@ -6,3 +6,10 @@ from .fst.vector_fst import VectorFst
 from .fst.const_fst import ConstFst
 from .iterators import TrsIterator, MutableTrsIterator, StateIterator
 from .drawing_config import DrawingConfig
+from .progress import Progress
+from .resource_limits import ResourceLimits
+from .ffi_utils import (
+    CancelledError,
+    ResourceLimitExceededError,
+    DeterminizeStateThresholdError,
+)
@@ -8,6 +8,8 @@ from rustfst.ffi_utils import (
 )
 
 from rustfst.fst.vector_fst import VectorFst
+from rustfst.progress import Progress
+from rustfst.resource_limits import ResourceLimits
 
 
 from enum import Enum
@@ -73,6 +75,8 @@ class ComposeConfig:
       connect: Connect the resulting FST after composition.
       matcher1_config: Matcher configuration for left-hand FST.
       matcher2_config: Matcher configuration for right-hand FST.
+      progress: Progress to observe or cancel the composition.
+      limits: Bounds on the size of the composition.
     """
 
     def __init__(
@@ -81,7 +85,10 @@ class ComposeConfig:
         connect: bool = True,
         matcher1_config: Optional[MatcherConfig] = None,
         matcher2_config: Optional[MatcherConfig] = None,
+        progress: Optional[Progress] = None,
+        limits: Optional[ResourceLimits] = None,
     ):
+        self.progress = progress
         config = ctypes.pointer(ctypes.c_void_p())
 
         m1_ptr = None
@@ -97,6 +104,8 @@ class ComposeConfig:
             ctypes.c_bool(connect),
             m1_ptr,
             m2_ptr,
+            progress.ptr if progress is not None else None,
+            limits.ptr if limits is not None else None,
             ctypes.byref(config),
         )
         err_msg = "Error creating ComposeConfig"
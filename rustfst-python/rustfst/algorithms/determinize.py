@@ -8,6 +8,8 @@ from rustfst.ffi_utils import (
 )
 
 from rustfst.fst.vector_fst import VectorFst
+from rustfst.progress import Progress
+from rustfst.resource_limits import ResourceLimits
 from enum import Enum
 
 KDELTA = 1.0 / 1024.0
@@ -38,26 +40,67 @@ class DeterminizeConfig:
     Struct containing the parameters controlling the determinization algorithm.
     """
 
-    def __init__(self, det_type: DeterminizeType, delta: Optional[float] = None):
+    def __init__(
+        self,
+        det_type: DeterminizeType,
+        delta: Optional[float] = None,
+        progress: Optional[Progress] = None,
+        limits: Optional[ResourceLimits] = None,
+        weight_threshold: Optional[float] = None,
+        state_threshold: Optional[int] = None,
+        subsequential_label: Optional[int] = None,
+        partial_on_state_threshold: bool = False,
+    ):
         """
         Creates the configuration object.
         Args:
             det_type: Type of determinization to perform.
             delta:
+            progress: Progress to observe or cancel the determinization.
+            limits: Bounds on the size of the output.
+            weight_threshold: Only keep the paths whose weight is below the weight of the
+                shortest path times this threshold.
+            state_threshold: Maximum number of states of the output. Exceeding it raises a
+                `DeterminizeStateThresholdError`.
+            subsequential_label: Label of the transitions to the superfinal state when
+                determinizing a transducer.
+            partial_on_state_threshold: Return the part of the output computed so far when
+                the state threshold is reached instead of raising.
         """
         if delta is None:
             delta = KDELTA
 
+        self.progress = progress
         config = ctypes.pointer(ctypes.c_void_p())
         ret_code = lib.fst_determinize_config_new(
             ctypes.c_float(delta),
             ctypes.c_size_t(det_type.value),
+            progress.ptr if progress is not None else None,
+            limits.ptr if limits is not None else None,
             ctypes.byref(config),
         )
         err_msg = "Error creating DeterminizeConfig"
         check_ffi_error(ret_code, err_msg)
         self.ptr = config
 
+        if weight_threshold is not None:
+            ret_code = lib.fst_determinize_config_set_weight_threshold(
+                self.ptr, ctypes.c_float(weight_threshold)
+            )
+            check_ffi_error(ret_code, err_msg)
+        if state_threshold is not None:
+            ret_code = lib.fst_determinize_config_set_state_threshold(
+                self.ptr,
+                ctypes.c_size_t(state_threshold),
+                ctypes.c_bool(partial_on_state_threshold),
+            )
+            check_ffi_error(ret_code, err_msg)
+        if subsequential_label is not None:
+            ret_code = lib.fst_determinize_config_set_subsequential_label(
+                self.ptr, ctypes.c_size_t(subsequential_label)
+            )
+            check_ffi_error(ret_code, err_msg)
+
 
 def determinize(fst: VectorFst) -> VectorFst:
     """
@@ -1,11 +1,14 @@
 from __future__ import annotations
 import ctypes
+from typing import Optional
+
 from rustfst.ffi_utils import (
     lib,
     check_ffi_error,
 )
 
 from rustfst.fst.vector_fst import VectorFst
+from rustfst.progress import Progress
 
 KSHORTESTDELTA = 1e-6
 
@@ -13,15 +16,20 @@ KSHORTESTDELTA = 1e-6
 class MinimizeConfig:
     """
     Configuration for the minimization operation.
+    The `progress` can be used to observe or cancel the minimization.
     """
 
-    def __init__(self, delta=None, allow_nondet=False):
+    def __init__(
+        self, delta=None, allow_nondet=False, progress: Optional[Progress] = None
+    ):
         if delta is None:
             delta = KSHORTESTDELTA
+        self.progress = progress
         config = ctypes.pointer(ctypes.c_void_p())
         ret_code = lib.fst_minimize_config_new(
             ctypes.c_float(delta),
             ctypes.c_bool(allow_nondet),
+            progress.ptr if progress is not None else None,
             ctypes.byref(config),
         )
         err_msg = "Error creating MinimizeConfig"
@@ -23,7 +23,9 @@ def randgen(
     argument. The default selector, "uniform", randomly selects a transition
     using a uniform distribution. The "log_prob" selector randomly selects a
     transition w.r.t. the weights treated as negative log probabilities after
-    normalizing for the total weight leaving the state. In all cases, finality is
+    normalizing for the total weight leaving the state. The "fast_log_prob"
+    selector samples from the same distribution but caches the cumulative
+    distribution of each state. In all cases, finality is
     treated as a transition to a super-final state.
 
     Args:
@@ -45,13 +47,12 @@ def randgen(
       ValueError: when something wrong happened.
     """
 
-    if select != "uniform":
-        raise ValueError(
-            f"Only the uniform distribution is supported for now. Found {select}"
-        )
+    if select not in ("uniform", "log_prob", "fast_log_prob"):
+        raise ValueError(f"Unknown selector : {select}")
 
     npath = ctypes.c_size_t(npath)
     seed = ctypes.c_size_t(seed)
+    select = ctypes.c_char_p(select.encode("utf-8"))
     max_length = ctypes.c_size_t(max_length)
     weight = ctypes.c_bool(weight)
     remove_total_weight = ctypes.c_bool(remove_total_weight)
@@ -61,6 +62,7 @@ def randgen(
         ifst.ptr,
         npath,
         seed,
+        select,
         max_length,
         weight,
         remove_total_weight,
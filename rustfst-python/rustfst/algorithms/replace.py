@@ -1,5 +1,5 @@
 from __future__ import annotations
-from typing import List, Tuple
+from typing import List, Optional, Tuple
 import ctypes
 from rustfst.ffi_utils import (
     lib,
@@ -7,6 +7,7 @@ from rustfst.ffi_utils import (
 )
 
 from rustfst.fst.vector_fst import VectorFst
+from rustfst.resource_limits import ResourceLimits
 
 
 class LabelFstPair(ctypes.Structure):
@@ -17,7 +18,10 @@ class LabelFstPair(ctypes.Structure):
 
 
 def replace(
-    root_idx: int, fst_list: List[Tuple[int, VectorFst]], epsilon_on_replace: bool
+    root_idx: int,
+    fst_list: List[Tuple[int, VectorFst]],
+    epsilon_on_replace: bool,
+    limits: Optional[ResourceLimits] = None,
 ) -> VectorFst:
     """
     Recursively replaces trs in the root FSTs with other FSTs.
@@ -59,6 +63,7 @@ def replace(
         root_idx:
         fst_list:
         epsilon_on_replace:
+        limits: Bounds on the size of the output.
 
     Returns:
         The resulting Fst.
@@ -72,6 +77,7 @@ def replace(
         ctypes.byref(pairs_array),
         ctypes.c_size_t(len(pairs)),
         ctypes.c_bool(epsilon_on_replace),
+        limits.ptr if limits is not None else None,
         ctypes.byref(res_fst),
     )
     err_msg = "Error performing replace"
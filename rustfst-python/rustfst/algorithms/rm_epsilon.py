@@ -1,10 +1,47 @@
 from __future__ import annotations
+import ctypes
+from typing import Optional
+
 from rustfst.ffi_utils import (
     lib,
     check_ffi_error,
 )
 
 from rustfst.fst.vector_fst import VectorFst
+from rustfst.progress import Progress
+
+KSHORTESTDELTA = 1e-6
+
+
+class RmEpsilonConfig:
+    """
+    Configuration for the epsilon removal.
+
+    Args:
+      connect: Connect the resulting FST.
+      delta: Comparison delta of the shortest distance computations.
+      progress: Progress to observe or cancel the epsilon removal.
+    """
+
+    def __init__(
+        self,
+        connect: bool = True,
+        delta: Optional[float] = None,
+        progress: Optional[Progress] = None,
+    ):
+        if delta is None:
+            delta = KSHORTESTDELTA
+        self.progress = progress
+        config = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.fst_rm_epsilon_config_new(
+            ctypes.c_bool(connect),
+            ctypes.c_float(delta),
+            progress.ptr if progress is not None else None,
+            ctypes.byref(config),
+        )
+        err_msg = "Error creating RmEpsilonConfig"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = config
 
 
 def rm_epsilon(fst: VectorFst) -> VectorFst:
@@ -21,3 +58,20 @@ def rm_epsilon(fst: VectorFst) -> VectorFst:
     check_ffi_error(ret_code, err_msg)
 
     return fst
+
+
+def rm_epsilon_with_config(fst: VectorFst, config: RmEpsilonConfig) -> VectorFst:
+    """
+    Remove epsilon transitions in-place
+    Args:
+      fst: Fst to remove epsilons from
+      config: Configuration
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = lib.fst_rm_epsilon_with_config(fst.ptr, config.ptr)
+    err_msg = "Error during rm_epsilon"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -39,6 +39,29 @@ def string_pointer(ptr):
         lib.rustfst_destroy_string(ptr)
 
 
+RUSTFST_FFI_RESULT_CANCELLED = 9
+RUSTFST_FFI_RESULT_RESOURCE_LIMIT_EXCEEDED = 10
+RUSTFST_FFI_RESULT_DETERMINIZE_STATE_THRESHOLD = 12
+
+
+class CancelledError(ValueError):
+    """
+    Raised when an algorithm is cancelled with `Progress.cancel`.
+    """
+
+
+class ResourceLimitExceededError(ValueError):
+    """
+    Raised when the output of an algorithm exceeds its `ResourceLimits`.
+    """
+
+
+class DeterminizeStateThresholdError(ValueError):
+    """
+    Raised when the output of the determinization exceeds its state threshold.
+    """
+
+
 def check_ffi_error(exit_code, error_context_msg):
     if exit_code != 0:
         with string_pointer(c_char_p()) as ptr:
@@ -46,4 +69,14 @@ def check_ffi_error(exit_code, error_context_msg):
                 ffi_error_message = string_at(ptr).decode("utf8")
             else:
                 ffi_error_message = "see stderr"
+        if exit_code == RUSTFST_FFI_RESULT_CANCELLED:
+            raise CancelledError(f"{error_context_msg}: {ffi_error_message}")
+        if exit_code == RUSTFST_FFI_RESULT_RESOURCE_LIMIT_EXCEEDED:
+            raise ResourceLimitExceededError(
+                f"{error_context_msg}: {ffi_error_message}"
+            )
+        if exit_code == RUSTFST_FFI_RESULT_DETERMINIZE_STATE_THRESHOLD:
+            raise DeterminizeStateThresholdError(
+                f"{error_context_msg}: {ffi_error_message}"
+            )
         raise ValueError(f"{error_context_msg}: {ffi_error_message}")
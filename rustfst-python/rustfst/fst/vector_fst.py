@@ -24,6 +24,7 @@ if TYPE_CHECKING:
     from rustfst.algorithms.minimize import MinimizeConfig
     from rustfst.algorithms.project import ProjectType
     from rustfst.algorithms.shortest_path import ShortestPathConfig
+    from rustfst.resource_limits import ResourceLimits
 
 
 class VectorFst(Fst):
@@ -541,6 +542,7 @@ class VectorFst(Fst):
         root_label: int,
         fst_list: List[Tuple[int, VectorFst]],
         epsilon_on_replace: bool = False,
+        limits: Optional[ResourceLimits] = None,
     ) -> VectorFst:
         """Recursively replaces trs in the root FSTs with other FSTs.
 
@@ -585,6 +587,7 @@ class VectorFst(Fst):
             root_label: Label for self
             fst_list: Other FSTs
             epsilon_on_replace:
+            limits: Bounds on the size of the output.
 
         Returns:
             The resulting Fst.
@@ -593,7 +596,7 @@ class VectorFst(Fst):
         from rustfst.algorithms.replace import replace
 
         complete_fst_list = [(root_label, self)] + fst_list
-        return replace(root_label, complete_fst_list, epsilon_on_replace)
+        return replace(root_label, complete_fst_list, epsilon_on_replace, limits)
 
     def reverse(self) -> VectorFst:
         """
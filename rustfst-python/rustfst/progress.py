@@ -0,0 +1,59 @@
+from __future__ import annotations
+import ctypes
+from typing import Callable, Optional
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+DEFAULT_PROGRESS_INTERVAL = 1024
+
+CProgressCallback = ctypes.CFUNCTYPE(None, ctypes.c_size_t)
+
+
+class Progress:
+    """
+    Observes and cancels a long-running algorithm (determinization, composition,
+    minimization, epsilon removal) when passed to its configuration.
+
+    The algorithm calls `callback` with the number of steps done (e.g states expanded)
+    every `interval` steps and fails with a `CancelledError` once `cancel` has been called,
+    either from the callback or from another thread.
+    """
+
+    def __init__(
+        self,
+        callback: Optional[Callable[[int], None]] = None,
+        interval: int = DEFAULT_PROGRESS_INTERVAL,
+    ):
+        # Keep a reference to the C callback for as long as the algorithms may call it.
+        self._callback = None
+        if callback is not None:
+            self._callback = CProgressCallback(callback)
+
+        ptr = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.progress_new(
+            self._callback, ctypes.c_size_t(interval), ctypes.byref(ptr)
+        )
+        err_msg = "Error creating Progress"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = ptr
+
+    def cancel(self):
+        """
+        Cancels the algorithms using this Progress.
+        """
+        ret_code = lib.progress_cancel(self.ptr)
+        err_msg = "Error cancelling Progress"
+        check_ffi_error(ret_code, err_msg)
+
+    def is_cancelled(self) -> bool:
+        is_cancelled = ctypes.c_size_t()
+        ret_code = lib.progress_is_cancelled(self.ptr, ctypes.byref(is_cancelled))
+        err_msg = "Error checking Progress"
+        check_ffi_error(ret_code, err_msg)
+        return bool(is_cancelled.value)
+
+    def __del__(self):
+        lib.progress_destroy(self.ptr)
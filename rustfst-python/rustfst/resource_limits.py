@@ -0,0 +1,53 @@
+from __future__ import annotations
+import ctypes
+from typing import Optional
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+
+class ResourceLimits:
+    """
+    Bounds on the size of the FST built by an algorithm that can blow up (determinization,
+    composition, replace) when passed to its configuration. The algorithm raises a
+    `ResourceLimitExceededError` as soon as its output exceeds one of them and its inputs
+    are left untouched. `None` means unlimited.
+
+    Args:
+      max_states: Maximum number of states of the output.
+      max_trs: Maximum number of transitions of the output.
+      max_memory_bytes: Maximum estimated memory of the output, in bytes.
+    """
+
+    def __init__(
+        self,
+        max_states: Optional[int] = None,
+        max_trs: Optional[int] = None,
+        max_memory_bytes: Optional[int] = None,
+    ):
+        ptr = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.resource_limits_new(ctypes.byref(ptr))
+        err_msg = "Error creating ResourceLimits"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = ptr
+
+        if max_states is not None:
+            ret_code = lib.resource_limits_set_max_states(
+                self.ptr, ctypes.c_size_t(max_states)
+            )
+            check_ffi_error(ret_code, err_msg)
+        if max_trs is not None:
+            ret_code = lib.resource_limits_set_max_trs(
+                self.ptr, ctypes.c_size_t(max_trs)
+            )
+            check_ffi_error(ret_code, err_msg)
+        if max_memory_bytes is not None:
+            ret_code = lib.resource_limits_set_max_memory_bytes(
+                self.ptr, ctypes.c_size_t(max_memory_bytes)
+            )
+            check_ffi_error(ret_code, err_msg)
+
+    def __del__(self):
+        lib.resource_limits_destroy(self.ptr)
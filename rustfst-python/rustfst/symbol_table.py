@@ -151,6 +151,26 @@ class SymbolTable:
 
         return int(num_symbols.value)
 
+    def get_nth_key(self, pos: int) -> int:
+        """
+        Retrieves the label of the symbol at a given position, in the order in which the
+        symbols were added. The labels are not necessarily contiguous.
+        Args:
+          pos: The position of the symbol.
+        Returns:
+          The label of the symbol.
+        Raises:
+          ValueError: Position out of range.
+        """
+        key = ctypes.c_size_t()
+        ret_code = lib.symt_get_nth_key(
+            self.ptr, ctypes.c_size_t(pos), ctypes.byref(key)
+        )
+        err_msg = "`get_nth_key` failed"
+        check_ffi_error(ret_code, err_msg)
+
+        return int(key.value)
+
     @classmethod
     def read(cls, filename: Union[str, Path]) -> SymbolTable:
         """
@@ -306,7 +326,8 @@ class SymbolTableIterator:
             A pair label (int) and symbol (str).
         """
         if self._idx < self._len:
-            output = (self._idx, self._symt.find(self._idx))
+            key = self._symt.get_nth_key(self._idx)
+            output = (key, self._symt.find(key))
             self._idx += 1
             return output
         raise StopIteration
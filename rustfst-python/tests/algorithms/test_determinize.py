@@ -1,6 +1,11 @@
-from rustfst import VectorFst, Tr
+import pytest
 
-from rustfst.algorithms.determinize import DeterminizeConfig, DeterminizeType
+from rustfst import VectorFst, Tr, DeterminizeStateThresholdError
+from rustfst.algorithms.determinize import (
+    DeterminizeConfig,
+    DeterminizeType,
+    determinize_with_config,
+)
 
 
 def test_determinize_fst():
@@ -61,3 +66,27 @@ def test_determinize_fst():
     det_fst = fst1.determinize(config)
 
     assert det_fst == expected_fst
+
+
+def test_determinize_state_threshold():
+    fst = VectorFst()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+    fst.set_start(s1)
+    fst.set_final(s3, 0.0)
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s2, Tr(2, 2, 2.0, s3))
+
+    config = DeterminizeConfig(
+        DeterminizeType.DETERMINIZE_FUNCTIONAL, state_threshold=2
+    )
+    with pytest.raises(DeterminizeStateThresholdError):
+        determinize_with_config(fst, config)
+
+    config = DeterminizeConfig(
+        DeterminizeType.DETERMINIZE_FUNCTIONAL,
+        state_threshold=2,
+        partial_on_state_threshold=True,
+    )
+    assert determinize_with_config(fst, config).num_states() == 2
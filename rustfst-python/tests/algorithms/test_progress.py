@@ -0,0 +1,44 @@
+import pytest
+
+from rustfst import VectorFst, Tr, Progress, CancelledError
+from rustfst.algorithms.determinize import (
+    DeterminizeConfig,
+    DeterminizeType,
+    determinize_with_config,
+)
+from rustfst.algorithms.rm_epsilon import RmEpsilonConfig, rm_epsilon_with_config
+
+
+def build_fst():
+    fst = VectorFst()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+    fst.set_start(s1)
+    fst.set_final(s3, 1.0)
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s1, Tr(1, 1, 2.0, s3))
+    fst.add_tr(s2, Tr(0, 0, 1.0, s3))
+    return fst
+
+
+def test_progress_callback():
+    steps = []
+    progress = Progress(callback=steps.append, interval=1)
+    config = DeterminizeConfig(
+        DeterminizeType.DETERMINIZE_FUNCTIONAL, progress=progress
+    )
+    determinize_with_config(build_fst(), config)
+    assert len(steps) > 0
+    assert steps[0] == 1
+    assert not progress.is_cancelled()
+
+
+def test_progress_cancelled():
+    progress = Progress()
+    progress.cancel()
+    assert progress.is_cancelled()
+
+    config = RmEpsilonConfig(progress=progress)
+    with pytest.raises(CancelledError):
+        rm_epsilon_with_config(build_fst(), config)
@@ -1,5 +1,7 @@
 from rustfst import VectorFst, Tr
-from rustfst.weight import weight_one
+import pytest
+
+from rustfst.weight import weight_one, weight_zero
 from rustfst.algorithms.randgen import randgen
 
 
@@ -20,3 +22,27 @@ def test_randgen():
     for tr in fst.trs(fst.start()):
         assert tr.ilabel in {2, 3}
         assert tr.olabel in {2, 3}
+
+
+@pytest.mark.parametrize("select", ["log_prob", "fast_log_prob"])
+def test_randgen_log_prob(select):
+    fst = VectorFst()
+    s0 = fst.add_state()
+    s1 = fst.add_state()
+
+    fst.set_start(s0)
+    fst.set_final(s1)
+
+    fst.add_tr(s0, Tr(2, 2, weight_zero(), s1))
+    fst.add_tr(s0, Tr(3, 3, weight_one(), s1))
+
+    for seed in range(1, 10):
+        res = randgen(ifst=fst, seed=seed, select=select)
+        trs = list(res.trs(res.start()))
+        assert len(trs) == 1
+        assert trs[0].ilabel == 3
+
+
+def test_randgen_unknown_selector():
+    with pytest.raises(ValueError):
+        randgen(ifst=VectorFst(), select="unknown")
@@ -0,0 +1,48 @@
+import pytest
+
+from rustfst import VectorFst, Tr, ResourceLimits, ResourceLimitExceededError
+from rustfst.algorithms.compose import ComposeConfig, compose_with_config
+from rustfst.algorithms.determinize import (
+    DeterminizeConfig,
+    DeterminizeType,
+    determinize_with_config,
+)
+
+
+def build_fst():
+    fst = VectorFst()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+    fst.set_start(s1)
+    fst.set_final(s3, 1.0)
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s2, Tr(2, 2, 2.0, s3))
+    return fst
+
+
+def test_determinize_resource_limits():
+    fst = build_fst()
+    config = DeterminizeConfig(
+        DeterminizeType.DETERMINIZE_FUNCTIONAL, limits=ResourceLimits(max_states=2)
+    )
+    with pytest.raises(ResourceLimitExceededError):
+        determinize_with_config(fst, config)
+    assert fst == build_fst()
+
+    config = DeterminizeConfig(
+        DeterminizeType.DETERMINIZE_FUNCTIONAL, limits=ResourceLimits(max_states=3)
+    )
+    determinize_with_config(fst, config)
+
+
+def test_compose_resource_limits():
+    config = ComposeConfig(limits=ResourceLimits(max_trs=1))
+    with pytest.raises(ResourceLimitExceededError):
+        compose_with_config(build_fst(), build_fst(), config)
+
+
+def test_replace_resource_limits():
+    root = build_fst()
+    with pytest.raises(ResourceLimitExceededError):
+        root.replace(0, [], limits=ResourceLimits(max_states=1))
@@ -77,6 +77,16 @@ def test_symt_iterator():
     assert list(symt) == [(0, "<eps>"), (1, "a"), (2, "b")]
 
 
+def test_symt_iterator_sparse_labels(tmp_path):
+    path = tmp_path / "symt.txt"
+    path.write_text("<eps>\t0\na\t10\nb\t-2\n")
+    symt = SymbolTable.read_text(path)
+
+    assert symt.num_symbols() == 3
+    assert symt.get_nth_key(1) == 10
+    assert list(symt) == [(0, "<eps>"), (10, "a"), (2**32 - 2, "b")]
+
+
 def test_symt_copy_add():
     fst = VectorFst()
     symt = SymbolTable.from_symbols(["a", "b"])
@@ -0,0 +1,107 @@
+#!/usr/bin/env python3
+"""Generates the same FST in the binary formats written by OpenFST.
+
+The FST, in text format:
+0   1   1   2   0.5
+0   2   3   0   1
+1   2   0   4   0.25
+2   1.5
+
+The layout follows fst/fst.h (FstHeader), fst/vector-fst.h and fst/const-fst.h. The
+big-endian files are the ones OpenFST writes on big-endian platforms.
+"""
+
+import math
+import struct
+
+FST_MAGIC_NUMBER = 2125659606
+IS_ALIGNED = 4
+ALIGNMENT = 16
+
+START = 0
+FINAL_WEIGHTS = [math.inf, math.inf, 1.5]
+TRS = [
+    [(1, 2, 0.5, 1), (3, 0, 1.0, 2)],
+    [(0, 4, 0.25, 2)],
+    [],
+]
+
+
+class Writer:
+    def __init__(self, endianness):
+        self.endianness = endianness
+        self.data = b""
+
+    def pack(self, fmt, *values):
+        self.data += struct.pack(self.endianness + fmt, *values)
+
+    def string(self, s):
+        self.pack("i", len(s))
+        self.data += s.encode()
+
+    def align(self):
+        if len(self.data) % ALIGNMENT:
+            self.data += b"\0" * (ALIGNMENT - len(self.data) % ALIGNMENT)
+
+    def header(self, fst_type, arc_type, version, flags, properties, num_trs):
+        self.pack("i", FST_MAGIC_NUMBER)
+        self.string(fst_type)
+        self.string(arc_type)
+        self.pack("i", version)
+        self.pack("i", flags)
+        self.pack("Q", properties)
+        self.pack("q", START)
+        self.pack("q", len(TRS))
+        self.pack("q", num_trs)
+
+    def tr(self, tr):
+        ilabel, olabel, weight, nextstate = tr
+        self.pack("iifi", ilabel, olabel, weight, nextstate)
+
+
+def vector_fst(arc_type="standard", endianness="<"):
+    w = Writer(endianness)
+    w.header("vector", arc_type, 2, 0, 0x3, sum(len(trs) for trs in TRS))
+    for final_weight, trs in zip(FINAL_WEIGHTS, TRS):
+        w.pack("f", final_weight)
+        w.pack("q", len(trs))
+        for tr in trs:
+            w.tr(tr)
+    return w.data
+
+
+def const_fst(version, flags, endianness="<"):
+    aligned = version == 1
+    w = Writer(endianness)
+    w.header("const", "standard", version, flags, 0x1, sum(len(trs) for trs in TRS))
+    if aligned:
+        w.align()
+    pos = 0
+    for final_weight, trs in zip(FINAL_WEIGHTS, TRS):
+        niepsilons = sum(1 for tr in trs if tr[0] == 0)
+        noepsilons = sum(1 for tr in trs if tr[1] == 0)
+        w.pack("f", final_weight)
+        w.pack("IIII", pos, len(trs), niepsilons, noepsilons)
+        pos += len(trs)
+    if aligned:
+        w.align()
+    for trs in TRS:
+        for tr in trs:
+            w.tr(tr)
+    return w.data
+
+
+FIXTURES = {
+    "vector_v2.fst": vector_fst(),
+    "vector_v2_log.fst": vector_fst(arc_type="log"),
+    "vector_v2_big_endian.fst": vector_fst(endianness=">"),
+    "const_v1_aligned.fst": const_fst(1, IS_ALIGNED),
+    "const_v1_aligned_no_flag.fst": const_fst(1, 0),
+    "const_v2.fst": const_fst(2, 0),
+    "const_v2_big_endian.fst": const_fst(2, 0, endianness=">"),
+}
+
+if __name__ == "__main__":
+    for name, data in FIXTURES.items():
+        with open(name, "wb") as f:
+            f.write(data)
@@ -349,6 +349,14 @@ void compute_fst_determinization(const F& raw_fst, json& j, const string& dir_pa
     compute_fst_determinization(raw_fst, j, fst::DeterminizeType::DETERMINIZE_DISAMBIGUATE, "disambiguate", dir_path);
 }
 
+template<class F>
+void compute_fst_disambiguate(const F& raw_fst, json& j, const string& dir_path) {
+    F fst_out;
+    fst::Disambiguate(raw_fst, &fst_out);
+    bool error = prop_to_bool(fst_out.Properties(fst::kError, true), fst::kError);
+    j["disambiguate"]["result_path"] = error ? "error" : dump_fst(fst_out, dir_path);
+}
+
 template<class F>
 void compute_fst_topsort(const F& raw_fst, json& j, const string& dir_path) {
     auto fst_out = *raw_fst.Copy();
@@ -1145,6 +1153,9 @@ void compute_fst_data(const F& fst_test_data, const string fst_name) {
     std::cout << "Determinization" << std::endl;
     compute_fst_determinization(raw_fst, data, dir_path);
 
+    std::cout << "Disambiguate" << std::endl;
+    compute_fst_disambiguate(raw_fst, data, dir_path);
+
     std::cout << "TopSort" << std::endl;
     compute_fst_topsort(raw_fst, data, dir_path);
 
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use rustfst::SymbolTable;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = SymbolTable::load(data);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use rustfst::fst_impls::VectorFst;
+use rustfst::fst_traits::SerializableFst;
+use rustfst::semirings::TropicalWeight;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = VectorFst::<TropicalWeight>::load(data);
+});
@@ -1,6 +1,8 @@
+use anyhow::Result;
 use unsafe_unwrap::UnsafeUnwrap;
 
 use crate::algorithms::closure::ClosureType;
+use crate::algorithms::ResourceLimits;
 use crate::fst_properties::mutable_properties::closure_properties;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
@@ -69,3 +71,72 @@ where
         FstProperties::all_properties(),
     );
 }
+
+/// Computes the closure in place like `closure`, failing with `Error::ResourceLimitExceeded`
+/// if the result would exceed `limits`. The FST is left untouched in that case.
+pub fn closure_with_limits<W, F>(
+    fst: &mut F,
+    closure_type: ClosureType,
+    limits: &ResourceLimits,
+) -> Result<()>
+where
+    W: Semiring,
+    F: MutableFst<W>,
+{
+    if !limits.is_unlimited() {
+        let mut num_states = fst.num_states();
+        let mut num_trs: usize = fst
+            .states_iter()
+            .map(|s| unsafe { fst.num_trs_unchecked(s) })
+            .sum();
+        if fst.start().is_some() {
+            num_trs += fst.final_states_iter().count();
+        }
+        if closure_type == ClosureType::ClosureStar {
+            num_states += 1;
+            if fst.start().is_some() {
+                num_trs += 1;
+            }
+        }
+        limits.check::<W>(num_states, num_trs)?;
+    }
+    closure(fst, closure_type);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Error;
+
+    #[test]
+    fn test_closure_with_limits() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, 0.5)?;
+        fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+        let fst_in = fst.clone();
+
+        let limits = ResourceLimits::new().with_max_states(2);
+        let err = closure_with_limits(&mut fst, ClosureType::ClosureStar, &limits).unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::ResourceLimitExceeded {
+                resource: "states",
+                limit: 2
+            })
+        );
+        assert_eq!(fst, fst_in);
+
+        let limits = limits.with_max_trs(2);
+        closure_with_limits(&mut fst, ClosureType::ClosurePlus, &limits)?;
+        let mut expected = fst_in;
+        closure(&mut expected, ClosureType::ClosurePlus);
+        assert_eq!(fst, expected);
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ use crate::algorithms::compose::{
 use crate::algorithms::lazy::{
     FstCache, LazyFst, SerializableCache, SerializableLazyFst, SimpleVecCache,
 };
+use crate::algorithms::{Progress, ResourceLimits};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::parsers::SerializeBinary;
@@ -162,6 +163,24 @@ where
     pub fn compute<F: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F> {
         self.0.compute()
     }
+
+    /// Turns the Lazy FST into a static one, polling `progress` after each state expanded.
+    pub fn compute_with_progress<F: MutableFst<W> + AllocableFst<W>>(
+        &self,
+        progress: &Progress,
+    ) -> Result<F> {
+        self.0.compute_with_progress(progress)
+    }
+
+    /// Turns the Lazy FST into a static one, polling `progress` after each state expanded and
+    /// failing as soon as the output exceeds `limits`.
+    pub fn compute_with_limits<F: MutableFst<W> + AllocableFst<W>>(
+        &self,
+        progress: &Progress,
+        limits: &ResourceLimits,
+    ) -> Result<F> {
+        self.0.compute_with_limits(progress, limits)
+    }
 }
 
 impl<W, F1, F2, B1, B2>
@@ -10,10 +10,12 @@ use crate::algorithms::compose::compose_filters::{
 };
 use crate::algorithms::compose::matchers::{Matcher, SigmaMatcher, SortedMatcher};
 use crate::algorithms::compose::ComposeFst;
+use crate::algorithms::{Progress, ResourceLimits};
 use crate::fst_traits::{AllocableFst, ExpandedFst, Fst, MutableFst};
 use crate::prelude::compose::matchers::{MatchType, MatcherRewriteMode};
 use crate::prelude::compose::ComposeFstOpOptions;
 use crate::semirings::Semiring;
+use crate::symbol_table::check_compat_symbols;
 use crate::Label;
 
 #[derive(PartialOrd, PartialEq, Debug, Clone, Copy)]
@@ -45,12 +47,20 @@ impl MatcherConfig {
     }
 }
 
-#[derive(PartialOrd, PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct ComposeConfig {
     pub compose_filter: ComposeFilterEnum,
     pub matcher1_config: MatcherConfig,
     pub matcher2_config: MatcherConfig,
     pub connect: bool,
+    /// Fail if the output symbols of the first FST and the input symbols of the second FST
+    /// are not compatible according to `compat_symbols`. The check is also enabled for all
+    /// the compositions by `set_compat_symbols_check`.
+    pub compat_symbols: bool,
+    /// Polled after each state of the composition is expanded.
+    pub progress: Progress,
+    /// Bounds on the size of the composition.
+    pub limits: ResourceLimits,
 }
 
 impl Default for ComposeConfig {
@@ -60,6 +70,9 @@ impl Default for ComposeConfig {
             matcher1_config: MatcherConfig::default(),
             matcher2_config: MatcherConfig::default(),
             connect: true,
+            compat_symbols: false,
+            progress: Progress::default(),
+            limits: ResourceLimits::default(),
         }
     }
 }
@@ -114,7 +127,8 @@ macro_rules! run_compose {
         $f1: ty, $f2: ty,
         $builder: tt,
         $matcher1: expr, $matcher1_ty: ty,
-        $matcher2: expr, $matcher2_ty: ty
+        $matcher2: expr, $matcher2_ty: ty,
+        $progress: expr, $limits: expr
     ) => {{
         let compose_fst_op_opts = ComposeFstOpOptions::new($matcher1, $matcher2, None, None);
         ComposeFst::<
@@ -127,35 +141,35 @@ macro_rules! run_compose {
             _,
             $builder<_, _, _, _, _, $matcher1_ty, $matcher2_ty>,
         >::new_with_options($fst1, $fst2, compose_fst_op_opts)?
-        .compute()?
+        .compute_with_limits($progress, $limits)?
     }};
 }
 
 macro_rules! compose_generate_matchers {
     (
         $fst1: expr, $fst2: expr, $f1: ty, $f2: ty,
-        $builder: tt, $matcher1_enum: expr, $matcher2_enum: expr
+        $builder: tt, $matcher1_enum: expr, $matcher2_enum: expr, $progress: expr, $limits: expr
     ) => {
         {
             match ($matcher1_enum, $matcher2_enum) {
                 (MatcherEnum::SortedMatcher(m1), MatcherEnum::SortedMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SortedMatcher<_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SortedMatcher<_,_,_>, $progress, $limits
                     )
                 },
                 (MatcherEnum::SigmaMatcher(m1), MatcherEnum::SortedMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SortedMatcher<_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SortedMatcher<_,_,_>, $progress, $limits
                     )
                 },
                 (MatcherEnum::SortedMatcher(m1), MatcherEnum::SigmaMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SigmaMatcher<_,_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SigmaMatcher<_,_,_,_>, $progress, $limits
                     )
                 },
                 (MatcherEnum::SigmaMatcher(m1), MatcherEnum::SigmaMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SigmaMatcher<_,_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SigmaMatcher<_,_,_,_>, $progress, $limits
                     )
                 }
             }
@@ -175,6 +189,12 @@ pub fn compose_with_config<
     fst2: B2,
     config: ComposeConfig,
 ) -> Result<F3> {
+    check_compat_symbols(
+        fst1.borrow().output_symbols().map(|s| s.as_ref()),
+        fst2.borrow().input_symbols().map(|s| s.as_ref()),
+        config.compat_symbols,
+        "output symbols of the first FST and input symbols of the second FST in compose",
+    )?;
     let matcher1 = config
         .matcher1_config
         .create_matcher(fst1.borrow(), MatchType::MatchOutput)?;
@@ -185,7 +205,8 @@ pub fn compose_with_config<
     let mut ofst: F3 = match config.compose_filter {
         ComposeFilterEnum::AutoFilter => {
             if config.matcher1_config.empty() && config.matcher2_config.empty() {
-                ComposeFst::new_auto(fst1, fst2)?.compute()?
+                ComposeFst::new_auto(fst1, fst2)?
+                    .compute_with_limits(&config.progress, &config.limits)?
             } else {
                 bail!("Custom MatcherConfig not supported with AutoFilter")
             }
@@ -198,7 +219,9 @@ pub fn compose_with_config<
                 F2,
                 NullComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                &config.progress,
+                &config.limits
             )
         }
         ComposeFilterEnum::SequenceFilter => {
@@ -209,7 +232,9 @@ pub fn compose_with_config<
                 F2,
                 SequenceComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                &config.progress,
+                &config.limits
             )
         }
         ComposeFilterEnum::AltSequenceFilter => {
@@ -220,7 +245,9 @@ pub fn compose_with_config<
                 F2,
                 AltSequenceComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                &config.progress,
+                &config.limits
             )
         }
         ComposeFilterEnum::MatchFilter => {
@@ -231,7 +258,9 @@ pub fn compose_with_config<
                 F2,
                 MatchComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                &config.progress,
+                &config.limits
             )
         }
         ComposeFilterEnum::NoMatchFilter => {
@@ -242,7 +271,9 @@ pub fn compose_with_config<
                 F2,
                 NoMatchComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                &config.progress,
+                &config.limits
             )
         }
         ComposeFilterEnum::TrivialFilter => {
@@ -253,7 +284,9 @@ pub fn compose_with_config<
                 F2,
                 TrivialComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                &config.progress,
+                &config.limits
             )
         }
     };
@@ -304,3 +337,107 @@ pub fn compose<
     let config = ComposeConfig::default();
     compose_with_config(fst1, fst2, config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+    use crate::{symt, Error, SymbolTable, Tr};
+
+    fn fst_with_symbols(
+        isymt: SymbolTable,
+        osymt: SymbolTable,
+    ) -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 1, 0.5, s1))?;
+        fst.set_final(s1, 0.0)?;
+        fst.set_input_symbols(Arc::new(isymt));
+        fst.set_output_symbols(Arc::new(osymt));
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_compose_compat_symbols() -> Result<()> {
+        let lexicon = fst_with_symbols(symt!["a"], symt!["hello", "world"])?;
+        let grammar = fst_with_symbols(symt!["world", "hello"], symt!["x"])?;
+        let config = ComposeConfig {
+            compat_symbols: true,
+            ..ComposeConfig::default()
+        };
+        let res: Result<VectorFst<_>> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon,
+            &grammar,
+            config.clone(),
+        );
+        assert!(res.is_err());
+
+        // The check is opt-in.
+        let _: VectorFst<_> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon,
+            &grammar,
+            ComposeConfig::default(),
+        )?;
+
+        let grammar = fst_with_symbols(symt!["hello", "world"], symt!["x"])?;
+        let _: VectorFst<_> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon, &grammar, config,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_cancelled() -> Result<()> {
+        let lexicon = fst_with_symbols(symt!["a"], symt!["hello"])?;
+        let grammar = fst_with_symbols(symt!["hello"], symt!["x"])?;
+        let progress = Progress::new();
+        let config = ComposeConfig {
+            progress: progress.clone(),
+            ..ComposeConfig::default()
+        };
+        let _: VectorFst<_> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon,
+            &grammar,
+            config.clone(),
+        )?;
+
+        progress.cancel();
+        let res: Result<VectorFst<_>> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon, &grammar, config,
+        );
+        assert_eq!(Error::find(&res.unwrap_err()), Some(Error::Cancelled));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_with_limits() -> Result<()> {
+        let lexicon = fst_with_symbols(symt!["a"], symt!["hello"])?;
+        let grammar = fst_with_symbols(symt!["hello"], symt!["x"])?;
+        let config = ComposeConfig {
+            limits: ResourceLimits::new().with_max_states(2),
+            ..ComposeConfig::default()
+        };
+        let _: VectorFst<_> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon, &grammar, config,
+        )?;
+
+        let config = ComposeConfig {
+            limits: ResourceLimits::new().with_max_trs(0),
+            ..ComposeConfig::default()
+        };
+        let res: Result<VectorFst<_>> = compose_with_config::<_, VectorFst<_>, VectorFst<_>, _, _, _>(
+            &lexicon, &grammar, config,
+        );
+        assert_eq!(
+            Error::find(&res.unwrap_err()),
+            Some(Error::ResourceLimitExceeded {
+                resource: "trs",
+                limit: 0
+            })
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,3 @@
-use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::slice::Iter as IterSlice;
@@ -7,7 +6,8 @@ use superslice::Ext;
 use unsafe_unwrap::UnsafeUnwrap;
 
 /// Half-open integral interval [a, b) of signed integers of type T.
-#[derive(PartialEq, Clone, Eq, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntInterval {
     pub begin: usize,
     pub end: usize,
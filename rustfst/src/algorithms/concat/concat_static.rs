@@ -4,12 +4,16 @@ use crate::fst_properties::mutable_properties::concat_properties;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
 use crate::semirings::Semiring;
+use crate::symbol_table::check_compat_symbols;
 use crate::{StateId, Tr, Trs, EPS_LABEL};
 
 /// Performs the concatenation of two wFSTs. If `A` transduces string `x` to `y` with weight `a`
 /// and `B` transduces string `w` to `v` with weight `b`, then their concatenation
 /// transduces string `xw` to `yv` with weight `a ⊗ b`.
 ///
+/// If the check is enabled with `set_compat_symbols_check`, the symbol tables of the two FSTs
+/// must be compatible.
+///
 /// # Example 1
 /// ```
 /// # #[macro_use] extern crate rustfst;
@@ -56,6 +60,18 @@ where
     F1: ExpandedFst<W> + MutableFst<W> + AllocableFst<W>,
     F2: ExpandedFst<W>,
 {
+    check_compat_symbols(
+        fst_1.input_symbols().map(|s| s.as_ref()),
+        fst_2.input_symbols().map(|s| s.as_ref()),
+        false,
+        "input symbols in concat",
+    )?;
+    check_compat_symbols(
+        fst_1.output_symbols().map(|s| s.as_ref()),
+        fst_2.output_symbols().map(|s| s.as_ref()),
+        false,
+        "output symbols in concat",
+    )?;
     let props1 = fst_1.properties();
     let props2 = fst_2.properties();
     let start1 = fst_1.start();
@@ -1,12 +1,18 @@
+use crate::algorithms::connect;
 use crate::algorithms::determinize::divisors::CommonDivisor;
-use crate::algorithms::determinize::DeterminizeFsaOp;
+use crate::algorithms::determinize::{DeterminizeConfig, DeterminizeFsaOp};
 use crate::algorithms::lazy::{LazyFst, SimpleHashMapCache};
+use crate::algorithms::shortest_path::natural_less;
+use crate::algorithms::{Progress, ResourceLimits};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
-use crate::{Semiring, StateId, SymbolTable, TrsVec};
+use crate::{Error, Semiring, StateId, SymbolTable, Tr, Trs, TrsVec};
 use anyhow::Result;
+use binary_heap_plus::BinaryHeap;
 use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -159,10 +165,151 @@ where
         self.0.compute()
     }
 
+    /// Turns the Lazy FST into a static one, polling `progress` after each state expanded and
+    /// failing as soon as the output exceeds `limits`.
+    pub fn compute_with_limits<F2: MutableFst<W> + AllocableFst<W>>(
+        &self,
+        progress: &Progress,
+        limits: &ResourceLimits,
+    ) -> Result<F2> {
+        self.0.compute_with_limits(progress, limits)
+    }
+
     pub fn out_dist(self) -> Result<Vec<W>> {
         self.0.op.out_dist()
     }
 
+    /// Turns the Lazy FST into a static one, expanding the states in best-first order and
+    /// only keeping the ones allowed by the `weight_threshold` and `state_threshold` of the
+    /// config.
+    ///
+    /// `in_dist` contains the distances to the final states of the input FST, expressed in the
+    /// path semiring `WP`. `to_path_weight` converts the weights of the FSA to this semiring.
+    /// When `max_trs` transitions have been created, the other ones are dropped.
+    pub fn compute_pruned<WP, FN, F2>(
+        &self,
+        in_dist: &[WP],
+        to_path_weight: FN,
+        config: &DeterminizeConfig<WP>,
+        max_trs: Option<usize>,
+    ) -> Result<F2>
+    where
+        WP: Semiring,
+        FN: Fn(&W) -> Result<WP>,
+        F2: MutableFst<W> + AllocableFst<W>,
+    {
+        let mut ofst = F2::new();
+        let start = match self.start() {
+            Some(s) => s,
+            None => return Ok(ofst),
+        };
+        let delta = config.delta;
+        let distance = |s: StateId| self.0.op.subset_distance(s, in_dist, &to_path_weight);
+        let limit = match &config.weight_threshold {
+            Some(threshold) => Some(distance(start)?.times(threshold)?),
+            None => None,
+        };
+        let is_pruned = |w: &WP| -> Result<bool> {
+            match &limit {
+                Some(limit) => Ok(!w.approx_equal(limit, delta) && natural_less(limit, w)?),
+                None => Ok(false),
+            }
+        };
+
+        // Indexed by the states of the output FST.
+        let mut det_states = vec![start];
+        let mut alpha = vec![WP::one()];
+        let mut beta = vec![distance(start)?];
+        let mut expanded = vec![false];
+        let mut states = HashMap::new();
+        let mut num_trs = 0;
+
+        let ostart = ofst.add_state();
+        ofst.set_start(ostart)?;
+        states.insert(start, ostart);
+
+        // Max heap : the best priority must be the greatest element.
+        let mut heap = BinaryHeap::new_by(|a: &(WP, StateId), b: &(WP, StateId)| {
+            if natural_less(&a.0, &b.0).unwrap_or(false) {
+                Ordering::Greater
+            } else if natural_less(&b.0, &a.0).unwrap_or(false) {
+                Ordering::Less
+            } else {
+                b.1.cmp(&a.1)
+            }
+        });
+        heap.push((beta[0].clone(), ostart));
+
+        let mut num_expanded = 0;
+        while let Some((_, s)) = heap.pop() {
+            let s_idx = s as usize;
+            if expanded[s_idx] {
+                continue;
+            }
+            expanded[s_idx] = true;
+            num_expanded += 1;
+            config.progress.step(num_expanded)?;
+            config.limits.check::<W>(ofst.num_states(), num_trs)?;
+            let det_s = det_states[s_idx];
+            let alpha_s = alpha[s_idx].clone();
+
+            if let Some(final_weight) = self.final_weight(det_s)? {
+                if !is_pruned(&alpha_s.times(to_path_weight(&final_weight)?)?)? {
+                    ofst.set_final(s, final_weight)?;
+                }
+            }
+
+            for tr in self.get_trs(det_s)?.trs() {
+                let alpha_next = alpha_s.times(to_path_weight(&tr.weight)?)?;
+                let nextstate = match states.get(&tr.nextstate) {
+                    Some(n) => *n,
+                    None => {
+                        let beta_next = distance(tr.nextstate)?;
+                        if is_pruned(&alpha_next.times(&beta_next)?)? {
+                            continue;
+                        }
+                        if let Some(state_threshold) = config.state_threshold {
+                            if ofst.num_states() >= state_threshold as usize {
+                                if config.partial_on_state_threshold {
+                                    continue;
+                                }
+                                return Err(
+                                    Error::DeterminizeStateThreshold { state_threshold }.into()
+                                );
+                            }
+                        }
+                        let n = ofst.add_state();
+                        states.insert(tr.nextstate, n);
+                        det_states.push(tr.nextstate);
+                        alpha.push(WP::zero());
+                        beta.push(beta_next);
+                        expanded.push(false);
+                        n
+                    }
+                };
+                let n_idx = nextstate as usize;
+                if is_pruned(&alpha_next.times(&beta[n_idx])?)? {
+                    continue;
+                }
+                if matches!(max_trs, Some(max_trs) if num_trs >= max_trs) {
+                    break;
+                }
+                num_trs += 1;
+                ofst.add_tr(
+                    s,
+                    Tr::new(tr.ilabel, tr.olabel, tr.weight.clone(), nextstate),
+                )?;
+                if !expanded[n_idx] && natural_less(&alpha_next, &alpha[n_idx])? {
+                    heap.push((alpha_next.times(&beta[n_idx])?, nextstate));
+                    alpha[n_idx] = alpha_next;
+                }
+            }
+        }
+
+        connect(&mut ofst)?;
+        Ok(ofst)
+    }
+
     pub fn compute_with_distance<F2: MutableFst<W> + AllocableFst<W>>(
         self,
     ) -> Result<(F2, Vec<W>)> {
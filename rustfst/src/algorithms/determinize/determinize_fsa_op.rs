@@ -16,7 +16,7 @@ use crate::algorithms::lazy::FstOp;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::Fst;
 use crate::semirings::{DivideType, WeaklyDivisibleSemiring, WeightQuantize};
-use crate::{Label, Semiring, StateId, Tr, Trs, TrsVec};
+use crate::{Error, Label, Semiring, StateId, Tr, Trs, TrsVec};
 
 #[derive(Debug)]
 pub struct DeterminizeFsaOp<W, F, CD, B, BT>
@@ -134,7 +134,9 @@ where
 {
     pub fn new(fst: B, in_dist: Option<BT>, delta: f32) -> Result<Self> {
         if !fst.borrow().properties().contains(FstProperties::ACCEPTOR) {
-            bail!("DeterminizeFsaImpl : expected acceptor as argument");
+            bail!(Error::NotAcceptor {
+                operation: "determinize",
+            });
         }
         Ok(Self {
             fst,
@@ -183,6 +185,28 @@ where
         self.state_table.find_id_from_ref(tuple)
     }
 
+    /// Computes the distance from the subset of `state` to the final states, given the
+    /// distances `in_dist` of the input states converted with `to_path_weight`.
+    pub fn subset_distance<WP, FN>(
+        &self,
+        state: StateId,
+        in_dist: &[WP],
+        to_path_weight: FN,
+    ) -> Result<WP>
+    where
+        WP: Semiring,
+        FN: Fn(&W) -> Result<WP>,
+    {
+        let tuple = self.state_table.find_tuple(state);
+        let mut distance = WP::zero();
+        for elt in tuple.subset.iter() {
+            if let Some(d) = in_dist.get(elt.state as usize) {
+                distance.plus_assign(to_path_weight(&elt.weight)?.times(d)?)?;
+            }
+        }
+        Ok(distance)
+    }
+
     pub fn out_dist(self) -> Result<Vec<W>> {
         let out_dist = self.state_table.out_dist();
         out_dist
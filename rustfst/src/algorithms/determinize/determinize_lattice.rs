@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::divisors::CompactLatticeCommonDivisor;
+use crate::algorithms::determinize::{DeterminizeConfig, DeterminizeFsa};
+use crate::algorithms::rm_epsilon::rm_epsilon;
+use crate::algorithms::shortest_distance;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::{CompactLatticeWeight, LatticeWeight, Semiring};
+use crate::{StateId, Tr, Trs, EPS_LABEL, KDELTA};
+
+/// Default beam of `determinize_lattice_pruned`, same as Kaldi.
+pub const DEFAULT_LATTICE_BEAM: f32 = 10.0;
+
+#[derive(Clone, Debug, Copy, PartialOrd, PartialEq)]
+pub struct DeterminizeLatticeConfig {
+    pub delta: f32,
+    /// Only keep the paths whose cost is within `beam` of the cost of the best path.
+    pub beam: f32,
+    /// Maximum number of states of the output.
+    pub max_states: Option<StateId>,
+    /// Maximum number of transitions of the output.
+    pub max_trs: Option<usize>,
+}
+
+impl DeterminizeLatticeConfig {
+    pub fn new(beam: f32) -> Self {
+        Self {
+            beam,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_delta(self, delta: f32) -> Self {
+        Self { delta, ..self }
+    }
+
+    pub fn with_beam(self, beam: f32) -> Self {
+        Self { beam, ..self }
+    }
+
+    pub fn with_max_states(self, max_states: Option<StateId>) -> Self {
+        Self { max_states, ..self }
+    }
+
+    pub fn with_max_trs(self, max_trs: Option<usize>) -> Self {
+        Self { max_trs, ..self }
+    }
+}
+
+impl Default for DeterminizeLatticeConfig {
+    fn default() -> Self {
+        Self {
+            delta: KDELTA,
+            beam: DEFAULT_LATTICE_BEAM,
+            max_states: None,
+            max_trs: None,
+        }
+    }
+}
+
+/// Moves the output labels of a lattice into the strings of the weights.
+fn lattice_to_compact_acceptor<F: ExpandedFst<LatticeWeight>>(
+    ifst: &F,
+) -> Result<VectorFst<CompactLatticeWeight>> {
+    let mut ofst = VectorFst::new();
+    ofst.add_states(ifst.num_states());
+    if let Some(start) = ifst.start() {
+        ofst.set_start(start)?;
+    }
+    for s in 0..(ifst.num_states() as StateId) {
+        for tr in ifst.get_trs(s)?.trs() {
+            let string = if tr.olabel == EPS_LABEL {
+                vec![]
+            } else {
+                vec![tr.olabel]
+            };
+            ofst.add_tr(
+                s,
+                Tr::new(
+                    tr.ilabel,
+                    tr.ilabel,
+                    CompactLatticeWeight::new((tr.weight, string)),
+                    tr.nextstate,
+                ),
+            )?;
+        }
+        if let Some(final_weight) = ifst.final_weight(s)? {
+            ofst.set_final(s, CompactLatticeWeight::new((final_weight, vec![])))?;
+        }
+    }
+    Ok(ofst)
+}
+
+/// Determinizes a Kaldi lattice, keeping for each input string only the best output string
+/// and pruning the paths whose cost is not within `beam` of the best path.
+///
+/// The input labels of the lattice are the labels of the output compact lattice and the
+/// output labels end up in the strings of the `CompactLatticeWeight`. Epsilon input labels are
+/// removed.
+///
+/// The states are expanded in best-first order. When `max_states` or `max_trs` is reached the
+/// remaining states and transitions are dropped, the output then only contains the best paths
+/// found so far.
+pub fn determinize_lattice_pruned<F1, F2>(ifst: &F1, config: DeterminizeLatticeConfig) -> Result<F2>
+where
+    F1: ExpandedFst<LatticeWeight>,
+    F2: MutableFst<CompactLatticeWeight> + AllocableFst<CompactLatticeWeight>,
+{
+    let mut acceptor = lattice_to_compact_acceptor(ifst)?;
+    rm_epsilon(&mut acceptor)?;
+
+    let in_dist: Vec<LatticeWeight> = shortest_distance(&acceptor, true)?
+        .into_iter()
+        .map(|w| *w.weight())
+        .collect();
+    let det_config = DeterminizeConfig::default()
+        .with_delta(config.delta)
+        .with_weight_threshold(Some((config.beam, 0.0).into()))
+        .with_state_threshold(config.max_states)
+        .with_partial_on_state_threshold(true);
+
+    let det_fsa: DeterminizeFsa<
+        CompactLatticeWeight,
+        VectorFst<CompactLatticeWeight>,
+        CompactLatticeCommonDivisor,
+        _,
+        Vec<CompactLatticeWeight>,
+    > = DeterminizeFsa::new(&acceptor, None, config.delta)?;
+    let mut ofst: F2 = det_fsa.compute_pruned(
+        &in_dist,
+        |w: &CompactLatticeWeight| Ok(*w.weight()),
+        &det_config,
+        config.max_trs,
+    )?;
+
+    if let Some(symt) = ifst.input_symbols() {
+        ofst.set_input_symbols(Arc::clone(symt));
+        ofst.set_output_symbols(Arc::clone(symt));
+    }
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_traits::{CoreFst, Fst};
+    use crate::Label;
+
+    fn compact(cost: (f32, f32), string: Vec<Label>) -> CompactLatticeWeight {
+        CompactLatticeWeight::new((cost.into(), string))
+    }
+
+    #[test]
+    fn test_determinize_lattice_keeps_best_string() -> Result<()> {
+        let mut lattice = VectorFst::<LatticeWeight>::new();
+        let s0 = lattice.add_state();
+        let s1 = lattice.add_state();
+        let s2 = lattice.add_state();
+        lattice.set_start(s0)?;
+        lattice.set_final(s1, LatticeWeight::one())?;
+        lattice.set_final(s2, LatticeWeight::one())?;
+        lattice.add_tr(s0, Tr::new(1, 10, (1.0, 2.0), s1))?;
+        lattice.add_tr(s0, Tr::new(1, 20, (0.5, 0.5), s2))?;
+
+        let det: VectorFst<CompactLatticeWeight> =
+            determinize_lattice_pruned(&lattice, DeterminizeLatticeConfig::default())?;
+
+        let paths: Vec<_> = det.paths_iter().collect();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].ilabels, vec![1]);
+        assert_eq!(paths[0].weight, compact((0.5, 0.5), vec![20]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_lattice_epsilons() -> Result<()> {
+        let mut lattice = VectorFst::<LatticeWeight>::new();
+        let s0 = lattice.add_state();
+        let s1 = lattice.add_state();
+        let s2 = lattice.add_state();
+        lattice.set_start(s0)?;
+        lattice.set_final(s2, (0.0, 1.0))?;
+        lattice.add_tr(s0, Tr::new(EPS_LABEL, 5, (1.0, 0.0), s1))?;
+        lattice.add_tr(s1, Tr::new(2, 6, (0.0, 1.0), s2))?;
+
+        let det: VectorFst<CompactLatticeWeight> =
+            determinize_lattice_pruned(&lattice, DeterminizeLatticeConfig::default())?;
+
+        let paths: Vec<_> = det.paths_iter().collect();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].ilabels, vec![2]);
+        assert_eq!(paths[0].weight, compact((1.0, 2.0), vec![5, 6]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_lattice_beam_and_limits() -> Result<()> {
+        let mut lattice = VectorFst::<LatticeWeight>::new();
+        let s0 = lattice.add_state();
+        let s1 = lattice.add_state();
+        let s2 = lattice.add_state();
+        let s3 = lattice.add_state();
+        lattice.set_start(s0)?;
+        lattice.set_final(s1, LatticeWeight::one())?;
+        lattice.set_final(s3, LatticeWeight::one())?;
+        lattice.add_tr(s0, Tr::new(1, 1, (1.0, 0.0), s1))?;
+        lattice.add_tr(s0, Tr::new(2, 2, (2.0, 2.0), s2))?;
+        lattice.add_tr(s2, Tr::new(3, 3, (1.0, 0.0), s3))?;
+
+        let det: VectorFst<CompactLatticeWeight> =
+            determinize_lattice_pruned(&lattice, DeterminizeLatticeConfig::new(10.0))?;
+        assert_eq!(det.paths_iter().count(), 2);
+
+        let det: VectorFst<CompactLatticeWeight> =
+            determinize_lattice_pruned(&lattice, DeterminizeLatticeConfig::new(3.0))?;
+        assert_eq!(det.paths_iter().count(), 1);
+
+        let config = DeterminizeLatticeConfig::default().with_max_states(Some(2));
+        let det: VectorFst<CompactLatticeWeight> = determinize_lattice_pruned(&lattice, config)?;
+        assert_eq!(det.num_states(), 2);
+
+        let config = DeterminizeLatticeConfig::default().with_max_trs(Some(1));
+        let det: VectorFst<CompactLatticeWeight> = determinize_lattice_pruned(&lattice, config)?;
+        assert_eq!(det.paths_iter().count(), 1);
+        assert_eq!(det.num_trs(det.start().unwrap())?, 1);
+        Ok(())
+    }
+}
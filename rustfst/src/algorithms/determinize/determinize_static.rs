@@ -11,6 +11,9 @@ use crate::algorithms::factor_weight::factor_iterators::{
 use crate::algorithms::factor_weight::{factor_weight, FactorWeightOptions, FactorWeightType};
 use crate::algorithms::weight_convert;
 use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
+use crate::algorithms::{
+    shortest_distance_with_config, Progress, ResourceLimits, ShortestDistanceConfig,
+};
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::mutable_properties::determinize_properties;
 use crate::fst_properties::FstProperties;
@@ -19,7 +22,7 @@ use crate::semirings::SemiringProperties;
 use crate::semirings::{
     GallicWeight, GallicWeightMin, GallicWeightRestrict, WeaklyDivisibleSemiring, WeightQuantize,
 };
-use crate::{EPS_LABEL, KDELTA};
+use crate::{Error, Label, Semiring, StateId, EPS_LABEL, KDELTA};
 
 pub fn determinize_with_distance<W, F1, F2>(
     ifst: &F1,
@@ -32,13 +35,21 @@ where
     F2: MutableFst<W> + AllocableFst<W>,
 {
     if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
-        bail!("determinize_fsa : weight must be left distributive")
+        bail!(Error::MissingSemiringProperties {
+            operation: "determinize",
+            properties: SemiringProperties::LEFT_SEMIRING,
+        })
     }
     let fst = DeterminizeFsa::<_, F1, DefaultCommonDivisor, _, _>::new(ifst, Some(in_dist), delta)?;
     fst.compute_with_distance()
 }
 
-pub fn determinize_fsa<W, F1, F2, CD>(fst_in: &F1, delta: f32) -> Result<F2>
+fn determinize_fsa_with_limits<W, F1, F2, CD>(
+    fst_in: &F1,
+    delta: f32,
+    progress: &Progress,
+    limits: &ResourceLimits,
+) -> Result<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize,
     F1: Fst<W>,
@@ -46,41 +57,108 @@ where
     CD: CommonDivisor<W>,
 {
     if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
-        bail!("determinize_fsa : weight must be left distributive")
+        bail!(Error::MissingSemiringProperties {
+            operation: "determinize",
+            properties: SemiringProperties::LEFT_SEMIRING,
+        })
     }
     let det_fsa: DeterminizeFsa<W, F1, CD, _, Vec<W>> = DeterminizeFsa::new(fst_in, None, delta)?;
-    det_fsa.compute()
+    det_fsa.compute_with_limits(progress, limits)
+}
+
+fn determinize_fsa_pruned<W, WP, F1, F2, CD, FN>(
+    fst_in: &F1,
+    in_dist: &[WP],
+    to_path_weight: FN,
+    config: &DeterminizeConfig<WP>,
+) -> Result<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    WP: Semiring,
+    F1: Fst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+    CD: CommonDivisor<W>,
+    FN: Fn(&W) -> Result<WP>,
+{
+    if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
+        bail!(Error::MissingSemiringProperties {
+            operation: "determinize",
+            properties: SemiringProperties::LEFT_SEMIRING,
+        })
+    }
+    let det_fsa: DeterminizeFsa<W, F1, CD, _, Vec<W>> =
+        DeterminizeFsa::new(fst_in, None, config.delta)?;
+    det_fsa.compute_pruned(in_dist, to_path_weight, config, None)
 }
 
-pub fn determinize_fst<W, F1, F2>(fst_in: &F1, det_type: DeterminizeType, delta: f32) -> Result<F2>
+/// Returns the distances to the final states of `fst_in` used to prune the determinization or
+/// `None` if no pruning is requested.
+fn pruning_in_dist<W, F>(fst_in: &F, config: &DeterminizeConfig<W>) -> Result<Option<Vec<W>>>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+{
+    if !config.is_pruned() {
+        return Ok(None);
+    }
+    if !W::properties().contains(SemiringProperties::PATH) {
+        bail!(Error::MissingSemiringProperties {
+            operation: "determinize",
+            properties: SemiringProperties::PATH,
+        })
+    }
+    let sd_config = ShortestDistanceConfig::default().with_progress(config.progress.clone());
+    Ok(Some(shortest_distance_with_config(
+        fst_in, true, sd_config,
+    )?))
+}
+
+pub fn determinize_fst<W, F1, F2>(fst_in: &F1, config: &DeterminizeConfig<W>) -> Result<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
     F1: ExpandedFst<W>,
     F2: MutableFst<W> + AllocableFst<W>,
 {
+    let delta = config.delta;
+    let in_dist = pruning_in_dist(fst_in, config)?;
     let mut to_gallic = ToGallicConverter {};
     let mut from_gallic = FromGallicConverter {
-        superfinal_label: EPS_LABEL,
+        superfinal_label: config.subsequential_label,
     };
 
     let factor_opts = FactorWeightOptions {
         delta: KDELTA,
         mode: FactorWeightType::FACTOR_FINAL_WEIGHTS,
-        final_ilabel: EPS_LABEL,
-        final_olabel: EPS_LABEL,
+        final_ilabel: config.subsequential_label,
+        final_olabel: config.subsequential_label,
         increment_final_ilabel: false,
         increment_final_olabel: false,
     };
 
-    match det_type {
+    match config.det_type {
         DeterminizeType::DeterminizeDisambiguate => {
             if !W::properties().contains(SemiringProperties::PATH) {
-                bail!("determinize : weight needs to have the path property to disambiguate output")
+                bail!(Error::MissingSemiringProperties {
+                    operation: "determinize",
+                    properties: SemiringProperties::PATH,
+                })
             }
             let fsa: VectorFst<GallicWeightMin<W>> =
                 weight_convert(fst_in.borrow(), &mut to_gallic)?;
-            let determinized_fsa: VectorFst<GallicWeightMin<W>> =
-                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta)?;
+            let determinized_fsa: VectorFst<GallicWeightMin<W>> = match &in_dist {
+                Some(in_dist) => determinize_fsa_pruned::<_, _, _, _, GallicCommonDivisor, _>(
+                    &fsa,
+                    in_dist,
+                    |w: &GallicWeightMin<W>| Ok(w.value2().clone()),
+                    config,
+                )?,
+                None => determinize_fsa_with_limits::<_, VectorFst<_>, _, GallicCommonDivisor>(
+                    &fsa,
+                    delta,
+                    &config.progress,
+                    &config.limits,
+                )?,
+            };
             let factored_determinized_fsa: VectorFst<GallicWeightMin<W>> =
                 factor_weight::<_, VectorFst<GallicWeightMin<W>>, _, _, GallicFactorMin<W>>(
                     &determinized_fsa,
@@ -91,8 +169,20 @@ where
         DeterminizeType::DeterminizeFunctional => {
             let fsa: VectorFst<GallicWeightRestrict<W>> =
                 weight_convert(fst_in.borrow(), &mut to_gallic)?;
-            let determinized_fsa: VectorFst<GallicWeightRestrict<W>> =
-                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta)?;
+            let determinized_fsa: VectorFst<GallicWeightRestrict<W>> = match &in_dist {
+                Some(in_dist) => determinize_fsa_pruned::<_, _, _, _, GallicCommonDivisor, _>(
+                    &fsa,
+                    in_dist,
+                    |w: &GallicWeightRestrict<W>| Ok(w.value2().clone()),
+                    config,
+                )?,
+                None => determinize_fsa_with_limits::<_, VectorFst<_>, _, GallicCommonDivisor>(
+                    &fsa,
+                    delta,
+                    &config.progress,
+                    &config.limits,
+                )?,
+            };
             let factored_determinized_fsa: VectorFst<GallicWeightRestrict<W>> =
                 factor_weight::<
                     _,
@@ -105,8 +195,26 @@ where
         }
         DeterminizeType::DeterminizeNonFunctional => {
             let fsa: VectorFst<GallicWeight<W>> = weight_convert(fst_in.borrow(), &mut to_gallic)?;
-            let determinized_fsa: VectorFst<GallicWeight<W>> =
-                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta)?;
+            let determinized_fsa: VectorFst<GallicWeight<W>> = match &in_dist {
+                Some(in_dist) => determinize_fsa_pruned::<_, _, _, _, GallicCommonDivisor, _>(
+                    &fsa,
+                    in_dist,
+                    |w: &GallicWeight<W>| {
+                        let mut res = W::zero();
+                        for w_restrict in w.value() {
+                            res.plus_assign(w_restrict.value2())?;
+                        }
+                        Ok(res)
+                    },
+                    config,
+                )?,
+                None => determinize_fsa_with_limits::<_, VectorFst<_>, _, GallicCommonDivisor>(
+                    &fsa,
+                    delta,
+                    &config.progress,
+                    &config.limits,
+                )?,
+            };
             let factored_determinized_fsa: VectorFst<GallicWeight<W>> =
                 factor_weight::<_, VectorFst<GallicWeight<W>>, _, _, GallicFactor<W>>(
                     &determinized_fsa,
@@ -117,15 +225,34 @@ where
     }
 }
 
-#[derive(Clone, Debug, Copy, PartialOrd, PartialEq)]
-pub struct DeterminizeConfig {
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeterminizeConfig<W: Semiring> {
     pub delta: f32,
     pub det_type: DeterminizeType,
+    /// Only keep the paths whose weight is below the weight of the shortest path times
+    /// this threshold. The weight needs to have the path property.
+    pub weight_threshold: Option<W>,
+    /// Maximum number of states of the output. The weight needs to have the path property.
+    pub state_threshold: Option<StateId>,
+    /// Label used for the transitions to the superfinal state when determinizing a transducer.
+    pub subsequential_label: Label,
+    /// When the state threshold is reached, return the part of the output computed so far
+    /// instead of an `Error::DeterminizeStateThreshold`.
+    pub partial_on_state_threshold: bool,
+    /// Polled after each state of the output is expanded.
+    pub progress: Progress,
+    /// Bounds on the size of the output. When determinizing a transducer, they also apply to
+    /// the intermediate determinized FSA.
+    pub limits: ResourceLimits,
 }
 
-impl DeterminizeConfig {
+impl<W: Semiring> DeterminizeConfig<W> {
     pub fn new(delta: f32, det_type: DeterminizeType) -> Self {
-        Self { delta, det_type }
+        Self {
+            delta,
+            det_type,
+            ..Self::default()
+        }
     }
 
     pub fn with_delta(self, delta: f32) -> Self {
@@ -135,13 +262,59 @@ impl DeterminizeConfig {
     pub fn with_det_type(self, det_type: DeterminizeType) -> Self {
         Self { det_type, ..self }
     }
+
+    pub fn with_weight_threshold(self, weight_threshold: Option<W>) -> Self {
+        Self {
+            weight_threshold,
+            ..self
+        }
+    }
+
+    pub fn with_state_threshold(self, state_threshold: Option<StateId>) -> Self {
+        Self {
+            state_threshold,
+            ..self
+        }
+    }
+
+    pub fn with_subsequential_label(self, subsequential_label: Label) -> Self {
+        Self {
+            subsequential_label,
+            ..self
+        }
+    }
+
+    pub fn with_partial_on_state_threshold(self, partial_on_state_threshold: bool) -> Self {
+        Self {
+            partial_on_state_threshold,
+            ..self
+        }
+    }
+
+    pub fn with_progress(self, progress: Progress) -> Self {
+        Self { progress, ..self }
+    }
+
+    pub fn with_limits(self, limits: ResourceLimits) -> Self {
+        Self { limits, ..self }
+    }
+
+    fn is_pruned(&self) -> bool {
+        self.weight_threshold.is_some() || self.state_threshold.is_some()
+    }
 }
 
-impl Default for DeterminizeConfig {
+impl<W: Semiring> Default for DeterminizeConfig<W> {
     fn default() -> Self {
         Self {
             delta: KDELTA,
             det_type: DeterminizeType::DeterminizeFunctional,
+            weight_threshold: None,
+            state_threshold: None,
+            subsequential_label: EPS_LABEL,
+            partial_on_state_threshold: false,
+            progress: Progress::default(),
+            limits: ResourceLimits::default(),
         }
     }
 }
@@ -159,6 +332,9 @@ where
 /// state has two transitions with the same input label. For this algorithm,
 /// epsilon transitions are treated as regular symbols.
 ///
+/// The output can be pruned during its construction with the `weight_threshold` and
+/// `state_threshold` of the config.
+///
 /// # Example
 ///
 /// ## Input
@@ -169,25 +345,38 @@ where
 ///
 /// ![determinize_out](https://raw.githubusercontent.com/Garvys/rustfst-images-doc/master/images/determinize_out.svg?sanitize=true)
 ///
-pub fn determinize_with_config<W, F1, F2>(fst_in: &F1, config: DeterminizeConfig) -> Result<F2>
+pub fn determinize_with_config<W, F1, F2>(fst_in: &F1, config: DeterminizeConfig<W>) -> Result<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize,
     F1: ExpandedFst<W>,
     F2: MutableFst<W> + AllocableFst<W>,
 {
-    let delta = config.delta;
     let det_type = config.det_type;
     let iprops = fst_in.borrow().properties();
     let mut fst_res: F2 = if iprops.contains(FstProperties::ACCEPTOR) {
-        determinize_fsa::<_, F1, _, DefaultCommonDivisor>(fst_in, delta)?
+        match pruning_in_dist(fst_in, &config)? {
+            Some(in_dist) => determinize_fsa_pruned::<_, _, _, _, DefaultCommonDivisor, _>(
+                fst_in,
+                &in_dist,
+                |w: &W| Ok(w.clone()),
+                &config,
+            )?,
+            None => determinize_fsa_with_limits::<_, F1, _, DefaultCommonDivisor>(
+                fst_in,
+                config.delta,
+                &config.progress,
+                &config.limits,
+            )?,
+        }
     } else {
-        determinize_fst(fst_in, det_type, delta)?
+        determinize_fst(fst_in, &config)?
     };
+    config.limits.check_fst(&fst_res)?;
 
     let distinct_psubsequential_labels = !(det_type == DeterminizeType::DeterminizeNonFunctional);
     fst_res.set_properties(determinize_properties(
         iprops,
-        false,
+        config.subsequential_label != EPS_LABEL,
         distinct_psubsequential_labels,
     ));
     fst_res.set_symts_from_fst(fst_in.borrow());
@@ -197,10 +386,12 @@ where
 #[cfg(test)]
 mod tests {
     use crate::fst_impls::VectorFst;
-    use crate::semirings::TropicalWeight;
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::{LogWeight, TropicalWeight};
     use crate::tr::Tr;
     use crate::Semiring;
     use crate::SymbolTable;
+    use crate::Trs;
     use proptest::prelude::any;
     use proptest::proptest;
     use std::sync::Arc;
@@ -269,6 +460,129 @@ mod tests {
         Ok(())
     }
 
+    fn two_branches_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+
+        fst.set_start(s0)?;
+        fst.set_final(s1, TropicalWeight::one())?;
+        fst.set_final(s3, TropicalWeight::one())?;
+
+        fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+        fst.add_tr(s0, Tr::new(2, 2, 3.0, s2))?;
+        fst.add_tr(s2, Tr::new(3, 3, 0.0, s3))?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_determinize_weight_threshold() -> Result<()> {
+        let input_fst = two_branches_fst()?;
+
+        let config = DeterminizeConfig::default().with_weight_threshold(Some(1.0.into()));
+        let determinized_fst: VectorFst<TropicalWeight> =
+            determinize_with_config(&input_fst, config)?;
+
+        let mut ref_fst = VectorFst::new();
+        let s0 = ref_fst.add_state();
+        let s1 = ref_fst.add_state();
+        ref_fst.set_start(s0)?;
+        ref_fst.set_final(s1, TropicalWeight::one())?;
+        ref_fst.add_tr(s0, Tr::new(1, 1, TropicalWeight::new(1.0), s1))?;
+
+        assert_eq!(determinized_fst, ref_fst);
+
+        let config = DeterminizeConfig::default().with_weight_threshold(Some(2.0.into()));
+        let determinized_fst: VectorFst<TropicalWeight> =
+            determinize_with_config(&input_fst, config)?;
+        assert_eq!(determinized_fst.num_states(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_state_threshold() -> Result<()> {
+        let input_fst = two_branches_fst()?;
+
+        let config = DeterminizeConfig::default().with_state_threshold(Some(2));
+        let err = determinize_with_config::<_, _, VectorFst<_>>(&input_fst, config).unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::DeterminizeStateThreshold { state_threshold: 2 })
+        );
+
+        let config = DeterminizeConfig::default()
+            .with_state_threshold(Some(2))
+            .with_partial_on_state_threshold(true);
+        let determinized_fst: VectorFst<TropicalWeight> =
+            determinize_with_config(&input_fst, config)?;
+        assert_eq!(determinized_fst.num_states(), 2);
+        assert_eq!(determinized_fst.num_trs(0)?, 1);
+        assert_eq!(determinized_fst.get_trs(0)?.trs()[0].ilabel, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_fst_weight_threshold() -> Result<()> {
+        let mut input_fst = VectorFst::<TropicalWeight>::new();
+        let s0 = input_fst.add_state();
+        let s1 = input_fst.add_state();
+        input_fst.set_start(s0)?;
+        input_fst.set_final(s1, TropicalWeight::one())?;
+        input_fst.add_tr(s0, Tr::new(1, 2, 1.0, s1))?;
+        input_fst.add_tr(s0, Tr::new(2, 3, 4.0, s1))?;
+
+        let config = DeterminizeConfig::default().with_weight_threshold(Some(1.0.into()));
+        let determinized_fst: VectorFst<TropicalWeight> =
+            determinize_with_config(&input_fst, config)?;
+
+        let paths: Vec<_> = determinized_fst.paths_iter().collect();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].ilabels, vec![1]);
+        assert_eq!(paths[0].olabels, vec![2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_pruning_requires_path_property() {
+        let mut input_fst = VectorFst::<LogWeight>::new();
+        let s0 = input_fst.add_state();
+        input_fst.set_start(s0).unwrap();
+        input_fst.set_final(s0, LogWeight::one()).unwrap();
+
+        let config = DeterminizeConfig::default().with_state_threshold(Some(10));
+        assert!(determinize_with_config::<_, _, VectorFst<_>>(&input_fst, config).is_err());
+    }
+
+    #[test]
+    fn test_determinize_transducer_with_limits() -> Result<()> {
+        let mut input_fst = VectorFst::<TropicalWeight>::new();
+        let s0 = input_fst.add_state();
+        let s1 = input_fst.add_state();
+        let s2 = input_fst.add_state();
+        input_fst.set_start(s0)?;
+        input_fst.add_tr(s0, Tr::new(1, 2, 1.0, s1))?;
+        input_fst.add_tr(s1, Tr::new(2, 3, 1.0, s2))?;
+        input_fst.set_final(s2, 0.0)?;
+
+        let config =
+            DeterminizeConfig::default().with_limits(ResourceLimits::new().with_max_states(2));
+        let err = determinize_with_config::<_, _, VectorFst<_>>(&input_fst, config).unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::ResourceLimitExceeded {
+                resource: "states",
+                limit: 2
+            })
+        );
+
+        let config =
+            DeterminizeConfig::default().with_limits(ResourceLimits::new().with_max_states(10));
+        let _: VectorFst<_> = determinize_with_config(&input_fst, config)?;
+        Ok(())
+    }
+
     proptest! {
         #[test]
         fn test_proptest_determinize_keeps_symts(mut fst in any::<VectorFst::<TropicalWeight>>()) {
@@ -3,8 +3,8 @@ use std::fmt::Debug;
 use anyhow::Result;
 
 use crate::semirings::{
-    GallicWeight, GallicWeightLeft, GallicWeightMin, GallicWeightRestrict, StringWeightLeft,
-    StringWeightRestrict,
+    CompactLatticeWeight, GallicWeight, GallicWeightLeft, GallicWeightMin, GallicWeightRestrict,
+    StringWeightLeft, StringWeightRestrict,
 };
 use crate::Semiring;
 
@@ -88,3 +88,31 @@ impl<W: Semiring> CommonDivisor<GallicWeight<W>> for GallicCommonDivisor {
         }
     }
 }
+
+/// Common divisor of `CompactLatticeWeight` : the best `LatticeWeight` and the longest common
+/// prefix of the strings.
+#[derive(Debug, PartialEq)]
+pub struct CompactLatticeCommonDivisor {}
+
+impl CommonDivisor<CompactLatticeWeight> for CompactLatticeCommonDivisor {
+    fn common_divisor(
+        w1: &CompactLatticeWeight,
+        w2: &CompactLatticeWeight,
+    ) -> Result<CompactLatticeWeight> {
+        if w1.is_zero() {
+            return Ok(w2.clone());
+        }
+        if w2.is_zero() {
+            return Ok(w1.clone());
+        }
+        let weight = w1.weight().plus(w2.weight())?;
+        let prefix = w1
+            .string()
+            .iter()
+            .zip(w2.string().iter())
+            .take_while(|(l1, l2)| l1 == l2)
+            .map(|(l, _)| *l)
+            .collect();
+        Ok((weight, prefix).into())
+    }
+}
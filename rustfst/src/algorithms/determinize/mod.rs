@@ -1,14 +1,18 @@
 use determinize_fsa::DeterminizeFsa;
 use determinize_fsa_op::DeterminizeFsaOp;
+pub use determinize_lattice::{
+    determinize_lattice_pruned, DeterminizeLatticeConfig, DEFAULT_LATTICE_BEAM,
+};
 pub use determinize_static::{
     determinize, determinize_with_config, determinize_with_distance, DeterminizeConfig,
 };
-use divisors::{DefaultCommonDivisor, GallicCommonDivisor};
+pub(crate) use divisors::{CommonDivisor, DefaultCommonDivisor, GallicCommonDivisor};
 use element::{DeterminizeElement, DeterminizeStateTuple, DeterminizeTr, WeightedSubset};
 use state_table::DeterminizeStateTable;
 
 mod determinize_fsa;
 mod determinize_fsa_op;
+mod determinize_lattice;
 mod determinize_static;
 mod divisors;
 mod element;
@@ -0,0 +1,856 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::{CommonDivisor, DefaultCommonDivisor, GallicCommonDivisor};
+use crate::algorithms::factor_weight::factor_iterators::GallicFactorRestrict;
+use crate::algorithms::factor_weight::{factor_weight, FactorWeightOptions, FactorWeightType};
+use crate::algorithms::prune::{prune_with_config, PruneConfig};
+use crate::algorithms::tr_compares::TrCompare;
+use crate::algorithms::tr_sort;
+use crate::algorithms::weight_convert;
+use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
+use crate::algorithms::{connect, fst_convert_from_ref};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::{
+    DivideType, GallicWeightRestrict, Semiring, SemiringProperties, WeaklyDivisibleSemiring,
+    WeightQuantize,
+};
+use crate::{Error, Label, StateId, Tr, Trs, EPS_LABEL, KDELTA};
+
+/// Configuration for disambiguation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisambiguateConfig<W: Semiring> {
+    /// Quantization delta used when comparing the residual weights.
+    pub delta: f32,
+    /// Label used for the transitions to the superfinal state when disambiguating a transducer.
+    pub subsequential_label: Label,
+    /// Maximum number of states of the output. The weight needs to have the path property.
+    pub state_threshold: Option<StateId>,
+    /// Paths whose weight is worse than the shortest path times this threshold are pruned.
+    /// The weight needs to have the path property.
+    pub weight_threshold: Option<W>,
+}
+
+impl<W: Semiring> DisambiguateConfig<W> {
+    pub fn new(
+        delta: f32,
+        subsequential_label: Label,
+        state_threshold: Option<StateId>,
+        weight_threshold: Option<W>,
+    ) -> Self {
+        Self {
+            delta,
+            subsequential_label,
+            state_threshold,
+            weight_threshold,
+        }
+    }
+
+    pub fn with_delta(self, delta: f32) -> Self {
+        Self { delta, ..self }
+    }
+
+    pub fn with_subsequential_label(self, subsequential_label: Label) -> Self {
+        Self {
+            subsequential_label,
+            ..self
+        }
+    }
+
+    pub fn with_state_threshold(self, state_threshold: Option<StateId>) -> Self {
+        Self {
+            state_threshold,
+            ..self
+        }
+    }
+
+    pub fn with_weight_threshold(self, weight_threshold: Option<W>) -> Self {
+        Self {
+            weight_threshold,
+            ..self
+        }
+    }
+
+    fn is_pruned(&self) -> bool {
+        self.weight_threshold.is_some() || self.state_threshold.is_some()
+    }
+}
+
+impl<W: Semiring> Default for DisambiguateConfig<W> {
+    fn default() -> Self {
+        Self {
+            delta: KDELTA,
+            subsequential_label: EPS_LABEL,
+            state_threshold: None,
+            weight_threshold: None,
+        }
+    }
+}
+
+/// Compares the input labels and then the next states. The pre-disambiguation creates one
+/// transition per pair (input label, next state) of the head state.
+struct ILabelNextStateCompare {}
+
+impl TrCompare for ILabelNextStateCompare {
+    fn compare<W: Semiring>(a: &Tr<W>, b: &Tr<W>) -> Ordering {
+        a.ilabel
+            .cmp(&b.ilabel)
+            .then_with(|| a.nextstate.cmp(&b.nextstate))
+    }
+
+    fn properties(inprops: FstProperties) -> FstProperties {
+        let mut outprops =
+            (inprops & FstProperties::arcsort_properties()) | FstProperties::I_LABEL_SORTED;
+        if inprops.contains(FstProperties::ACCEPTOR) {
+            outprops |= FstProperties::O_LABEL_SORTED;
+        }
+        outprops
+    }
+}
+
+/// Relation between the states of the input sharing a common future : two states are related
+/// if they can be reached from the initial state with the same input string and if a final
+/// state can be reached from both of them with the same input string.
+struct CommonFuture {
+    related: HashSet<(StateId, StateId)>,
+}
+
+impl CommonFuture {
+    fn new<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> Result<Self> {
+        // Accessible part of the composition of the input projection with itself, epsilons
+        // being treated as regular symbols.
+        let mut pairs = vec![];
+        let mut ids = HashMap::new();
+        let mut predecessors: Vec<Vec<usize>> = vec![];
+        if let Some(start) = fst.start() {
+            pairs.push((start, start));
+            ids.insert((start, start), 0);
+            predecessors.push(vec![]);
+        }
+        let mut id = 0;
+        while id < pairs.len() {
+            let (s1, s2) = pairs[id];
+            let trs1 = fst.get_trs(s1)?;
+            let trs2 = fst.get_trs(s2)?;
+            for tr1 in trs1.trs() {
+                for tr2 in trs2.trs().iter().filter(|tr2| tr2.ilabel == tr1.ilabel) {
+                    let next_id = match ids.entry((tr1.nextstate, tr2.nextstate)) {
+                        Entry::Occupied(e) => *e.get(),
+                        Entry::Vacant(e) => {
+                            let next_id = pairs.len();
+                            pairs.push(*e.key());
+                            predecessors.push(vec![]);
+                            e.insert(next_id);
+                            next_id
+                        }
+                    };
+                    predecessors[next_id].push(id);
+                }
+            }
+            id += 1;
+        }
+
+        // Only the coaccessible pairs are related.
+        let mut coaccess = vec![false; pairs.len()];
+        let mut stack = vec![];
+        for (id, (s1, s2)) in pairs.iter().enumerate() {
+            if fst.is_final(*s1)? && fst.is_final(*s2)? {
+                coaccess[id] = true;
+                stack.push(id);
+            }
+        }
+        while let Some(id) = stack.pop() {
+            for p in predecessors[id].iter() {
+                if !coaccess[*p] {
+                    coaccess[*p] = true;
+                    stack.push(*p);
+                }
+            }
+        }
+
+        let related = pairs
+            .into_iter()
+            .zip(coaccess)
+            .filter_map(|(pair, coaccess)| if coaccess { Some(pair) } else { None })
+            .collect();
+        Ok(Self { related })
+    }
+
+    fn contains(&self, s1: StateId, s2: StateId) -> bool {
+        self.related.contains(&(s1, s2))
+    }
+}
+
+/// State of the pre-disambiguated FST : a weighted subset of the states of the input reachable
+/// with the same input string and sharing a common future with the head state.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct PreDisambiguateTuple<W: Semiring> {
+    subset: Vec<(StateId, W)>,
+    head: StateId,
+}
+
+struct PreDisambiguateTr<W: Semiring> {
+    label: Label,
+    weight: W,
+    dest_tuple: PreDisambiguateTuple<W>,
+}
+
+struct PreDisambiguateStateTable<W: Semiring> {
+    tuples: Vec<PreDisambiguateTuple<W>>,
+    ids: HashMap<PreDisambiguateTuple<W>, StateId>,
+}
+
+impl<W: Semiring> PreDisambiguateStateTable<W> {
+    fn new() -> Self {
+        Self {
+            tuples: vec![],
+            ids: HashMap::new(),
+        }
+    }
+
+    fn find_state(&mut self, tuple: PreDisambiguateTuple<W>) -> StateId {
+        match self.ids.entry(tuple) {
+            Entry::Occupied(e) => *e.get(),
+            Entry::Vacant(e) => {
+                let id = self.tuples.len() as StateId;
+                self.tuples.push(e.key().clone());
+                e.insert(id);
+                id
+            }
+        }
+    }
+}
+
+/// Sums the weights of the duplicate states of the subset and divides them by the weight of
+/// the transition.
+fn norm_tr<W, CD>(det_tr: &mut PreDisambiguateTr<W>, delta: f32) -> Result<()>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    CD: CommonDivisor<W>,
+{
+    // The elements are inserted at the front of the subset by OpenFST. Keep the same order to
+    // sum the weights of the duplicate states.
+    let mut elements = std::mem::take(&mut det_tr.dest_tuple.subset);
+    elements.reverse();
+    elements.sort_by_key(|(s, _)| *s);
+
+    let mut subset: Vec<(StateId, W)> = Vec::with_capacity(elements.len());
+    for (s, w) in elements {
+        det_tr.weight = CD::common_divisor(&det_tr.weight, &w)?;
+        match subset.last_mut() {
+            Some((last_s, last_w)) if *last_s == s => last_w.plus_assign(&w)?,
+            _ => subset.push((s, w)),
+        }
+    }
+    for (_, w) in subset.iter_mut() {
+        *w = w.divide(&det_tr.weight, DivideType::DivideLeft)?;
+        w.quantize_assign(delta)?;
+    }
+    det_tr.dest_tuple.subset = subset;
+    Ok(())
+}
+
+/// Subset construction where the subset of a state only contains the states sharing a common
+/// future with its head state. The input must be an acceptor with its transitions sorted by
+/// input label and next state. Returns the output along with the head state of each of its
+/// states.
+fn pre_disambiguate<W, CD, F1, F2>(
+    fst: &F1,
+    common_future: &CommonFuture,
+    delta: f32,
+) -> Result<(F2, Vec<StateId>)>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    CD: CommonDivisor<W>,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    let mut ofst = F2::new();
+    let mut heads = vec![];
+    let start = match fst.start() {
+        Some(s) => s,
+        None => return Ok((ofst, heads)),
+    };
+
+    let mut state_table = PreDisambiguateStateTable::new();
+    state_table.find_state(PreDisambiguateTuple {
+        subset: vec![(start, W::one())],
+        head: start,
+    });
+    ofst.add_state();
+    ofst.set_start(0)?;
+
+    let mut state = 0;
+    while (state as usize) < state_table.tuples.len() {
+        let tuple = state_table.tuples[state as usize].clone();
+        heads.push(tuple.head);
+
+        // One transition per pair (input label, next state) of the head state.
+        let mut det_trs: Vec<PreDisambiguateTr<W>> = vec![];
+        for tr in fst.get_trs(tuple.head)?.trs() {
+            if let Some(last) = det_trs.last() {
+                if last.label == tr.ilabel && last.dest_tuple.head == tr.nextstate {
+                    continue;
+                }
+            }
+            det_trs.push(PreDisambiguateTr {
+                label: tr.ilabel,
+                weight: W::zero(),
+                dest_tuple: PreDisambiguateTuple {
+                    subset: vec![],
+                    head: tr.nextstate,
+                },
+            });
+        }
+
+        // Each element goes to the subsets whose head shares a common future with it.
+        for (s, w) in tuple.subset.iter() {
+            for tr in fst.get_trs(*s)?.trs() {
+                let begin = det_trs.partition_point(|det_tr| det_tr.label < tr.ilabel);
+                for det_tr in det_trs[begin..]
+                    .iter_mut()
+                    .take_while(|det_tr| det_tr.label == tr.ilabel)
+                {
+                    if common_future.contains(tr.nextstate, det_tr.dest_tuple.head) {
+                        det_tr
+                            .dest_tuple
+                            .subset
+                            .push((tr.nextstate, w.times(&tr.weight)?));
+                    }
+                }
+            }
+        }
+
+        // The state is final only if its head state is.
+        if fst.is_final(tuple.head)? {
+            let mut final_weight = W::zero();
+            for (s, w) in tuple.subset.iter() {
+                if let Some(s_final_weight) = fst.final_weight(*s)? {
+                    final_weight.plus_assign(w.times(&s_final_weight)?)?;
+                }
+            }
+            if !final_weight.is_zero() {
+                ofst.set_final(state, final_weight)?;
+            }
+        }
+
+        for mut det_tr in det_trs {
+            norm_tr::<_, CD>(&mut det_tr, delta)?;
+            let nextstate = state_table.find_state(det_tr.dest_tuple);
+            if nextstate as usize == ofst.num_states() {
+                ofst.add_state();
+            }
+            ofst.add_tr(
+                state,
+                Tr::new(det_tr.label, det_tr.label, det_tr.weight, nextstate),
+            )?;
+        }
+        state += 1;
+    }
+    Ok((ofst, heads))
+}
+
+/// Union-find over the states of the pre-disambiguated FST, with union by rank.
+struct UnionFind {
+    parents: Vec<StateId>,
+    ranks: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size as StateId).collect(),
+            ranks: vec![0; size],
+        }
+    }
+
+    fn find_set(&mut self, x: StateId) -> StateId {
+        let mut root = x;
+        while self.parents[root as usize] != root {
+            root = self.parents[root as usize];
+        }
+        let mut x = x;
+        while x != root {
+            let parent = self.parents[x as usize];
+            self.parents[x as usize] = root;
+            x = parent;
+        }
+        root
+    }
+
+    fn union(&mut self, x: StateId, y: StateId) {
+        let (x, y) = (self.find_set(x), self.find_set(y));
+        if x == y {
+            return;
+        }
+        let (rank_x, rank_y) = (self.ranks[x as usize], self.ranks[y as usize]);
+        if rank_x > rank_y {
+            self.parents[y as usize] = x;
+        } else {
+            self.parents[x as usize] = y;
+            if rank_x == rank_y {
+                self.ranks[y as usize] += 1;
+            }
+        }
+    }
+}
+
+/// Transition of the pre-disambiguated FST identified by its source state and its index,
+/// `None` standing for the final weight of the state.
+type TrId = (StateId, Option<usize>);
+
+/// Finds and removes the ambiguous transitions of the pre-disambiguated FST.
+struct Disambiguator {
+    /// Head state in the pre-disambiguation of each state.
+    heads: Vec<Option<StateId>>,
+    /// Pairs of states reachable with the same input string.
+    coreachable: HashSet<(StateId, StateId)>,
+    queue: VecDeque<(StateId, StateId)>,
+    /// Pairs (A, B) of transitions with the same input label and next state whose source
+    /// states are coreachable, the head of the source of B being lower than the one of A.
+    candidates: Vec<(TrId, TrId)>,
+    ambiguous: BTreeSet<TrId>,
+    /// States split by the quantization of the weights.
+    merge: Option<UnionFind>,
+}
+
+impl Disambiguator {
+    fn new(heads: Vec<Option<StateId>>) -> Self {
+        Self {
+            heads,
+            coreachable: HashSet::new(),
+            queue: VecDeque::new(),
+            candidates: vec![],
+            ambiguous: BTreeSet::new(),
+            merge: None,
+        }
+    }
+
+    fn head(&self, s: StateId) -> Option<StateId> {
+        self.heads[s as usize]
+    }
+
+    fn insert_candidate(&mut self, s1: StateId, s2: StateId, tr_id1: TrId, tr_id2: TrId) {
+        if self.head(s1) > self.head(s2) {
+            self.candidates.push((tr_id1, tr_id2));
+        } else {
+            self.candidates.push((tr_id2, tr_id1));
+        }
+    }
+
+    fn find_ambiguities<W: Semiring, F: ExpandedFst<W>>(&mut self, fst: &F) -> Result<()> {
+        let start = match fst.start() {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+        self.candidates.clear();
+        self.coreachable.insert((start, start));
+        self.queue.push_back((start, start));
+        while let Some((s1, s2)) = self.queue.pop_front() {
+            self.find_ambiguous_pairs(fst, s1, s2)?;
+        }
+        Ok(())
+    }
+
+    fn find_ambiguous_pairs<W: Semiring, F: ExpandedFst<W>>(
+        &mut self,
+        fst: &F,
+        s1: StateId,
+        s2: StateId,
+    ) -> Result<()> {
+        // As in OpenFST, the pair is also searched in the other order when the second state
+        // has more transitions.
+        if fst.num_trs(s2)? > fst.num_trs(s1)? {
+            self.find_ambiguous_pairs(fst, s2, s1)?;
+        }
+        let trs1 = fst.get_trs(s1)?;
+        let trs2 = fst.get_trs(s2)?;
+        let trs2 = trs2.trs();
+        for (idx1, tr1) in trs1.trs().iter().enumerate() {
+            let begin = trs2.partition_point(|tr2| tr2.ilabel < tr1.ilabel);
+            for (idx2, tr2) in trs2
+                .iter()
+                .enumerate()
+                .skip(begin)
+                .take_while(|(_, tr2)| tr2.ilabel == tr1.ilabel)
+            {
+                if s1 != s2 && tr1.nextstate == tr2.nextstate {
+                    self.insert_candidate(s1, s2, (s1, Some(idx1)), (s2, Some(idx2)));
+                }
+                let pair = if tr1.nextstate <= tr2.nextstate {
+                    (tr1.nextstate, tr2.nextstate)
+                } else {
+                    (tr2.nextstate, tr1.nextstate)
+                };
+                if self.coreachable.insert(pair) {
+                    // Only possible if the states were split by quantization.
+                    if pair.0 != pair.1 && self.head(pair.0) == self.head(pair.1) {
+                        let num_states = fst.num_states();
+                        self.merge
+                            .get_or_insert_with(|| UnionFind::new(num_states))
+                            .union(pair.0, pair.1);
+                    } else {
+                        self.queue.push_back(pair);
+                    }
+                }
+            }
+        }
+        if s1 != s2 && fst.is_final(s1)? && fst.is_final(s2)? {
+            self.insert_candidate(s1, s2, (s1, None), (s2, None));
+        }
+        Ok(())
+    }
+
+    /// Merges the states split by quantization and searches the ambiguities again.
+    fn remove_splits<W: Semiring, F: MutableFst<W>>(&mut self, fst: &mut F) -> Result<()> {
+        let mut merge = match self.merge.take() {
+            Some(merge) => merge,
+            None => return Ok(()),
+        };
+        for s in 0..(fst.num_states() as StateId) {
+            let mut it_trs = fst.tr_iter_mut(s)?;
+            for idx in 0..it_trs.len() {
+                let nextstate = unsafe { it_trs.get_unchecked(idx) }.nextstate;
+                let merged_nextstate = merge.find_set(nextstate);
+                if merged_nextstate != nextstate {
+                    unsafe { it_trs.set_nextstate_unchecked(idx, merged_nextstate) };
+                }
+            }
+        }
+        self.coreachable.clear();
+        self.find_ambiguities(fst)?;
+        if self.merge.is_some() {
+            bail!("Disambiguate : unable to remove spurious ambiguities");
+        }
+        Ok(())
+    }
+
+    /// A transition is ambiguous if a candidate with a lower head is not itself ambiguous.
+    fn mark_ambiguities(&mut self) {
+        let heads = &self.heads;
+        let key = |tr_id: &TrId| (heads[tr_id.0 as usize], tr_id.0, tr_id.1);
+        self.candidates.sort_by_key(|(a, _)| key(a));
+        for (a, b) in self.candidates.drain(..) {
+            if !self.ambiguous.contains(&b) {
+                self.ambiguous.insert(a);
+            }
+        }
+        self.coreachable.clear();
+    }
+
+    fn remove_ambiguities<W: Semiring, F: MutableFst<W>>(&mut self, fst: &mut F) -> Result<()> {
+        if self.ambiguous.is_empty() {
+            return Ok(());
+        }
+        let dead_state = fst.add_state();
+        for (s, tr_idx) in self.ambiguous.iter() {
+            match tr_idx {
+                Some(idx) => fst.tr_iter_mut(*s)?.set_nextstate(*idx, dead_state)?,
+                None => fst.delete_final_weight(*s)?,
+            }
+        }
+        connect(fst)?;
+        self.ambiguous.clear();
+        Ok(())
+    }
+}
+
+/// Disambiguates a weighted FST. The result is an equivalent FST that has the property that no
+/// two successful paths have the same input labeling. For this algorithm, epsilon transitions
+/// are treated as regular symbols.
+///
+/// Contrary to determinization, the output is not required to be deterministic : each state of
+/// the result is made of a head state of the input and of the weighted subset of the states
+/// reachable with the same input string which share a common future with it. The ambiguous
+/// transitions of this FST are then removed.
+///
+/// The weight must be weakly left divisible (valid for `TropicalWeight` and `LogWeight` for
+/// instance). Transducers must be functional, an error is returned otherwise. The algorithm
+/// does not terminate on FSTs that can't be disambiguated. The disambiguable FSTs include all
+/// automata and the functional transducers that are unweighted, acyclic or unambiguous.
+///
+/// See Mohri, M. and Riley, M. 2015. On the disambiguation of weighted automata. In CIAA,
+/// pages 263-278.
+pub fn disambiguate<W, F1, F2>(ifst: &F1) -> Result<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    disambiguate_with_config(ifst, DisambiguateConfig::default())
+}
+
+/// Disambiguates a weighted FST with the possibility to configure pruning. See [`disambiguate`].
+pub fn disambiguate_with_config<W, F1, F2>(ifst: &F1, config: DisambiguateConfig<W>) -> Result<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
+        bail!(Error::MissingSemiringProperties {
+            operation: "disambiguate",
+            properties: SemiringProperties::LEFT_SEMIRING,
+        })
+    }
+    if config.is_pruned() && !W::properties().contains(SemiringProperties::PATH) {
+        bail!(Error::MissingSemiringProperties {
+            operation: "disambiguate",
+            properties: SemiringProperties::PATH,
+        })
+    }
+
+    let mut sfst: VectorFst<W> = fst_convert_from_ref(ifst);
+    connect(&mut sfst)?;
+    tr_sort(&mut sfst, ILabelNextStateCompare {});
+    let common_future = CommonFuture::new(&sfst)?;
+
+    let props = sfst.compute_and_update_properties(FstProperties::ACCEPTOR)?;
+    let (mut ofst, heads): (F2, _) = if props.contains(FstProperties::ACCEPTOR) {
+        pre_disambiguate::<_, DefaultCommonDivisor, _, _>(&sfst, &common_future, config.delta)?
+    } else {
+        let fsa: VectorFst<GallicWeightRestrict<W>> =
+            weight_convert(&sfst, &mut ToGallicConverter {})?;
+        let (det_fsa, heads): (VectorFst<_>, _) =
+            pre_disambiguate::<_, GallicCommonDivisor, _, _>(&fsa, &common_future, config.delta)?;
+        let factor_opts = FactorWeightOptions {
+            delta: config.delta,
+            mode: FactorWeightType::FACTOR_FINAL_WEIGHTS,
+            final_ilabel: config.subsequential_label,
+            final_olabel: config.subsequential_label,
+            increment_final_ilabel: false,
+            increment_final_olabel: false,
+        };
+        let factored_fsa: VectorFst<GallicWeightRestrict<W>> =
+            factor_weight::<_, VectorFst<_>, _, _, GallicFactorRestrict<W>>(&det_fsa, factor_opts)?;
+        let mut from_gallic = FromGallicConverter {
+            superfinal_label: config.subsequential_label,
+        };
+        (weight_convert(&factored_fsa, &mut from_gallic)?, heads)
+    };
+    if config.is_pruned() {
+        let prune_config = PruneConfig::new(config.weight_threshold.unwrap_or_else(W::zero))
+            .with_state_threshold(config.state_threshold);
+        prune_with_config(&mut ofst, prune_config)?;
+    }
+
+    // As in OpenFST, the heads are indexed by the states of the pre-disambiguated FSA, even if
+    // the factoring of the final weights or the pruning added or removed states.
+    let mut heads: Vec<_> = heads.into_iter().map(Some).collect();
+    heads.resize(ofst.num_states(), None);
+    let mut disambiguator = Disambiguator::new(heads);
+
+    tr_sort(&mut ofst, ILabelNextStateCompare {});
+    disambiguator.find_ambiguities(&ofst)?;
+    disambiguator.remove_splits(&mut ofst)?;
+    disambiguator.mark_ambiguities();
+    disambiguator.remove_ambiguities(&mut ofst)?;
+    ofst.set_symts_from_fst(ifst);
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst, Fst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::utils::acceptor;
+    use std::collections::HashMap;
+
+    fn paths_by_ilabels(fst: &VectorFst<TropicalWeight>) -> HashMap<Vec<Label>, Vec<f32>> {
+        let mut res: HashMap<Vec<Label>, Vec<f32>> = HashMap::new();
+        for path in fst.paths_iter() {
+            res.entry(path.ilabels)
+                .or_default()
+                .push(*path.weight.value());
+        }
+        res
+    }
+
+    #[test]
+    fn test_disambiguate_keeps_best_path() -> Result<()> {
+        // Two paths for the string "1 2" and one path for "1 3".
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 2, 3.0, 3))?;
+        fst.add_tr(2, Tr::new(2, 2, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 3, 1.0, 3))?;
+        fst.set_final(3, 0.5)?;
+
+        let res: VectorFst<TropicalWeight> = disambiguate(&fst)?;
+        let paths = paths_by_ilabels(&res);
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[&vec![1, 2]], vec![3.5]);
+        assert_eq!(paths[&vec![1, 3]], vec![3.5]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_final_weights() -> Result<()> {
+        // Both paths accept "1", the second one has a better final weight.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+        fst.set_final(1, 5.0)?;
+        fst.set_final(2, 1.0)?;
+
+        let res: VectorFst<TropicalWeight> = disambiguate(&fst)?;
+        let paths = paths_by_ilabels(&res);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[&vec![1]], vec![3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_is_not_determinization() -> Result<()> {
+        // Unambiguous but non-deterministic: nothing to remove.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 2, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 3, 1.0, 3))?;
+        fst.set_final(3, TropicalWeight::one())?;
+
+        let res: VectorFst<TropicalWeight> = disambiguate(&fst)?;
+        assert_eq!(res.num_trs(0)?, 2);
+        assert_eq!(paths_by_ilabels(&res), paths_by_ilabels(&fst));
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_cyclic() -> Result<()> {
+        // (1)* accepted twice with different weights.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 0))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 1))?;
+        fst.add_tr(1, Tr::new(1, 1, 2.0, 1))?;
+        fst.set_final(0, TropicalWeight::one())?;
+        fst.set_final(1, TropicalWeight::one())?;
+
+        let res: VectorFst<TropicalWeight> = disambiguate(&fst)?;
+        // Only the loop on the start state of the input is kept.
+        assert_eq!(res.num_states(), 2);
+        let num_trs: usize = (0..res.num_states() as StateId)
+            .map(|s| res.num_trs(s))
+            .sum::<Result<usize>>()?;
+        assert_eq!(num_trs, 2);
+        for s in 0..res.num_states() as StateId {
+            for tr in res.get_trs(s)?.trs() {
+                assert_eq!(tr.weight, TropicalWeight::new(1.0));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_state_threshold() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3, 4], TropicalWeight::one());
+        let config = DisambiguateConfig::default().with_state_threshold(Some(3));
+        let res: VectorFst<TropicalWeight> = disambiguate_with_config(&fst, config)?;
+        assert!(res.num_states() <= 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_weight_threshold() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 10.0, 1))?;
+        fst.set_final(1, TropicalWeight::one())?;
+
+        let config =
+            DisambiguateConfig::default().with_weight_threshold(Some(TropicalWeight::new(2.0)));
+        let res: VectorFst<TropicalWeight> = disambiguate_with_config(&fst, config)?;
+        let paths = paths_by_ilabels(&res);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[&vec![1]], vec![1.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_pruning_requires_path_property() -> Result<()> {
+        let fst: VectorFst<LogWeight> = acceptor(&[1, 2], LogWeight::one());
+        let _: VectorFst<LogWeight> = disambiguate(&fst)?;
+
+        let config = DisambiguateConfig::default().with_state_threshold(Some(10));
+        let res: Result<VectorFst<LogWeight>> = disambiguate_with_config(&fst, config);
+        assert!(res.is_err());
+        Ok(())
+    }
+
+    fn functional_transducer() -> Result<VectorFst<TropicalWeight>> {
+        // "1" is mapped to "4" and "1 2" to "4" by two different paths.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 4, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, EPS_LABEL, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, EPS_LABEL, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(2, 4, 1.0, 3))?;
+        fst.set_final(1, TropicalWeight::one())?;
+        fst.set_final(3, TropicalWeight::one())?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_disambiguate_functional_transducer() -> Result<()> {
+        let fst = functional_transducer()?;
+        let res: VectorFst<TropicalWeight> = disambiguate(&fst)?;
+
+        let mut paths: Vec<_> = res
+            .paths_iter()
+            .map(|p| (p.ilabels, p.olabels, *p.weight.value()))
+            .collect();
+        paths.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            paths,
+            vec![(vec![1], vec![4], 1.0), (vec![1, 2], vec![4], 2.0)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_subsequential_label() -> Result<()> {
+        // The output label of the path "1" is only known at the end of the path.
+        let fst = functional_transducer()?;
+        let config = DisambiguateConfig::default().with_subsequential_label(5);
+        let res: VectorFst<TropicalWeight> = disambiguate_with_config(&fst, config)?;
+
+        let mut paths: Vec<_> = res.paths_iter().map(|p| (p.ilabels, p.olabels)).collect();
+        paths.sort();
+        assert_eq!(paths, vec![(vec![1, 2], vec![4]), (vec![1, 5], vec![4])]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disambiguate_non_functional_transducer() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 3, 1.0, 1))?;
+        fst.set_final(1, TropicalWeight::one())?;
+
+        let res: Result<VectorFst<TropicalWeight>> = disambiguate(&fst);
+        assert!(res.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::encode::decode_static::DecodeMapper;
+use crate::algorithms::encode::EncodeTable;
+use crate::algorithms::TrMapFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerFst<W, F, B> = TrMapFst<W, F, B, DecodeMapper<W>>;
+
+/// Delayed version of `decode`: the labels of the trs are decoded with the `EncodeTable` when
+/// the states are visited. The symbol tables stored in the `EncodeTable` are used if present,
+/// otherwise the ones of the FST are kept.
+///
+/// Unlike `decode`, the final epsilon transitions created when encoding the weights are not
+/// removed. `rm_final_epsilon` can be called on the computed FST if needed.
+pub struct DecodeFst<W: Semiring, F: Fst<W>, B: Borrow<F>>(InnerFst<W, F, B>);
+
+impl<W, F, B> CoreFst<W> for DecodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B> StateIterator<'a> for DecodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type Iter = <InnerFst<W, F, B> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B> FstIterator<'a, W> for DecodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type FstIter = <InnerFst<W, F, B> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B> Fst<W> for DecodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B> Debug for DecodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B> DecodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    pub fn new(fst: B, encode_table: EncodeTable<W>) -> Result<Self> {
+        let isymt = encode_table
+            .input_symbols()
+            .or_else(|| fst.borrow().input_symbols().cloned());
+        let osymt = encode_table
+            .output_symbols()
+            .or_else(|| fst.borrow().output_symbols().cloned());
+        Ok(DecodeFst(TrMapFst::new_with_symts(
+            fst,
+            DecodeMapper::new(encode_table),
+            isymt,
+            osymt,
+        )))
+    }
+
+    pub fn encode_table(&self) -> &EncodeTable<W> {
+        &self.0.mapper().encode_table
+    }
+
+    pub fn into_encode_table(self) -> EncodeTable<W> {
+        self.0.into_mapper().encode_table
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
@@ -6,8 +6,8 @@ use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
 use crate::{Semiring, Tr};
 
-struct DecodeMapper<W: Semiring> {
-    encode_table: EncodeTable<W>,
+pub struct DecodeMapper<W: Semiring> {
+    pub(crate) encode_table: EncodeTable<W>,
 }
 
 impl<W: Semiring> DecodeMapper<W> {
@@ -68,12 +68,18 @@ impl<W: Semiring> TrMapper<W> for DecodeMapper<W> {
 }
 
 /// The `decode` operation takes as input an encoded FST and the corresponding `EncodeTable` object
-/// and reverts the encoding.
+/// and reverts the encoding. The symbol tables stored in the `EncodeTable` are set on the FST.
 pub fn decode<W, F>(fst: &mut F, encode_table: EncodeTable<W>) -> Result<()>
 where
     W: Semiring,
     F: MutableFst<W>,
 {
+    if let Some(isymt) = encode_table.input_symbols() {
+        fst.set_input_symbols(isymt);
+    }
+    if let Some(osymt) = encode_table.output_symbols() {
+        fst.set_output_symbols(osymt);
+    }
     let mut decode_mapper = DecodeMapper::new(encode_table);
     fst.tr_map(&mut decode_mapper)
         .with_context(|| format_err!("Error calling TrMap with EncodeMapper."))?;
@@ -0,0 +1,261 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::encode::encode_static::EncodeMapper;
+use crate::algorithms::encode::EncodeTable;
+use crate::algorithms::TrMapFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerFst<W, F, B> = TrMapFst<W, F, B, EncodeMapper<W>>;
+
+/// Delayed version of `encode_with_table`: the labels and/or weights of the trs are encoded
+/// when the states are visited. The tuples are added to the `EncodeTable` as they are
+/// encountered, so the table is only complete once all the states of the FST have been visited.
+///
+/// If the weights are encoded, the superfinal state is the state 0 of the encoded FST, as in
+/// OpenFST. The states are therefore numbered differently than with `encode`.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::encode::{EncodeFst, EncodeTable, EncodeType};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::{acceptor, transducer};
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<TropicalWeight> = fst![1, 2 => 3, 4];
+/// let encode_table = EncodeTable::new(EncodeType::EncodeLabels);
+/// let encoded_fst = EncodeFst::<_, VectorFst<_>, _>::new(&fst, encode_table)?;
+/// let encoded: VectorFst<_> = encoded_fst.compute()?;
+/// assert_eq!(encoded, fst![1, 2]);
+/// assert_eq!(encoded_fst.into_encode_table().0.borrow().len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct EncodeFst<W: Semiring, F: Fst<W>, B: Borrow<F>>(InnerFst<W, F, B>);
+
+impl<W, F, B> CoreFst<W> for EncodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B> StateIterator<'a> for EncodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type Iter = <InnerFst<W, F, B> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B> FstIterator<'a, W> for EncodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type FstIter = <InnerFst<W, F, B> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B> Fst<W> for EncodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B> Debug for EncodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B> EncodeFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    /// The symbol tables of the FST are stored in the `EncodeTable`, like `encode_with_table`.
+    pub fn new(fst: B, encode_table: EncodeTable<W>) -> Result<Self> {
+        {
+            let mut table = encode_table.0.borrow_mut();
+            if let Some(isymt) = fst.borrow().input_symbols() {
+                table.isymt = Some(Arc::clone(isymt));
+            }
+            if let Some(osymt) = fst.borrow().output_symbols() {
+                table.osymt = Some(Arc::clone(osymt));
+            }
+        }
+        Ok(EncodeFst(TrMapFst::new(
+            fst,
+            EncodeMapper::new(encode_table),
+        )?))
+    }
+
+    /// Table filled with the tuples of the states visited so far.
+    pub fn encode_table(&self) -> &EncodeTable<W> {
+        &self.0.mapper().encode_table
+    }
+
+    pub fn into_encode_table(self) -> EncodeTable<W> {
+        self.0.into_mapper().encode_table
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithms::encode::{encode, DecodeFst, EncodeType};
+    use crate::algorithms::isomorphic;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::ExpandedFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    fn fst_in() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 0.5, s1))?;
+        fst.add_tr(s1, Tr::new(2, 1, 1.5, s2))?;
+        fst.add_tr(s1, Tr::new(1, 2, 0.5, s2))?;
+        fst.set_final(s1, 1.0)?;
+        fst.set_final(s2, 2.0)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_encode_fst_labels() -> Result<()> {
+        let fst = fst_in()?;
+        let encoded_fst =
+            EncodeFst::<_, VectorFst<_>, _>::new(&fst, EncodeTable::new(EncodeType::EncodeLabels))?;
+        let encoded: VectorFst<_> = encoded_fst.compute()?;
+        let mut expected = fst.clone();
+        let expected_table = encode(&mut expected, EncodeType::EncodeLabels)?;
+        assert_eq!(encoded, expected);
+        assert_eq!(encoded.properties(), expected.properties());
+        assert_eq!(
+            encoded_fst.encode_table().0.borrow().len(),
+            expected_table.0.borrow().len()
+        );
+
+        let encode_table = encoded_fst.into_encode_table();
+        let decoded: VectorFst<_> =
+            DecodeFst::<_, VectorFst<_>, _>::new(&encoded, encode_table)?.compute()?;
+        assert_eq!(decoded, fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_fst_weights_and_labels() -> Result<()> {
+        // The states are visited in the same order as with the static version, the same keys
+        // are assigned.
+        let fst = fst_in()?;
+        let encoded: VectorFst<_> = EncodeFst::<_, VectorFst<_>, _>::new(
+            &fst,
+            EncodeTable::new(EncodeType::EncodeWeightsAndLabels),
+        )?
+        .compute()?;
+        let mut expected = fst;
+        encode(&mut expected, EncodeType::EncodeWeightsAndLabels)?;
+        assert_eq!(encoded.num_states(), expected.num_states());
+        assert!(isomorphic(&encoded, &expected)?);
+        Ok(())
+    }
+}
@@ -1,22 +1,20 @@
-use std::cell::RefCell;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 
-use crate::algorithms::encode::{EncodeTable, EncodeTableMut, EncodeType};
+use crate::algorithms::encode::{EncodeTable, EncodeType};
 use crate::algorithms::{FinalTr, MapFinalAction, TrMapper};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
 use crate::{Label, Semiring, Tr};
 
-struct EncodeMapper<W: Semiring> {
-    encode_table: EncodeTable<W>,
+pub struct EncodeMapper<W: Semiring> {
+    pub(crate) encode_table: EncodeTable<W>,
 }
 
 impl<W: Semiring> EncodeMapper<W> {
-    pub fn new(encode_type: EncodeType) -> Self {
-        EncodeMapper {
-            encode_table: EncodeTable(RefCell::new(EncodeTableMut::new(encode_type))),
-        }
+    pub fn new(encode_table: EncodeTable<W>) -> Self {
+        EncodeMapper { encode_table }
     }
 
     pub fn encode_weights(&self) -> bool {
@@ -88,13 +86,33 @@ impl<W: Semiring> TrMapper<W> for EncodeMapper<W> {
 /// of the encode flags: `encode_labels` and `encode_weights`.
 ///
 /// The encoding of each pair or triple of labels and/or weights as a unique key is stored
-/// in an `EncodeTable` object.
+/// in an `EncodeTable` object, along with the symbol tables of the FST.
 pub fn encode<W, F>(fst: &mut F, encode_type: EncodeType) -> Result<EncodeTable<W>>
 where
     W: Semiring,
     F: MutableFst<W>,
 {
-    let mut encode_mapper = EncodeMapper::new(encode_type);
+    encode_with_table(fst, EncodeTable::new(encode_type))
+}
+
+/// Same as `encode` but reuses an existing `EncodeTable`, e.g one read from a file, so that
+/// several FSTs are encoded with the same keys. The tuples not in the table yet are added
+/// to it.
+pub fn encode_with_table<W, F>(fst: &mut F, encode_table: EncodeTable<W>) -> Result<EncodeTable<W>>
+where
+    W: Semiring,
+    F: MutableFst<W>,
+{
+    {
+        let mut table = encode_table.0.borrow_mut();
+        if let Some(isymt) = fst.input_symbols() {
+            table.isymt = Some(Arc::clone(isymt));
+        }
+        if let Some(osymt) = fst.output_symbols() {
+            table.osymt = Some(Arc::clone(osymt));
+        }
+    }
+    let mut encode_mapper = EncodeMapper::new(encode_table);
     fst.tr_map(&mut encode_mapper)
         .with_context(|| format_err!("Error calling TrMap with EncodeMapper."))?;
     Ok(encode_mapper.encode_table)
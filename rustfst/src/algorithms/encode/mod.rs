@@ -1,10 +1,13 @@
+pub use decode_fst::DecodeFst;
 pub use decode_static::decode;
-pub use encode_static::encode;
+pub use encode_fst::EncodeFst;
+pub use encode_static::{encode, encode_with_table};
 pub use encode_type::EncodeType;
 pub use table::EncodeTable;
-use table::EncodeTableMut;
 
+mod decode_fst;
 mod decode_static;
+mod encode_fst;
 mod encode_static;
 mod encode_type;
 mod table;
@@ -1,25 +1,61 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use bitflags::bitflags;
 
 use crate::algorithms::encode::EncodeType;
 use crate::algorithms::FinalTr;
-use crate::{Label, Semiring, Tr, EPS_LABEL};
+use crate::parsers::bin_fst::bin_reader::BinReader;
+use crate::parsers::bin_fst::fst_header::OpenFstString;
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
+use crate::parsers::bin_symt::nom_parser::{read_symbol_table_bin, write_bin_symt};
+use crate::parsers::{write_bin_i32, write_bin_i64, write_bin_u8};
+use crate::semirings::SerializableSemiring;
+use crate::{Label, Semiring, SymbolTable, Tr, EPS_LABEL};
 use std::collections::hash_map::Entry;
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+// Identifies stream data as an encode table (and its endianity).
+static ENCODE_MAGIC_NUMBER: i32 = 2_128_178_506;
+// Magic number of the encode tables written by OpenFST before 1.8, without the arc type.
+static ENCODE_DEPRECATED_MAGIC_NUMBER: i32 = 2_129_983_209;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct EncodeFlags: u32 {
+        const ENCODE_LABELS = 0b1;
+        const ENCODE_WEIGHTS = 0b1 << 1;
+        const HAS_ISYMBOLS = 0b1 << 2;
+        const HAS_OSYMBOLS = 0b1 << 3;
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct EncodeTuple<W: Semiring> {
     pub ilabel: Label,
     pub olabel: Label,
     pub weight: W,
 }
 
+#[derive(Debug)]
 pub struct EncodeTableMut<W: Semiring> {
     pub encode_type: EncodeType,
     // FIXME : Store references ?
     id_to_tuple: Vec<EncodeTuple<W>>,
     tuple_to_id: HashMap<EncodeTuple<W>, usize>,
+    /// Input symbols of the encoded FST, restored by `decode`.
+    pub isymt: Option<Arc<SymbolTable>>,
+    /// Output symbols of the encoded FST, restored by `decode`.
+    pub osymt: Option<Arc<SymbolTable>>,
 }
 
+/// Mapping between the labels of an encoded FST and the tuples they encode. It can be stored
+/// in binary format to decode the FST in another process, and is compatible with the encoder
+/// files of OpenFST (`fstencode`).
+#[derive(Debug)]
 pub struct EncodeTable<W: Semiring>(pub RefCell<EncodeTableMut<W>>);
 
 impl<W: Semiring> EncodeTableMut<W> {
@@ -28,9 +64,20 @@ impl<W: Semiring> EncodeTableMut<W> {
             encode_type,
             id_to_tuple: vec![],
             tuple_to_id: HashMap::new(),
+            isymt: None,
+            osymt: None,
         }
     }
 
+    /// Number of tuples in the table.
+    pub fn len(&self) -> usize {
+        self.id_to_tuple.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id_to_tuple.is_empty()
+    }
+
     pub fn tr_to_tuple(&self, tr: &Tr<W>) -> EncodeTuple<W> {
         EncodeTuple {
             ilabel: tr.ilabel,
@@ -85,3 +132,258 @@ impl<W: Semiring> Default for EncodeTableMut<W> {
         Self::new(EncodeType::EncodeWeightsAndLabels)
     }
 }
+
+impl<W: Semiring> EncodeTable<W> {
+    pub fn new(encode_type: EncodeType) -> Self {
+        EncodeTable(RefCell::new(EncodeTableMut::new(encode_type)))
+    }
+
+    pub fn encode_type(&self) -> EncodeType {
+        self.0.borrow().encode_type
+    }
+
+    pub fn input_symbols(&self) -> Option<Arc<SymbolTable>> {
+        self.0.borrow().isymt.clone()
+    }
+
+    pub fn output_symbols(&self) -> Option<Arc<SymbolTable>> {
+        self.0.borrow().osymt.clone()
+    }
+}
+
+impl<W: SerializableSemiring> EncodeTable<W> {
+    /// Loads an EncodeTable from a file in binary format.
+    pub fn read<P: AsRef<Path>>(path_bin_encode_table: P) -> Result<Self> {
+        let data = std::fs::read(path_bin_encode_table.as_ref()).with_context(|| {
+            format!(
+                "Can't open EncodeTable binary file : {:?}",
+                path_bin_encode_table.as_ref()
+            )
+        })?;
+        Self::load(&data)
+    }
+
+    /// Loads an EncodeTable from a buffer in binary format. Both the current format of
+    /// OpenFST and the one used before OpenFST 1.8 are supported. The errors are
+    /// `FstReadError` reporting the offset of the invalid data.
+    pub fn load(data: &[u8]) -> Result<Self> {
+        let mut reader = BinReader::new(data);
+        let table = read_encode_table(&mut reader)
+            .map_err(|e| anyhow::Error::new(e).context("Error while parsing binary EncodeTable"))?;
+        Ok(EncodeTable(RefCell::new(table)))
+    }
+
+    /// Writes the EncodeTable to a file in binary format.
+    pub fn write<P: AsRef<Path>>(&self, path_bin_encode_table: P) -> Result<()> {
+        let output = std::fs::File::create(path_bin_encode_table.as_ref()).with_context(|| {
+            format!(
+                "Cannot create EncodeTable binary file : {:?}",
+                path_bin_encode_table.as_ref()
+            )
+        })?;
+        self.store(BufWriter::new(output))
+    }
+
+    /// Stores the EncodeTable in binary format, in the format of OpenFST 1.8, to a `Write`.
+    pub fn store<O: Write>(&self, mut output: O) -> Result<()> {
+        let table = self.0.borrow();
+        let mut flags = EncodeFlags::empty();
+        if table.encode_type.encode_labels() {
+            flags |= EncodeFlags::ENCODE_LABELS;
+        }
+        if table.encode_type.encode_weights() {
+            flags |= EncodeFlags::ENCODE_WEIGHTS;
+        }
+        if table.isymt.is_some() {
+            flags |= EncodeFlags::HAS_ISYMBOLS;
+        }
+        if table.osymt.is_some() {
+            flags |= EncodeFlags::HAS_OSYMBOLS;
+        }
+
+        write_bin_i32(&mut output, ENCODE_MAGIC_NUMBER)?;
+        OpenFstString::new(Tr::<W>::tr_type()).write(&mut output)?;
+        write_bin_u8(&mut output, flags.bits() as u8)?;
+        write_bin_i64(&mut output, table.len() as i64)?;
+        for tuple in table.id_to_tuple.iter() {
+            write_bin_i32(&mut output, tuple.ilabel as i32)?;
+            write_bin_i32(&mut output, tuple.olabel as i32)?;
+            tuple.weight.write_binary(&mut output)?;
+        }
+        if let Some(isymt) = &table.isymt {
+            write_bin_symt(&mut output, isymt)?;
+        }
+        if let Some(osymt) = &table.osymt {
+            write_bin_symt(&mut output, osymt)?;
+        }
+        output.flush()?;
+        Ok(())
+    }
+}
+
+fn read_encode_table<W: SerializableSemiring>(
+    reader: &mut BinReader,
+) -> Result<EncodeTableMut<W>, FstReadError> {
+    let offset = reader.offset();
+    let magic_number = reader.read_i32()?;
+    let (flags_offset, flags) = if magic_number == ENCODE_MAGIC_NUMBER {
+        let tr_type_offset = reader.offset();
+        let tr_type = reader.read_string()?;
+        if tr_type != Tr::<W>::tr_type() {
+            return Err(FstReadError::TrTypeMismatch {
+                offset: tr_type_offset,
+                expected: Tr::<W>::tr_type(),
+                found: tr_type,
+            });
+        }
+        let flags_offset = reader.offset();
+        (flags_offset, u32::from(reader.take(1)?[0]))
+    } else if magic_number == ENCODE_DEPRECATED_MAGIC_NUMBER {
+        let flags_offset = reader.offset();
+        (flags_offset, reader.read_i32()? as u32)
+    } else if magic_number.swap_bytes() == ENCODE_MAGIC_NUMBER
+        || magic_number.swap_bytes() == ENCODE_DEPRECATED_MAGIC_NUMBER
+    {
+        return Err(FstReadError::BigEndian { offset });
+    } else {
+        return Err(FstReadError::InvalidMagicNumber {
+            offset,
+            magic_number,
+        });
+    };
+    let invalid_flags = FstReadError::InvalidFlags {
+        offset: flags_offset,
+        flags,
+    };
+    let flags = EncodeFlags::from_bits(flags).ok_or_else(|| invalid_flags.clone())?;
+    let encode_type = EncodeType::from_bools(
+        flags.contains(EncodeFlags::ENCODE_WEIGHTS),
+        flags.contains(EncodeFlags::ENCODE_LABELS),
+    )
+    .map_err(|_| invalid_flags)?;
+
+    let offset = reader.offset();
+    let num_tuples = reader.read_i64()?;
+    // Each tuple contains at least its two labels.
+    let num_tuples = reader.check_count("number of tuples", num_tuples, 8, offset)?;
+    let mut table = EncodeTableMut::new(encode_type);
+    for id in 1..=num_tuples {
+        let offset = reader.offset();
+        let ilabel = reader.read_i32()? as Label;
+        let olabel = reader.read_i32()? as Label;
+        let weight = reader.parse(W::parse_binary)?;
+        if table.encode(EncodeTuple {
+            ilabel,
+            olabel,
+            weight,
+        }) != id
+        {
+            return Err(FstReadError::InvalidEncodeTable {
+                offset,
+                message: format!("The tuple with key {} is a duplicate", id),
+            });
+        }
+    }
+    if flags.contains(EncodeFlags::HAS_ISYMBOLS) {
+        table.isymt = Some(Arc::new(read_symbol_table_bin(reader)?));
+    }
+    if flags.contains(EncodeFlags::HAS_OSYMBOLS) {
+        table.osymt = Some(Arc::new(read_symbol_table_bin(reader)?));
+    }
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::encode::{decode, encode, encode_with_table};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{Fst, MutableFst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::symt;
+
+    fn build_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 0.5, s1))?;
+        fst.add_tr(s0, Tr::new(2, 1, 1.5, s1))?;
+        fst.set_final(s1, 2.0)?;
+        fst.set_input_symbols(Arc::new(symt!["a", "b"]));
+        fst.set_output_symbols(Arc::new(symt!["x", "y"]));
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_encode_table_store_load_decode() -> Result<()> {
+        let ref_fst = build_fst()?;
+        let mut fst = ref_fst.clone();
+        let table = encode(&mut fst, EncodeType::EncodeLabels)?;
+        let mut data = vec![];
+        table.store(&mut data)?;
+
+        let loaded = EncodeTable::<TropicalWeight>::load(&data)?;
+        assert_eq!(loaded.encode_type(), EncodeType::EncodeLabels);
+        assert_eq!(loaded.0.borrow().len(), table.0.borrow().len());
+        assert_eq!(loaded.input_symbols(), ref_fst.input_symbols().cloned());
+        assert_eq!(loaded.output_symbols(), ref_fst.output_symbols().cloned());
+        let mut stored_again = vec![];
+        loaded.store(&mut stored_again)?;
+        assert_eq!(stored_again, data);
+
+        let mut decoded = fst.clone();
+        decoded.take_input_symbols();
+        decode(&mut decoded, loaded)?;
+        assert_eq!(decoded, ref_fst);
+        assert_eq!(decoded.input_symbols(), ref_fst.input_symbols());
+
+        // Encoding another FST with the loaded table reuses its keys.
+        let mut reencoded = ref_fst.clone();
+        encode_with_table(&mut reencoded, EncodeTable::load(&data)?)?;
+        assert_eq!(reencoded, fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_table_load_deprecated_format() -> Result<()> {
+        let mut data = vec![];
+        write_bin_i32(&mut data, ENCODE_DEPRECATED_MAGIC_NUMBER)?;
+        write_bin_i32(&mut data, EncodeFlags::ENCODE_LABELS.bits() as i32)?;
+        write_bin_i64(&mut data, 1)?;
+        write_bin_i32(&mut data, 3)?;
+        write_bin_i32(&mut data, 4)?;
+        TropicalWeight::one().write_binary(&mut data)?;
+
+        let table = EncodeTable::<TropicalWeight>::load(&data)?;
+        assert_eq!(table.encode_type(), EncodeType::EncodeLabels);
+        assert!(table.input_symbols().is_none());
+        let tuple = table.0.borrow_mut().decode(1).cloned().unwrap();
+        assert_eq!((tuple.ilabel, tuple.olabel), (3, 4));
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_table_load_errors() -> Result<()> {
+        let mut fst = build_fst()?;
+        let table = encode(&mut fst, EncodeType::EncodeWeightsAndLabels)?;
+        let mut data = vec![];
+        table.store(&mut data)?;
+
+        let err = EncodeTable::<LogWeight>::load(&data).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<FstReadError>(),
+            Some(&FstReadError::TrTypeMismatch {
+                offset: 4,
+                expected: "log".to_string(),
+                found: "standard".to_string(),
+            })
+        );
+        let err = EncodeTable::<TropicalWeight>::load(&data[..data.len() - 1]).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FstReadError>(),
+            Some(FstReadError::Truncated { .. })
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::WeightQuantize;
+use crate::{Label, StateId, Trs, KDELTA};
+
+/// Configuration for equivalence checking.
+#[derive(Clone, Debug, Copy, PartialOrd, PartialEq)]
+pub struct EquivalentConfig {
+    /// Quantization delta used when comparing the weights.
+    pub delta: f32,
+}
+
+impl Default for EquivalentConfig {
+    fn default() -> Self {
+        Self { delta: KDELTA }
+    }
+}
+
+impl EquivalentConfig {
+    pub fn new(delta: f32) -> Self {
+        Self { delta }
+    }
+}
+
+/// Union-find over the states of both FSTs. The states of the second FST are shifted by the
+/// number of states of the first one.
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    fn union(&mut self, x: usize, y: usize) {
+        let (root_x, root_y) = (self.find(x), self.find(y));
+        self.parents[root_y] = root_x;
+    }
+}
+
+type TrKey<W> = (Label, Label, W);
+type TransitionsMap<W> = HashMap<TrKey<W>, StateId>;
+
+/// Maps the (ilabel, olabel, quantized weight) of the transitions leaving `state` to their
+/// nextstate. Fails if two transitions share the same key.
+fn transitions_map<W, F>(fst: &F, state: StateId, delta: f32) -> Result<TransitionsMap<W>>
+where
+    W: WeightQuantize,
+    F: ExpandedFst<W>,
+{
+    let mut res = HashMap::new();
+    for tr in fst.get_trs(state)?.trs() {
+        let key = (tr.ilabel, tr.olabel, tr.weight.quantize(delta)?);
+        match res.entry(key) {
+            Entry::Vacant(e) => {
+                e.insert(tr.nextstate);
+            }
+            Entry::Occupied(_) => bail!(
+                "Equivalent : expected deterministic FSTs, state {} has several transitions with the same labels and weight",
+                state
+            ),
+        }
+    }
+    Ok(res)
+}
+
+fn final_weight_quantized<W, F>(fst: &F, state: StateId, delta: f32) -> Result<Option<W>>
+where
+    W: WeightQuantize,
+    F: ExpandedFst<W>,
+{
+    fst.final_weight(state)?
+        .map(|w| w.quantize(delta))
+        .transpose()
+}
+
+/// Final weight and transitions of a state, `None` being the dead state reached when the
+/// other FST has a transition that this one doesn't have.
+fn state_info<W, F>(
+    fst: &F,
+    state: Option<StateId>,
+    delta: f32,
+) -> Result<(Option<W>, TransitionsMap<W>)>
+where
+    W: WeightQuantize,
+    F: ExpandedFst<W>,
+{
+    match state {
+        Some(state) => Ok((
+            final_weight_quantized(fst, state, delta)?,
+            transitions_map(fst, state, delta)?,
+        )),
+        None => Ok((None, HashMap::new())),
+    }
+}
+
+/// Determines if two FSTs are equivalent, i.e they accept the same weighted strings, using the
+/// Hopcroft-Karp algorithm.
+///
+/// The FSTs must be deterministic when each (input label, output label, weight) triple is
+/// considered as a single label, which is the case for instance of the output of
+/// `determinize` on an acceptor. The weights are compared after quantization so the weights
+/// must be distributed in the same way along the paths, for instance by applying `push` to
+/// both FSTs beforehand.
+///
+/// A transition missing from one of the FSTs leads to a dead state, so that the FSTs don't
+/// need to be trimmed.
+pub fn equivalent<W, F1, F2>(fst_1: &F1, fst_2: &F2) -> Result<bool>
+where
+    W: WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+{
+    equivalent_with_config(fst_1, fst_2, EquivalentConfig::default())
+}
+
+/// Same as `equivalent` with a configurable quantization delta.
+pub fn equivalent_with_config<W, F1, F2>(
+    fst_1: &F1,
+    fst_2: &F2,
+    config: EquivalentConfig,
+) -> Result<bool>
+where
+    W: WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+{
+    let (start_1, start_2) = match (fst_1.start(), fst_2.start()) {
+        (None, None) => return Ok(true),
+        (Some(s1), Some(s2)) => (s1, s2),
+        _ => return Ok(false),
+    };
+    // The states of the second FST are shifted by `offset` and the dead state shared by both
+    // FSTs comes last.
+    let offset = fst_1.num_states();
+    let dead_state = offset + fst_2.num_states();
+    let index_1 = |s: Option<StateId>| s.map_or(dead_state, |s| s as usize);
+    let index_2 = |s: Option<StateId>| s.map_or(dead_state, |s| s as usize + offset);
+    let mut union_find = UnionFind::new(dead_state + 1);
+    union_find.union(start_1 as usize, start_2 as usize + offset);
+
+    let mut queue = VecDeque::new();
+    queue.push_back((Some(start_1), Some(start_2)));
+    while let Some((s1, s2)) = queue.pop_front() {
+        let (final_weight_1, trs_1) = state_info(fst_1, s1, config.delta)?;
+        let (final_weight_2, mut trs_2) = state_info(fst_2, s2, config.delta)?;
+        if final_weight_1 != final_weight_2 {
+            return Ok(false);
+        }
+        let mut next_pairs: Vec<_> = trs_1
+            .into_iter()
+            .map(|(key, nextstate_1)| (Some(nextstate_1), trs_2.remove(&key)))
+            .collect();
+        next_pairs.extend(trs_2.into_values().map(|n| (None, Some(n))));
+        for (nextstate_1, nextstate_2) in next_pairs {
+            let root_1 = union_find.find(index_1(nextstate_1));
+            let root_2 = union_find.find(index_2(nextstate_2));
+            if root_1 != root_2 {
+                union_find.union(root_1, root_2);
+                queue.push_back((nextstate_1, nextstate_2));
+            }
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::SerializableFst;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_equivalent_different_numbering() -> Result<()> {
+        let fst_1: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.5\n1\t2\t2\t2\n2\t1\t1\t1\n2\n")?;
+        let fst_2: VectorFst<TropicalWeight> = SerializableFst::from_text_string(
+            "0\t2\t1\t1\t0.5\n2\t1\t2\t2\n1\t3\t1\t1\n3\t4\t2\t2\n4\t3\t1\t1\n1\n4\n",
+        )?;
+        assert!(equivalent(&fst_1, &fst_2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_equivalent() -> Result<()> {
+        let fst_1: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.5\n1\n")?;
+        let fst_2: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.7\n1\n")?;
+        assert!(!equivalent(&fst_1, &fst_2)?);
+        assert!(equivalent_with_config(
+            &fst_1,
+            &fst_2,
+            EquivalentConfig::new(1.0)
+        )?);
+
+        let fst_3: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.5\n1\t1\t1\t1\t0.5\n1\n")?;
+        assert!(!equivalent(&fst_1, &fst_3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_equivalent_not_trimmed() -> Result<()> {
+        let fst_1: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.5\n1\n")?;
+        let fst_2: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.5\n0\t2\t2\t2\n2\t3\t3\t3\n1\n")?;
+        assert!(equivalent(&fst_1, &fst_2)?);
+        assert!(equivalent(&fst_2, &fst_1)?);
+
+        let fst_3: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\t0.5\n0\t2\t2\t2\n1\n2\n")?;
+        assert!(!equivalent(&fst_1, &fst_3)?);
+        assert!(!equivalent(&fst_3, &fst_1)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_equivalent_non_deterministic() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\n0\t2\t1\t1\n1\n2\n")?;
+        assert!(equivalent(&fst, &fst).is_err());
+        Ok(())
+    }
+}
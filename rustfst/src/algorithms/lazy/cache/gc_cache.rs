@@ -0,0 +1,497 @@
+use std::collections::{BTreeMap, HashMap};
+use std::mem::size_of;
+use std::sync::Mutex;
+
+use crate::algorithms::lazy::cache::cache_internal_types::{CacheTrs, FinalWeight, StartState};
+use crate::algorithms::lazy::{CacheStatus, FstCache};
+use crate::semirings::Semiring;
+use crate::{StateId, Tr, Trs, TrsVec, EPS_LABEL};
+
+/// Default number of bytes a `GcCache` is allowed to use (same value as in OpenFST).
+pub const DEFAULT_GC_LIMIT: usize = 1 << 20;
+
+/// Fraction of the `gc_limit` the cache is shrunk to when a garbage collection is triggered.
+const GC_CACHE_FRACTION: f32 = 0.666;
+
+/// Configuration of a [`GcCache`]. Equivalent of OpenFST's `CacheOptions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GcCacheConfig {
+    /// Enables garbage collection. If false, the cache grows without bound.
+    pub gc: bool,
+    /// Number of bytes the expanded states are allowed to use before the least
+    /// recently used ones get evicted.
+    pub gc_limit: usize,
+}
+
+impl Default for GcCacheConfig {
+    fn default() -> Self {
+        Self {
+            gc: true,
+            gc_limit: DEFAULT_GC_LIMIT,
+        }
+    }
+}
+
+impl GcCacheConfig {
+    pub fn new(gc: bool, gc_limit: usize) -> Self {
+        Self { gc, gc_limit }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GcCacheState<W: Semiring> {
+    trs: Option<CacheTrs<W>>,
+    final_weight: CacheStatus<FinalWeight<W>>,
+    last_access: u64,
+    size: usize,
+}
+
+impl<W: Semiring> GcCacheState<W> {
+    fn compute_size(&self) -> usize {
+        let trs_size = self
+            .trs
+            .as_ref()
+            .map(|e| e.trs.len() * size_of::<Tr<W>>())
+            .unwrap_or(0);
+        size_of::<Self>() + trs_size
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GcCacheData<W: Semiring> {
+    start: CacheStatus<StartState>,
+    states: HashMap<StateId, GcCacheState<W>>,
+    // Access stamp -> state. The first element is the least recently used state.
+    lru: BTreeMap<u64, StateId>,
+    clock: u64,
+    cache_size: usize,
+    num_known_states: usize,
+}
+
+impl<W: Semiring> Default for GcCacheData<W> {
+    fn default() -> Self {
+        Self {
+            start: CacheStatus::NotComputed,
+            states: HashMap::new(),
+            lru: BTreeMap::new(),
+            clock: 0,
+            cache_size: 0,
+            num_known_states: 0,
+        }
+    }
+}
+
+impl<W: Semiring> GcCacheData<W> {
+    fn touch(&mut self, id: StateId) {
+        if let Some(state) = self.states.get_mut(&id) {
+            self.lru.remove(&state.last_access);
+            self.clock += 1;
+            state.last_access = self.clock;
+            self.lru.insert(self.clock, id);
+        }
+    }
+
+    fn state_mut(&mut self, id: StateId) -> &mut GcCacheState<W> {
+        if !self.states.contains_key(&id) {
+            self.clock += 1;
+            self.lru.insert(self.clock, id);
+            let mut state = GcCacheState {
+                trs: None,
+                final_weight: CacheStatus::NotComputed,
+                last_access: self.clock,
+                size: 0,
+            };
+            state.size = state.compute_size();
+            self.cache_size += state.size;
+            self.states.insert(id, state);
+        } else {
+            self.touch(id);
+        }
+        self.states.get_mut(&id).unwrap()
+    }
+
+    fn update_size(&mut self, id: StateId) {
+        if let Some(state) = self.states.get_mut(&id) {
+            let new_size = state.compute_size();
+            self.cache_size = self.cache_size - state.size + new_size;
+            state.size = new_size;
+        }
+    }
+
+    /// Evicts the least recently used states until the cache fits in its budget.
+    /// The `protected` state is never evicted as it is the one currently being accessed.
+    fn gc(&mut self, protected: StateId, gc_limit: usize) {
+        if self.cache_size <= gc_limit {
+            return;
+        }
+        let target = (gc_limit as f32 * GC_CACHE_FRACTION) as usize;
+        while self.cache_size > target {
+            let oldest = self
+                .lru
+                .iter()
+                .map(|(stamp, s)| (*stamp, *s))
+                .find(|(_, s)| *s != protected);
+            match oldest {
+                Some((stamp, s)) => {
+                    self.lru.remove(&stamp);
+                    if let Some(state) = self.states.remove(&s) {
+                        self.cache_size -= state.size;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Cache storing the expanded states of a lazy FST within a memory budget.
+///
+/// Once the estimated memory used by the cached states exceeds `gc_limit`, the least
+/// recently accessed states are evicted. An evicted state is reported as `NotComputed`
+/// and is therefore recomputed by the `LazyFst` the next time it is accessed. The start
+/// state and the number of known states are never forgotten.
+#[derive(Debug)]
+pub struct GcCache<W: Semiring> {
+    config: GcCacheConfig,
+    data: Mutex<GcCacheData<W>>,
+}
+
+impl<W: Semiring> GcCache<W> {
+    pub fn new(config: GcCacheConfig) -> Self {
+        Self {
+            config,
+            data: Mutex::new(GcCacheData::default()),
+        }
+    }
+
+    pub fn config(&self) -> GcCacheConfig {
+        self.config
+    }
+
+    /// Estimation of the number of bytes currently used by the cached states.
+    pub fn cache_size(&self) -> usize {
+        self.data.lock().unwrap().cache_size
+    }
+
+    pub fn clear(&self) {
+        let mut data = self.data.lock().unwrap();
+        *data = GcCacheData::default();
+    }
+
+    fn maybe_gc(&self, data: &mut GcCacheData<W>, protected: StateId) {
+        if self.config.gc {
+            data.gc(protected, self.config.gc_limit);
+        }
+    }
+}
+
+impl<W: Semiring> Clone for GcCache<W> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config,
+            data: Mutex::new(self.data.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl<W: Semiring> Default for GcCache<W> {
+    fn default() -> Self {
+        Self::new(GcCacheConfig::default())
+    }
+}
+
+impl<W: Semiring> FstCache<W> for GcCache<W> {
+    fn get_start(&self) -> CacheStatus<StartState> {
+        self.data.lock().unwrap().start
+    }
+
+    fn insert_start(&self, id: StartState) {
+        let mut data = self.data.lock().unwrap();
+        if let Some(s) = id {
+            data.num_known_states = std::cmp::max(data.num_known_states, s as usize + 1);
+        }
+        data.start = CacheStatus::Computed(id);
+    }
+
+    fn get_trs(&self, id: StateId) -> CacheStatus<TrsVec<W>> {
+        let mut data = self.data.lock().unwrap();
+        let res = match data.states.get(&id).and_then(|e| e.trs.as_ref()) {
+            Some(e) => CacheStatus::Computed(e.trs.shallow_clone()),
+            None => return CacheStatus::NotComputed,
+        };
+        data.touch(id);
+        res
+    }
+
+    fn insert_trs(&self, id: StateId, trs: TrsVec<W>) {
+        let mut data = self.data.lock().unwrap();
+        data.num_known_states = std::cmp::max(data.num_known_states, id as usize + 1);
+        let mut niepsilons = 0;
+        let mut noepsilons = 0;
+        for tr in trs.trs() {
+            data.num_known_states = std::cmp::max(data.num_known_states, tr.nextstate as usize + 1);
+            if tr.ilabel == EPS_LABEL {
+                niepsilons += 1;
+            }
+            if tr.olabel == EPS_LABEL {
+                noepsilons += 1;
+            }
+        }
+        data.state_mut(id).trs = Some(CacheTrs {
+            trs,
+            niepsilons,
+            noepsilons,
+        });
+        data.update_size(id);
+        self.maybe_gc(&mut data, id);
+    }
+
+    fn get_final_weight(&self, id: StateId) -> CacheStatus<FinalWeight<W>> {
+        let mut data = self.data.lock().unwrap();
+        let res = match data.states.get(&id) {
+            Some(e) => e.final_weight.clone(),
+            None => return CacheStatus::NotComputed,
+        };
+        if res.is_computed() {
+            data.touch(id);
+        }
+        res
+    }
+
+    fn insert_final_weight(&self, id: StateId, weight: FinalWeight<W>) {
+        let mut data = self.data.lock().unwrap();
+        data.num_known_states = std::cmp::max(data.num_known_states, id as usize + 1);
+        data.state_mut(id).final_weight = CacheStatus::Computed(weight);
+        data.update_size(id);
+        self.maybe_gc(&mut data, id);
+    }
+
+    fn num_known_states(&self) -> usize {
+        self.data.lock().unwrap().num_known_states
+    }
+
+    fn compute_num_known_trs(&self) -> usize {
+        let data = self.data.lock().unwrap();
+        data.states
+            .values()
+            .filter_map(|e| e.trs.as_ref())
+            .map(|e| e.trs.len())
+            .sum()
+    }
+
+    fn num_trs(&self, id: StateId) -> Option<usize> {
+        let data = self.data.lock().unwrap();
+        data.states
+            .get(&id)
+            .and_then(|e| e.trs.as_ref())
+            .map(|e| e.trs.len())
+    }
+
+    fn num_input_epsilons(&self, id: StateId) -> Option<usize> {
+        let data = self.data.lock().unwrap();
+        data.states
+            .get(&id)
+            .and_then(|e| e.trs.as_ref())
+            .map(|e| e.niepsilons)
+    }
+
+    fn num_output_epsilons(&self, id: StateId) -> Option<usize> {
+        let data = self.data.lock().unwrap();
+        data.states
+            .get(&id)
+            .and_then(|e| e.trs.as_ref())
+            .map(|e| e.noepsilons)
+    }
+
+    fn len_trs(&self) -> usize {
+        let data = self.data.lock().unwrap();
+        data.states.values().filter(|e| e.trs.is_some()).count()
+    }
+
+    fn len_final_weights(&self) -> usize {
+        let data = self.data.lock().unwrap();
+        data.states
+            .values()
+            .filter(|e| e.final_weight.is_computed())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+    use crate::algorithms::compose::matchers::GenericMatcher;
+    use crate::algorithms::compose::{ComposeFst, ComposeFstOpOptions};
+    use crate::algorithms::lazy::SimpleHashMapCache;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst, Fst, StateIterator};
+    use crate::semirings::TropicalWeight;
+    use crate::utils::acceptor;
+    use anyhow::Result;
+    use std::sync::Arc;
+
+    fn build_trs(n: usize) -> TrsVec<TropicalWeight> {
+        let mut trs = TrsVec::default();
+        for i in 0..n {
+            trs.push(Tr::new(1, 1, TropicalWeight::one(), i as StateId));
+        }
+        trs
+    }
+
+    #[test]
+    fn test_gc_cache_without_gc_keeps_everything() {
+        let cache = GcCache::new(GcCacheConfig::new(false, 0));
+        for s in 0..10 {
+            cache.insert_trs(s, build_trs(3));
+        }
+        assert_eq!(cache.len_trs(), 10);
+        assert_eq!(cache.compute_num_known_trs(), 30);
+    }
+
+    #[test]
+    fn test_gc_cache_evicts_least_recently_used() {
+        let state_size = GcCacheState {
+            trs: Some(CacheTrs {
+                trs: build_trs(2),
+                niepsilons: 0,
+                noepsilons: 0,
+            }),
+            final_weight: CacheStatus::NotComputed,
+            last_access: 0,
+            size: 0,
+        }
+        .compute_size();
+        let cache = GcCache::new(GcCacheConfig::new(true, 3 * state_size));
+        cache.insert_trs(0, build_trs(2));
+        cache.insert_trs(1, build_trs(2));
+        cache.insert_trs(2, build_trs(2));
+        assert_eq!(cache.len_trs(), 3);
+
+        // Touch state 0 so that state 1 becomes the least recently used.
+        assert!(cache.get_trs(0).is_computed());
+        cache.insert_trs(3, build_trs(2));
+
+        assert!(cache.cache_size() <= 3 * state_size);
+        assert!(cache.get_trs(1).is_not_computed());
+        assert!(cache.get_trs(3).is_computed());
+        assert_eq!(cache.num_trs(1), None);
+        assert_eq!(cache.num_trs(3), Some(2));
+        // The number of known states is never forgotten.
+        assert_eq!(cache.num_known_states(), 4);
+    }
+
+    #[test]
+    fn test_gc_cache_never_evicts_current_state() {
+        let cache = GcCache::new(GcCacheConfig::new(true, 1));
+        cache.insert_trs(0, build_trs(5));
+        cache.insert_trs(1, build_trs(5));
+        assert!(cache.get_trs(0).is_not_computed());
+        assert_eq!(cache.num_trs(1), Some(5));
+        cache.insert_final_weight(1, Some(TropicalWeight::one()));
+        assert_eq!(cache.num_trs(1), Some(5));
+        assert_eq!(
+            cache.get_final_weight(1),
+            CacheStatus::Computed(Some(TropicalWeight::one()))
+        );
+    }
+
+    #[test]
+    fn test_gc_cache_lazy_fst() -> Result<()> {
+        let fst1: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3, 4], TropicalWeight::one());
+        let fst2: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3, 4], TropicalWeight::new(2.0));
+        let fst1 = Arc::new(fst1);
+        let fst2 = Arc::new(fst2);
+
+        let compose_unbounded = ComposeFst::<
+            TropicalWeight,
+            VectorFst<_>,
+            VectorFst<_>,
+            _,
+            _,
+            GenericMatcher<_, _, _>,
+            GenericMatcher<_, _, _>,
+            SequenceComposeFilterBuilder<_, _, _, _, _, _, _>,
+            SimpleHashMapCache<_>,
+        >::new_with_options_and_cache(
+            Arc::clone(&fst1),
+            Arc::clone(&fst2),
+            ComposeFstOpOptions::default(),
+            SimpleHashMapCache::default(),
+        )?;
+        let compose_gc = ComposeFst::<
+            TropicalWeight,
+            VectorFst<_>,
+            VectorFst<_>,
+            _,
+            _,
+            GenericMatcher<_, _, _>,
+            GenericMatcher<_, _, _>,
+            SequenceComposeFilterBuilder<_, _, _, _, _, _, _>,
+            GcCache<_>,
+        >::new_with_options_and_cache(
+            fst1,
+            fst2,
+            ComposeFstOpOptions::default(),
+            GcCache::new(GcCacheConfig::new(true, 1)),
+        )?;
+
+        let fst_ref: VectorFst<_> = compose_unbounded.compute()?;
+        let fst_gc: VectorFst<_> = compose_gc.compute()?;
+        assert_eq!(fst_ref, fst_gc);
+
+        // Paths can still be enumerated even though states keep being evicted.
+        assert_eq!(compose_gc.paths_iter().count(), 1);
+        assert_eq!(fst_ref.num_states(), compose_gc.states_iter().count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_gc_cache_lazy_fst_counts_after_eviction() -> Result<()> {
+        let fst1: VectorFst<TropicalWeight> = acceptor(&[1, 0, 3, 0], TropicalWeight::one());
+        let fst2: VectorFst<TropicalWeight> = acceptor(&[1, 0, 3, 0], TropicalWeight::one());
+        let fst_ref: VectorFst<TropicalWeight> =
+            ComposeFst::<_, VectorFst<_>, VectorFst<_>, _, _, _, _, _>::new_auto(
+                Arc::new(fst1.clone()),
+                Arc::new(fst2.clone()),
+            )?
+            .compute()?;
+        let compose_gc = ComposeFst::<
+            TropicalWeight,
+            VectorFst<_>,
+            VectorFst<_>,
+            _,
+            _,
+            GenericMatcher<_, _, _>,
+            GenericMatcher<_, _, _>,
+            SequenceComposeFilterBuilder<_, _, _, _, _, _, _>,
+            GcCache<_>,
+        >::new_with_options_and_cache(
+            Arc::new(fst1),
+            Arc::new(fst2),
+            ComposeFstOpOptions::default(),
+            GcCache::new(GcCacheConfig::new(true, 1)),
+        )?;
+
+        // Expands all the states, only the last one expanded remains in the cache.
+        assert_eq!(fst_ref.num_states(), compose_gc.states_iter().count());
+        for s in fst_ref.states_iter() {
+            assert_eq!(compose_gc.num_trs(s)?, fst_ref.num_trs(s)?);
+            assert_eq!(
+                unsafe { compose_gc.num_trs_unchecked(s) },
+                fst_ref.num_trs(s)?
+            );
+            assert_eq!(
+                compose_gc.num_input_epsilons(s)?,
+                fst_ref.num_input_epsilons(s)?
+            );
+            assert_eq!(
+                compose_gc.num_output_epsilons(s)?,
+                fst_ref.num_output_epsilons(s)?
+            );
+        }
+        let num_states = fst_ref.num_states() as StateId;
+        assert!(compose_gc.num_trs(num_states).is_err());
+        Ok(())
+    }
+}
@@ -326,9 +326,16 @@ pub fn parse_simple_hashmap_cache<W: SerializableSemiring>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::compose::compose_filters::SequenceComposeFilterBuilder;
+    use crate::algorithms::compose::matchers::GenericMatcher;
+    use crate::algorithms::compose::{ComposeFst, ComposeFstOpOptions};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst};
     use crate::prelude::Tr;
     use crate::semirings::TropicalWeight;
+    use crate::utils::acceptor;
     use anyhow::anyhow;
+    use std::sync::Arc;
 
     #[test]
     fn test_read_write_cache_start_state_computed() -> Result<()> {
@@ -434,4 +441,47 @@ mod tests {
         assert_eq!(cache, parsed_cache);
         Ok(())
     }
+
+    #[test]
+    fn test_simple_hashmap_cache_lazy_fst_counts_expand_states() -> Result<()> {
+        let fst: Arc<VectorFst<TropicalWeight>> =
+            Arc::new(acceptor(&[1, 0, 3], TropicalWeight::one()));
+        let new_compose = || {
+            ComposeFst::<
+                TropicalWeight,
+                VectorFst<_>,
+                VectorFst<_>,
+                _,
+                _,
+                GenericMatcher<_, _, _>,
+                GenericMatcher<_, _, _>,
+                SequenceComposeFilterBuilder<_, _, _, _, _, _, _>,
+                SimpleHashMapCache<_>,
+            >::new_with_options_and_cache(
+                Arc::clone(&fst),
+                Arc::clone(&fst),
+                ComposeFstOpOptions::default(),
+                SimpleHashMapCache::default(),
+            )
+        };
+        let fst_ref: VectorFst<_> = new_compose()?.compute()?;
+
+        let compose = new_compose()?;
+        // The counts expand the states that are not in the cache yet, following the order in
+        // which they are discovered.
+        for s in 0..(fst_ref.num_states() as StateId) {
+            assert_eq!(compose.num_trs(s)?, fst_ref.num_trs(s)?);
+            assert_eq!(
+                compose.num_input_epsilons(s)?,
+                fst_ref.num_input_epsilons(s)?
+            );
+            assert_eq!(
+                compose.num_output_epsilons(s)?,
+                fst_ref.num_output_epsilons(s)?
+            );
+        }
+        let num_states = fst_ref.num_states() as StateId;
+        assert!(compose.num_trs(num_states).is_err());
+        Ok(())
+    }
 }
@@ -1,17 +1,37 @@
 use std::sync::Arc;
 
 use nom::multi::count;
+use nom::number::complete::le_i32;
 use nom::IResult;
 
 use crate::algorithms::lazy::cache::cache_internal_types::{CacheTrs, FinalWeight, StartState};
 use crate::algorithms::lazy::CacheStatus;
-use crate::parsers::bin_fst::utils_parsing::{parse_bin_fst_tr, parse_start_state};
+use crate::parsers::bin_fst::utils_parsing::parse_start_state;
 use crate::parsers::nom_utils::NomCustomError;
 use crate::parsers::{parse_bin_i64, parse_bin_u64, parse_bin_u8};
 use crate::prelude::{SerializableSemiring, StateId, TrsVec};
+use crate::{Label, Tr};
 
 pub(crate) type IResultCustomError<A, B> = IResult<A, B, NomCustomError<A>>;
 
+// The cached trs are written with the layout of the binary FST format. Unlike the FST
+// loaders, the destination states are not validated as the cache may be partial.
+fn parse_cache_tr<W: SerializableSemiring>(i: &[u8]) -> IResultCustomError<&[u8], Tr<W>> {
+    let (i, ilabel) = le_i32(i)?;
+    let (i, olabel) = le_i32(i)?;
+    let (i, weight) = W::parse_binary(i)?;
+    let (i, nextstate) = le_i32(i)?;
+    Ok((
+        i,
+        Tr {
+            ilabel: ilabel as Label,
+            olabel: olabel as Label,
+            weight,
+            nextstate: nextstate as StateId,
+        },
+    ))
+}
+
 pub(crate) fn parse_cache_start_state(
     i: &[u8],
 ) -> IResultCustomError<&[u8], CacheStatus<StartState>> {
@@ -33,7 +53,7 @@ pub(crate) fn parse_vec_cache_trs<W: SerializableSemiring>(
         Ok((i, CacheStatus::NotComputed))
     } else {
         let (i, num_trs) = parse_bin_i64(i)?;
-        let (i, trs) = count(parse_bin_fst_tr::<W>, num_trs as usize)(i)?;
+        let (i, trs) = count(parse_cache_tr::<W>, num_trs as usize)(i)?;
         let (i, niepsilons) = parse_bin_u64(i)?;
         let (i, noepsilons) = parse_bin_u64(i)?;
 
@@ -53,7 +73,7 @@ pub(crate) fn parse_hashmap_cache_trs<W: SerializableSemiring>(
 ) -> IResultCustomError<&[u8], (StateId, CacheTrs<W>)> {
     let (i, state) = parse_bin_i64(i)?;
     let (i, num_trs) = parse_bin_i64(i)?;
-    let (i, trs) = count(parse_bin_fst_tr::<W>, num_trs as usize)(i)?;
+    let (i, trs) = count(parse_cache_tr::<W>, num_trs as usize)(i)?;
     let (i, niepsilons) = parse_bin_u64(i)?;
     let (i, noepsilons) = parse_bin_u64(i)?;
 
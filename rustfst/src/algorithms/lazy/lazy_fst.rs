@@ -13,12 +13,13 @@ use unsafe_unwrap::UnsafeUnwrap;
 use crate::algorithms::lazy::cache::CacheStatus;
 use crate::algorithms::lazy::fst_op::{AccessibleOpState, FstOp, SerializableOpState};
 use crate::algorithms::lazy::{FstCache, SerializableCache};
+use crate::algorithms::{Progress, ResourceLimits};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{
     AllocableFst, CoreFst, Fst, FstIterData, FstIterator, MutableFst, StateIterator,
 };
 use crate::semirings::{Semiring, SerializableSemiring};
-use crate::{StateId, SymbolTable, Trs, TrsVec};
+use crate::{Error, StateId, SymbolTable, Trs, TrsVec, EPS_LABEL};
 
 #[derive(Debug, Clone)]
 pub struct LazyFst<W: Semiring, Op: FstOp<W>, Cache> {
@@ -61,13 +62,14 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
     }
 
     fn num_trs(&self, s: StateId) -> Result<usize> {
-        self.cache
-            .num_trs(s)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", s))
+        match self.cache.num_trs(s) {
+            Some(num_trs) => Ok(num_trs),
+            None => Ok(self.expand_known_state(s)?.len()),
+        }
     }
 
     unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
-        self.cache.num_trs(s).unsafe_unwrap()
+        self.num_trs(s).unsafe_unwrap()
     }
 
     fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
@@ -90,15 +92,39 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
     }
 
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
-        self.cache
-            .num_input_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+        match self.cache.num_input_epsilons(state) {
+            Some(num_eps) => Ok(num_eps),
+            None => Ok(self
+                .expand_known_state(state)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.ilabel == EPS_LABEL)
+                .count()),
+        }
     }
 
     fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
-        self.cache
-            .num_output_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+        match self.cache.num_output_epsilons(state) {
+            Some(num_eps) => Ok(num_eps),
+            None => Ok(self
+                .expand_known_state(state)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.olabel == EPS_LABEL)
+                .count()),
+        }
+    }
+}
+
+impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> LazyFst<W, Op, Cache> {
+    /// Trs of a state that is either not expanded yet or has been evicted from the cache.
+    /// Only the states already discovered can be expanded, the others are out of range.
+    fn expand_known_state(&self, state: StateId) -> Result<TrsVec<W>> {
+        self.start();
+        if (state as usize) >= self.cache.num_known_states() {
+            return Err(Error::StateOutOfRange { state }.into());
+        }
+        self.get_trs(state)
     }
 }
 
@@ -225,6 +251,24 @@ where
 
     /// Turns the Lazy FST into a static one.
     pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.compute_with_progress(&Progress::default())
+    }
+
+    /// Turns the Lazy FST into a static one, polling `progress` after each state expanded.
+    pub fn compute_with_progress<F2: MutableFst<W> + AllocableFst<W>>(
+        &self,
+        progress: &Progress,
+    ) -> Result<F2> {
+        self.compute_with_limits(progress, &ResourceLimits::default())
+    }
+
+    /// Turns the Lazy FST into a static one, polling `progress` after each state expanded and
+    /// failing as soon as the output exceeds `limits`.
+    pub fn compute_with_limits<F2: MutableFst<W> + AllocableFst<W>>(
+        &self,
+        progress: &Progress,
+        limits: &ResourceLimits,
+    ) -> Result<F2> {
         let start_state = self.start();
         let mut fst_out = F2::new();
         let start_state = match start_state {
@@ -238,8 +282,13 @@ where
         visited_states.resize(start_state as usize + 1, false);
         visited_states[start_state as usize] = true;
         queue.push_back(start_state);
+        let mut num_expanded = 0;
+        let mut num_trs = 0;
         while let Some(s) = queue.pop_front() {
+            num_expanded += 1;
+            progress.step(num_expanded)?;
             let trs_owner = self.get_trs(s)?;
+            num_trs += trs_owner.trs().len();
             for tr in trs_owner.trs() {
                 if (tr.nextstate as usize) >= visited_states.len() {
                     visited_states.resize(tr.nextstate as usize + 1, false);
@@ -253,6 +302,7 @@ where
                     fst_out.add_states(tr.nextstate as usize - n + 1)
                 }
             }
+            limits.check::<W>(fst_out.num_states(), num_trs)?;
             unsafe { fst_out.set_trs_unchecked(s, trs_owner.trs().to_vec()) };
             if let Some(f_w) = self.final_weight(s)? {
                 fst_out.set_final(s, f_w)?;
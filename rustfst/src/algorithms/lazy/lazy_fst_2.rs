@@ -1,9 +1,7 @@
-use std::iter::{repeat, Map, Repeat, Zip};
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 use anyhow::Result;
-use itertools::izip;
 use unsafe_unwrap::UnsafeUnwrap;
 
 use crate::algorithms::lazy::fst_op_2::FstOp2;
@@ -11,7 +9,7 @@ use crate::algorithms::lazy::{CacheStatus, FstCache};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, Fst, FstIterData, FstIterator, MutableFst, StateIterator};
 use crate::semirings::Semiring;
-use crate::{StateId, SymbolTable, Trs, TrsVec};
+use crate::{Error, StateId, SymbolTable, Trs, TrsVec, EPS_LABEL};
 use std::collections::{HashSet, VecDeque};
 
 #[derive(Debug)]
@@ -56,13 +54,14 @@ impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst2<W,
     }
 
     fn num_trs(&self, s: StateId) -> Result<usize> {
-        self.cache
-            .num_trs(s)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", s))
+        match self.cache.num_trs(s) {
+            Some(num_trs) => Ok(num_trs),
+            None => Ok(self.expand_known_state(s)?.len()),
+        }
     }
 
     unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
-        self.cache.num_trs(s).unsafe_unwrap()
+        self.num_trs(s).unsafe_unwrap()
     }
 
     fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
@@ -86,29 +85,55 @@ impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst2<W,
     }
 
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
-        self.cache
-            .num_input_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+        match self.cache.num_input_epsilons(state) {
+            Some(num_eps) => Ok(num_eps),
+            None => Ok(self
+                .expand_known_state(state)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.ilabel == EPS_LABEL)
+                .count()),
+        }
     }
 
     fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
-        self.cache
-            .num_output_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+        match self.cache.num_output_epsilons(state) {
+            Some(num_eps) => Ok(num_eps),
+            None => Ok(self
+                .expand_known_state(state)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.olabel == EPS_LABEL)
+                .count()),
+        }
+    }
+}
+
+impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> LazyFst2<W, Op, Cache> {
+    /// Trs of a state that is not expanded yet. Only the states already discovered can be
+    /// expanded, the others are out of range.
+    fn expand_known_state(&self, state: StateId) -> Result<TrsVec<W>> {
+        self.start();
+        if (state as usize) >= self.cache.num_known_states() {
+            return Err(Error::StateOutOfRange { state }.into());
+        }
+        self.get_trs(state)
     }
 }
 
+// The iterators are boxed so that they don't require `Op: 'a` : `Fst` requires them for any
+// lifetime, which would restrict the lazy FSTs to 'static operations, i.e owned inputs.
 impl<'a, W, Op, Cache> StateIterator<'a> for LazyFst2<W, Op, Cache>
 where
     W: Semiring,
-    Op: FstOp2<W> + 'a,
-    Cache: FstCache<W> + 'a,
+    Op: FstOp2<W>,
+    Cache: FstCache<W>,
 {
-    type Iter = StatesIteratorLazyFst<'a, Self>;
+    type Iter = Box<dyn Iterator<Item = StateId> + 'a>;
 
     fn states_iter(&'a self) -> Self::Iter {
         self.start();
-        StatesIteratorLazyFst { fst: self, s: 0 }
+        Box::new(StatesIteratorLazyFst { fst: self, s: 0 })
     }
 }
 
@@ -140,29 +165,20 @@ where
     }
 }
 
-type ZipIter<'a, W, Op, Cache, SELF> =
-    Zip<<LazyFst2<W, Op, Cache> as StateIterator<'a>>::Iter, Repeat<&'a SELF>>;
-type MapFunction<'a, W, SELF, TRS> = Box<dyn FnMut((StateId, &'a SELF)) -> FstIterData<W, TRS>>;
-type MapIter<'a, W, Op, Cache, SELF, TRS> =
-    Map<ZipIter<'a, W, Op, Cache, SELF>, MapFunction<'a, W, SELF, TRS>>;
-
 impl<'a, W, Op, Cache> FstIterator<'a, W> for LazyFst2<W, Op, Cache>
 where
     W: Semiring,
-    Op: FstOp2<W> + 'a,
-    Cache: FstCache<W> + 'a,
+    Op: FstOp2<W>,
+    Cache: FstCache<W>,
 {
-    type FstIter = MapIter<'a, W, Op, Cache, Self, Self::TRS>;
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, TrsVec<W>>> + 'a>;
 
     fn fst_iter(&'a self) -> Self::FstIter {
-        let it = repeat(self);
-        izip!(self.states_iter(), it).map(Box::new(|(state_id, p): (StateId, &'a Self)| {
-            FstIterData {
-                state_id,
-                trs: unsafe { p.get_trs_unchecked(state_id) },
-                final_weight: unsafe { p.final_weight_unchecked(state_id) },
-                num_trs: unsafe { p.num_trs_unchecked(state_id) },
-            }
+        Box::new(self.states_iter().map(move |state_id| FstIterData {
+            state_id,
+            trs: unsafe { self.get_trs_unchecked(state_id) },
+            final_weight: unsafe { self.final_weight_unchecked(state_id) },
+            num_trs: unsafe { self.num_trs_unchecked(state_id) },
         }))
     }
 }
@@ -170,8 +186,8 @@ where
 impl<W, Op, Cache> Fst<W> for LazyFst2<W, Op, Cache>
 where
     W: Semiring,
-    Op: FstOp2<W> + 'static,
-    Cache: FstCache<W> + 'static,
+    Op: FstOp2<W>,
+    Cache: FstCache<W>,
 {
     fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
         self.isymt.as_ref()
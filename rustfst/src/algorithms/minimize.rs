@@ -24,7 +24,7 @@ use crate::algorithms::{
     encode::{decode, encode},
     tr_map, tr_sort, weight_convert, ReweightType,
 };
-use crate::algorithms::{push_weights_with_config, reverse, PushWeightsConfig};
+use crate::algorithms::{push_weights_with_config, reverse, Progress, PushWeightsConfig};
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, ExpandedFst, Fst, MutableFst};
@@ -33,17 +33,19 @@ use crate::semirings::{
 };
 use crate::EPS_LABEL;
 use crate::KDELTA;
-use crate::{Label, StateId, Trs};
+use crate::{Error, Label, StateId, Trs};
 use crate::{Tr, KSHORTESTDELTA};
 use itertools::Itertools;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 /// Configuration for minimization.
-#[derive(Clone, Copy, PartialOrd, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct MinimizeConfig {
     pub delta: f32,
     pub allow_nondet: bool,
+    /// Polled after each class of states is refined.
+    pub progress: Progress,
 }
 
 impl MinimizeConfig {
@@ -51,6 +53,7 @@ impl MinimizeConfig {
         Self {
             delta,
             allow_nondet,
+            progress: Progress::default(),
         }
     }
 
@@ -64,6 +67,10 @@ impl MinimizeConfig {
             ..self
         }
     }
+
+    pub fn with_progress(self, progress: Progress) -> Self {
+        Self { progress, ..self }
+    }
 }
 
 impl Default for MinimizeConfig {
@@ -71,6 +78,7 @@ impl Default for MinimizeConfig {
         Self {
             delta: KSHORTESTDELTA,
             allow_nondet: false,
+            progress: Progress::default(),
         }
     }
 }
@@ -110,7 +118,10 @@ where
         true
     } else {
         if !W::properties().contains(SemiringProperties::IDEMPOTENT) {
-            bail!("Cannot minimize a non-deterministic FST over a non-idempotent semiring")
+            bail!(Error::MissingSemiringProperties {
+                operation: "minimize",
+                properties: SemiringProperties::IDEMPOTENT,
+            })
         } else if !allow_nondet {
             bail!("Refusing to minimize a non-deterministic FST with allow_nondet = false")
         }
@@ -134,7 +145,7 @@ where
 
         let encode_table = encode(&mut gfst, EncodeType::EncodeWeightsAndLabels)?;
 
-        acceptor_minimize(&mut gfst, allow_acyclic_minimization)?;
+        acceptor_minimize_with_progress(&mut gfst, allow_acyclic_minimization, &config.progress)?;
 
         decode(&mut gfst, encode_table)?;
 
@@ -166,11 +177,11 @@ where
         let quantize_mapper = QuantizeMapper::new(delta);
         tr_map(ifst, &quantize_mapper)?;
         let encode_table = encode(ifst, EncodeType::EncodeWeightsAndLabels)?;
-        acceptor_minimize(ifst, allow_acyclic_minimization)?;
+        acceptor_minimize_with_progress(ifst, allow_acyclic_minimization, &config.progress)?;
         decode(ifst, encode_table)
     } else {
         // Unweighted acceptor
-        acceptor_minimize(ifst, allow_acyclic_minimization)
+        acceptor_minimize_with_progress(ifst, allow_acyclic_minimization, &config.progress)
     }
 }
 
@@ -182,12 +193,23 @@ where
 pub fn acceptor_minimize<W: Semiring, F: MutableFst<W> + ExpandedFst<W>>(
     ifst: &mut F,
     allow_acyclic_minimization: bool,
+) -> Result<()> {
+    acceptor_minimize_with_progress(ifst, allow_acyclic_minimization, &Progress::default())
+}
+
+fn acceptor_minimize_with_progress<W: Semiring, F: MutableFst<W> + ExpandedFst<W>>(
+    ifst: &mut F,
+    allow_acyclic_minimization: bool,
+    progress: &Progress,
 ) -> Result<()> {
     let props = ifst.compute_and_update_properties(
         FstProperties::ACCEPTOR | FstProperties::UNWEIGHTED | FstProperties::ACYCLIC,
     )?;
     if !props.contains(FstProperties::ACCEPTOR | FstProperties::UNWEIGHTED) {
-        bail!("FST is not an unweighted acceptor");
+        bail!(Error::MissingFstProperties {
+            operation: "minimize",
+            properties: FstProperties::ACCEPTOR | FstProperties::UNWEIGHTED,
+        });
     }
 
     connect(ifst)?;
@@ -199,10 +221,10 @@ pub fn acceptor_minimize<W: Semiring, F: MutableFst<W> + ExpandedFst<W>>(
     if allow_acyclic_minimization && props.contains(FstProperties::ACYCLIC) {
         // Acyclic minimization
         tr_sort(ifst, ILabelCompare {});
-        let minimizer = AcyclicMinimizer::new(ifst)?;
+        let minimizer = AcyclicMinimizer::new(ifst, progress)?;
         merge_states(minimizer.get_partition(), ifst)?;
     } else {
-        let p = cyclic_minimize(ifst)?;
+        let p = cyclic_minimize(ifst, progress)?;
         merge_states(p, ifst)?;
     }
 
@@ -308,12 +330,12 @@ struct AcyclicMinimizer {
 }
 
 impl AcyclicMinimizer {
-    pub fn new<W: Semiring, F: MutableFst<W>>(fst: &mut F) -> Result<Self> {
+    pub fn new<W: Semiring, F: MutableFst<W>>(fst: &mut F, progress: &Progress) -> Result<Self> {
         let mut c = Self {
             partition: Rc::new(RefCell::new(Partition::empty_new())),
         };
         c.initialize(fst)?;
-        c.refine(fst);
+        c.refine(fst, progress)?;
         Ok(c)
     }
 
@@ -338,7 +360,11 @@ impl AcyclicMinimizer {
         Ok(())
     }
 
-    fn refine<W: Semiring, F: MutableFst<W>>(&mut self, fst: &mut F) {
+    fn refine<W: Semiring, F: MutableFst<W>>(
+        &mut self,
+        fst: &mut F,
+        progress: &Progress,
+    ) -> Result<()> {
         let state_cmp = StateComparator {
             fst,
             partition: Rc::clone(&self.partition),
@@ -347,6 +373,7 @@ impl AcyclicMinimizer {
 
         let height = self.partition.borrow().num_classes();
         for h in 0..height {
+            progress.step(h + 1)?;
             // We need here a binary search tree in order to order the states id and create a partition.
             // For now uses the crate `stable_bst` which is quite old but seems to do the job
             // TODO: Bench the performances of the implementation. Maybe re-write it.
@@ -375,6 +402,7 @@ impl AcyclicMinimizer {
                 }
             }
         }
+        Ok(())
     }
 
     pub fn get_partition(self) -> Rc<RefCell<Partition>> {
@@ -512,7 +540,10 @@ fn pre_partition<W: Semiring, F: MutableFst<W>>(
     }
 }
 
-fn cyclic_minimize<W: Semiring, F: MutableFst<W>>(fst: &mut F) -> Result<Rc<RefCell<Partition>>> {
+fn cyclic_minimize<W: Semiring, F: MutableFst<W>>(
+    fst: &mut F,
+    progress: &Progress,
+) -> Result<Rc<RefCell<Partition>>> {
     // Initialize
     let mut tr: VectorFst<W::ReverseWeight> = reverse(fst)?;
     tr_sort(&mut tr, ILabelCompare {});
@@ -532,7 +563,10 @@ fn cyclic_minimize<W: Semiring, F: MutableFst<W>>(fst: &mut F) -> Result<Rc<RefC
     });
 
     // Compute
+    let mut num_dequeued = 0;
     while let Some(c) = queue.dequeue() {
+        num_dequeued += 1;
+        progress.step(num_dequeued)?;
         // Split
         for s in partition.borrow().iter(c as usize) {
             if tr.num_trs(s as StateId + 1)? > 0 {
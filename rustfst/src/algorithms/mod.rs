@@ -3,27 +3,39 @@ pub use self::{
     all_pairs_shortest_distance::all_pairs_shortest_distance,
     condense::condense,
     connect::connect,
+    disambiguate::{disambiguate, disambiguate_with_config, DisambiguateConfig},
+    equivalent::{equivalent, equivalent_with_config, EquivalentConfig},
     fst_convert::{fst_convert, fst_convert_from_ref},
     inversion::invert,
+    invert_fst::InvertFst,
     isomorphic::{isomorphic, isomorphic_with_config, IsomorphicConfig},
     minimize::{acceptor_minimize, minimize, minimize_with_config, MinimizeConfig},
     optimize::optimize,
+    progress::Progress,
+    project_fst::ProjectFst,
     projection::{project, ProjectType},
+    prune::{prune, prune_with_config, PruneConfig},
     push::{
         push, push_weights, push_weights_with_config, push_with_config, PushConfig, PushType,
         PushWeightsConfig,
     },
     queue::{Queue, QueueType},
+    relabel_fst::RelabelFst,
     relabel_pairs::relabel_pairs,
+    resource_limits::ResourceLimits,
     reverse::reverse,
     reweight::{reweight, ReweightType},
     rm_final_epsilon::rm_final_epsilon,
     shortest_distance::{shortest_distance, shortest_distance_with_config, ShortestDistanceConfig},
     shortest_path::{shortest_path, shortest_path_with_config, ShortestPathConfig},
     state_sort::state_sort,
+    symbol_table_ops::{compact_symbol_table, merge_symbol_table, prune_symbol_table},
+    synchronize::synchronize,
     top_sort::top_sort,
     tr_map::{tr_map, FinalTr, MapFinalAction, TrMapper},
+    tr_map_fst::TrMapFst,
     tr_sort::tr_sort,
+    tr_sort_fst::TrSortFst,
     tr_sum::tr_sum,
     tr_unique::tr_unique,
     weight_convert::{weight_convert, WeightConverter},
@@ -43,25 +55,33 @@ mod connect;
 /// Functions to determinize FSTs.
 pub mod determinize;
 pub(crate) mod dfs_visit;
+mod disambiguate;
 /// Functions to encode FSTs as FSAs and vice versa.
 pub mod encode;
+mod equivalent;
 /// Functions to factor various weight types.
 pub mod factor_weight;
 mod fst_convert;
 mod inversion;
+mod invert_fst;
 mod isomorphic;
 mod minimize;
 mod optimize;
 mod partition;
+mod progress;
+mod project_fst;
 mod projection;
+mod prune;
 mod push;
 mod queue;
 
 /// Functions to randomly generate paths through an Fst. A static and a delayed version are available.
 pub mod randgen;
+mod relabel_fst;
 mod relabel_pairs;
 /// Functions for lazy replacing transitions in an FST.
 pub mod replace;
+mod resource_limits;
 mod reverse;
 mod reweight;
 
@@ -71,9 +91,13 @@ mod rm_final_epsilon;
 mod shortest_distance;
 mod shortest_path;
 mod state_sort;
+mod symbol_table_ops;
+mod synchronize;
 mod top_sort;
 mod tr_map;
+mod tr_map_fst;
 mod tr_sort;
+mod tr_sort_fst;
 mod tr_sum;
 pub(crate) mod tr_unique;
 /// Functions to compute the union of FSTs.
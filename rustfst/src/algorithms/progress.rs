@@ -0,0 +1,154 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::Error;
+
+const DEFAULT_PROGRESS_INTERVAL: usize = 1024;
+
+/// Observes and cancels a long-running algorithm, e.g `determinize_with_config`,
+/// `compose_with_config` or `shortest_distance_with_config`.
+///
+/// The algorithm counts its steps (states expanded, states dequeued, ...) and polls the
+/// `Progress` at each step : it fails with `Error::Cancelled` as soon as `cancel` has been
+/// called and calls the callback with the number of steps done every `interval` steps.
+///
+/// The clones of a `Progress` share their cancellation flag, so that a clone can be kept to
+/// cancel the algorithm from another thread or from the callback. The in-place algorithms leave
+/// their FST in an unspecified state when cancelled.
+///
+/// # Example
+/// ```
+/// # use rustfst::algorithms::{shortest_distance_with_config, Progress, ShortestDistanceConfig};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::semirings::TropicalWeight;
+/// # use rustfst::{Error, Tr};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+///
+/// let progress = Progress::new();
+/// progress.cancel();
+/// let config = ShortestDistanceConfig::default().with_progress(progress);
+/// let err = shortest_distance_with_config(&fst, false, config).unwrap_err();
+/// assert_eq!(Error::find(&err), Some(Error::Cancelled));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Progress {
+    cancelled: Arc<AtomicBool>,
+    callback: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    interval: usize,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `callback` with the number of steps done every `interval` steps.
+    pub fn with_callback<C: Fn(usize) + Send + Sync + 'static>(self, callback: C) -> Self {
+        Self {
+            callback: Some(Arc::new(callback)),
+            ..self
+        }
+    }
+
+    /// Number of steps between two calls to the callback. Must be greater than 0.
+    pub fn with_interval(self, interval: usize) -> Self {
+        Self {
+            interval: interval.max(1),
+            ..self
+        }
+    }
+
+    /// Cancels the algorithms using this `Progress` or one of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Reports that `steps` steps have been done, counting from the start of the algorithm.
+    pub(crate) fn step(&self, steps: usize) -> Result<()> {
+        if self.is_cancelled() {
+            bail!(Error::Cancelled);
+        }
+        if let Some(callback) = &self.callback {
+            if steps.is_multiple_of(self.interval) {
+                callback(steps);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            callback: None,
+            interval: DEFAULT_PROGRESS_INTERVAL,
+        }
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("cancelled", &self.is_cancelled())
+            .field("callback", &self.callback.is_some())
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+/// Two `Progress` are equal if they share their cancellation flag and their callback.
+impl PartialEq for Progress {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+            && self.interval == other.interval
+            && match (&self.callback, &other.callback) {
+                (Some(c1), Some(c2)) => Arc::ptr_eq(c1, c2),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress() -> Result<()> {
+        let reported = Arc::new(Mutex::new(vec![]));
+        let reported_clone = Arc::clone(&reported);
+        let progress = Progress::new()
+            .with_interval(2)
+            .with_callback(move |steps| reported_clone.lock().unwrap().push(steps));
+        for steps in 1..=5 {
+            progress.step(steps)?;
+        }
+        assert_eq!(*reported.lock().unwrap(), vec![2, 4]);
+
+        let progress_clone = progress.clone();
+        assert_eq!(progress, progress_clone);
+        assert_ne!(progress, Progress::new());
+        progress_clone.cancel();
+        let err = progress.step(6).unwrap_err();
+        assert_eq!(Error::find(&err), Some(Error::Cancelled));
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+use std::cmp::Ordering;
+
+use anyhow::Result;
+
+use crate::algorithms::connect;
+use crate::algorithms::shortest_distance::shortest_distance;
+use crate::algorithms::shortest_path::natural_less;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::{Semiring, SemiringProperties};
+use crate::{Error, StateId, Trs, KDELTA};
+
+/// Configuration for pruning.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PruneConfig<W: Semiring> {
+    /// Paths whose weight is worse than the shortest path times this threshold are pruned.
+    pub weight_threshold: W,
+    /// Maximum number of states of the output. The states on the best paths are kept first.
+    pub state_threshold: Option<StateId>,
+    /// Quantization delta used when comparing the weights.
+    pub delta: f32,
+}
+
+impl<W: Semiring> PruneConfig<W> {
+    pub fn new(weight_threshold: W) -> Self {
+        Self {
+            weight_threshold,
+            state_threshold: None,
+            delta: KDELTA,
+        }
+    }
+
+    pub fn with_weight_threshold(self, weight_threshold: W) -> Self {
+        Self {
+            weight_threshold,
+            ..self
+        }
+    }
+
+    pub fn with_state_threshold(self, state_threshold: Option<StateId>) -> Self {
+        Self {
+            state_threshold,
+            ..self
+        }
+    }
+
+    pub fn with_delta(self, delta: f32) -> Self {
+        Self { delta, ..self }
+    }
+}
+
+impl<W: Semiring> Default for PruneConfig<W> {
+    fn default() -> Self {
+        Self::new(W::zero())
+    }
+}
+
+/// Removes the transitions and final weights of `fst` that only belong to successful paths
+/// whose weight is worse than the weight of the shortest path times `weight_threshold`.
+/// The states that are not on a successful path anymore are then removed.
+///
+/// The weight must have the path property.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::prune;
+/// # use rustfst::Tr;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, TropicalWeight::one())?;
+/// fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+/// fst.add_tr(s0, Tr::new(2, 2, 5.0, s1))?;
+///
+/// prune(&mut fst, TropicalWeight::new(2.0))?;
+/// assert_eq!(fst.num_trs(s0)?, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn prune<W, F>(fst: &mut F, weight_threshold: W) -> Result<()>
+where
+    W: Semiring,
+    F: MutableFst<W> + ExpandedFst<W>,
+{
+    prune_with_config(fst, PruneConfig::new(weight_threshold))
+}
+
+/// Same as `prune` but also allows to limit the number of states of the output.
+/// When `state_threshold` is set, only the states with the best path weights going through
+/// them are kept.
+pub fn prune_with_config<W, F>(fst: &mut F, config: PruneConfig<W>) -> Result<()>
+where
+    W: Semiring,
+    F: MutableFst<W> + ExpandedFst<W>,
+{
+    if !W::properties().contains(SemiringProperties::PATH) {
+        bail!(Error::MissingSemiringProperties {
+            operation: "prune",
+            properties: SemiringProperties::PATH,
+        })
+    }
+    let start = match fst.start() {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+    let alpha = shortest_distance(fst, false)?;
+    let beta = shortest_distance(fst, true)?;
+    let distance = |d: &[W], s: StateId| d.get(s as usize).cloned().unwrap_or_else(W::zero);
+
+    let limit = distance(&beta, start).times(&config.weight_threshold)?;
+    let delta = config.delta;
+    let is_pruned =
+        |w: &W| -> Result<bool> { Ok(!w.approx_equal(&limit, delta) && natural_less(&limit, w)?) };
+
+    for s in 0..(fst.num_states() as StateId) {
+        let alpha_s = distance(&alpha, s);
+        let mut to_del = vec![];
+        {
+            let trs = fst.get_trs(s)?;
+            for (idx, tr) in trs.trs().iter().enumerate() {
+                let w = alpha_s
+                    .times(&tr.weight)?
+                    .times(distance(&beta, tr.nextstate))?;
+                if is_pruned(&w)? {
+                    to_del.push(idx);
+                }
+            }
+        }
+        if !to_del.is_empty() {
+            unsafe { fst.del_trs_id_sorted_unchecked(s, &to_del) };
+        }
+        if let Some(final_weight) = fst.final_weight(s)? {
+            if is_pruned(&alpha_s.times(&final_weight)?)? {
+                fst.delete_final_weight(s)?;
+            }
+        }
+    }
+
+    if let Some(state_threshold) = config.state_threshold {
+        // Best path weight going through each state.
+        let mut states = vec![];
+        for s in 0..(fst.num_states() as StateId) {
+            states.push((s, distance(&alpha, s).times(distance(&beta, s))?));
+        }
+        let mut error = None;
+        states.sort_by(|(_, w1), (_, w2)| match natural_less(w1, w2) {
+            Ok(true) => Ordering::Less,
+            Ok(false) => match natural_less(w2, w1) {
+                Ok(true) => Ordering::Greater,
+                Ok(false) => Ordering::Equal,
+                Err(e) => {
+                    error = Some(e);
+                    Ordering::Equal
+                }
+            },
+            Err(e) => {
+                error = Some(e);
+                Ordering::Equal
+            }
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+        let mut kept = vec![false; fst.num_states()];
+        for (s, _) in states.iter().take(state_threshold as usize) {
+            kept[*s as usize] = true;
+        }
+        for s in 0..(fst.num_states() as StateId) {
+            if !kept[s as usize] {
+                fst.delete_trs(s)?;
+                fst.delete_final_weight(s)?;
+                continue;
+            }
+            let to_del: Vec<_> = fst
+                .get_trs(s)?
+                .trs()
+                .iter()
+                .enumerate()
+                .filter(|(_, tr)| !kept[tr.nextstate as usize])
+                .map(|(idx, _)| idx)
+                .collect();
+            if !to_del.is_empty() {
+                unsafe { fst.del_trs_id_sorted_unchecked(s, &to_del) };
+            }
+        }
+    }
+
+    connect(fst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::Tr;
+
+    fn build_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 3))?;
+        fst.add_tr(0, Tr::new(2, 2, 1.0, 1))?;
+        fst.add_tr(1, Tr::new(3, 3, 1.0, 3))?;
+        fst.add_tr(0, Tr::new(4, 4, 1.0, 2))?;
+        fst.add_tr(2, Tr::new(5, 5, 5.0, 3))?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_prune_weight_threshold() -> Result<()> {
+        let mut fst = build_fst()?;
+        prune(&mut fst, TropicalWeight::new(1.5))?;
+        assert_eq!(fst.num_states(), 3);
+        assert_eq!(fst.num_trs(0)?, 2);
+
+        let mut fst = build_fst()?;
+        prune(&mut fst, TropicalWeight::new(0.0))?;
+        assert_eq!(fst.num_states(), 2);
+        assert_eq!(fst.num_trs(0)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_state_threshold() -> Result<()> {
+        let mut fst = build_fst()?;
+        let config = PruneConfig::new(TropicalWeight::zero()).with_state_threshold(Some(3));
+        prune_with_config(&mut fst, config)?;
+        assert_eq!(fst.num_states(), 3);
+        assert_eq!(fst.num_trs(0)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_requires_path_property() {
+        let mut fst = VectorFst::<LogWeight>::new();
+        assert!(prune(&mut fst, LogWeight::one()).is_err());
+    }
+}
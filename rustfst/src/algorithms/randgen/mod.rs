@@ -5,7 +5,9 @@ use crate::fst_impls::VectorFst;
 pub use randgen_config::RandGenConfig;
 pub use randgen_fst::RandGenFst;
 use tr_sampler::TrSampler;
-pub use tr_selector::{TrSelector, UniformTrSelector};
+pub use tr_selector::{
+    FastLogProbTrSelector, LogProbTrSelector, RandGenSelector, TrSelector, UniformTrSelector,
+};
 
 use crate::fst_traits::Fst;
 use crate::prelude::dfs_visit::dfs_visit;
@@ -27,7 +29,7 @@ pub fn randgen_with_config<
     W: Semiring<Type = f32>,
     FI: Fst<W>,
     FO: MutableFst<W>,
-    S: TrSelector,
+    S: TrSelector<W>,
 >(
     ifst: &FI,
     config: RandGenConfig<S>,
@@ -1,8 +1,6 @@
-use crate::algorithms::randgen::TrSelector;
-
 /// Configuration struct for random path generation.
-pub struct RandGenConfig<S: TrSelector> {
-    /// How an arc is selected at a state.
+pub struct RandGenConfig<S> {
+    /// How an arc is selected at a state. Use `RandGenSelector` to choose it at runtime.
     pub selector: S,
     /// Maximum path length.
     pub max_length: usize,
@@ -14,7 +12,7 @@ pub struct RandGenConfig<S: TrSelector> {
     pub remove_total_weight: bool,
 }
 
-impl<S: TrSelector> RandGenConfig<S> {
+impl<S> RandGenConfig<S> {
     pub fn new(selector: S) -> Self {
         Self {
             selector,
@@ -15,7 +15,7 @@ use crate::{Semiring, StateId, SymbolTable, TrsVec};
 type InnerLazyFst<W, F, B, S> = LazyFst2<W, RandGenFstOp<W, F, B, S>, SimpleHashMapCache<W>>;
 
 /// Delayed Fst sampling Fst paths through the input Fst.
-pub struct RandGenFst<W: Semiring<Type = f32>, F: Fst<W>, B: Borrow<F>, S: TrSelector>(
+pub struct RandGenFst<W: Semiring<Type = f32>, F: Fst<W>, B: Borrow<F>, S: TrSelector<W>>(
     InnerLazyFst<W, F, B, S>,
 );
 
@@ -24,7 +24,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     type TRS = TrsVec<W>;
 
@@ -74,7 +74,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W> + 'a,
     B: Borrow<F> + 'a,
-    S: TrSelector + 'a,
+    S: TrSelector<W> + 'a,
 {
     type Iter = <InnerLazyFst<W, F, B, S> as StateIterator<'a>>::Iter;
 
@@ -88,7 +88,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W> + 'a,
     B: Borrow<F> + 'a,
-    S: TrSelector + 'a,
+    S: TrSelector<W> + 'a,
 {
     type FstIter = <InnerLazyFst<W, F, B, S> as FstIterator<'a, W>>::FstIter;
 
@@ -102,7 +102,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W> + 'static,
     B: Borrow<F> + 'static,
-    S: TrSelector + 'static,
+    S: TrSelector<W> + 'static,
 {
     fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
         self.0.input_symbols()
@@ -134,7 +134,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W> + 'static,
     B: Borrow<F> + 'static,
-    S: TrSelector + 'static,
+    S: TrSelector<W> + 'static,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
@@ -145,7 +145,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     pub fn new(
         fst: B,
@@ -20,7 +20,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     fst: B,
     sampler: RefCell<TrSampler<W, F, B, S>>,
@@ -36,7 +36,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     pub fn new(
         fst: B,
@@ -62,7 +62,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -84,7 +84,7 @@ where
     W: Semiring<Type = f32>,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     fn compute_start(&self) -> Result<Option<StateId>> {
         if let Some(s) = self.fst.borrow().start() {
@@ -14,7 +14,7 @@ use crate::Semiring;
 /// transitions from an FST's state. This is a generic version with a
 /// straightforward use of the tr selector. Specializations may be defined for
 /// tr selectors for greater efficiency or special behavior.
-pub struct TrSampler<W: Semiring, F: Fst<W>, B: Borrow<F>, S: TrSelector> {
+pub struct TrSampler<W: Semiring, F: Fst<W>, B: Borrow<F>, S: TrSelector<W>> {
     max_length: usize,
     selector: S,
     fst: B,
@@ -27,7 +27,7 @@ where
     W: Semiring,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -46,7 +46,7 @@ where
     W: Semiring,
     F: Fst<W>,
     B: Borrow<F>,
-    S: TrSelector,
+    S: TrSelector<W>,
 {
     pub fn new(fst: B, selector: S, max_length: usize) -> Self {
         Self {
@@ -1,9 +1,12 @@
 use crate::prelude::Fst;
-use crate::{Semiring, StateId};
+use crate::{Semiring, StateId, Trs};
 use anyhow::Result;
 use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
 use rand_chacha::rand_core::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 /// `TrSelector` implementors are used to select a random transition given an Fst
@@ -11,8 +14,8 @@ use std::fmt::Debug;
 /// `fst.num_trs(s)`, then the final weight is selected; otherwise the `N`-th transition is
 /// selected. It is assumed these are not applied to any state which is neither
 /// final nor has any arcs leaving it.
-pub trait TrSelector: Debug {
-    fn select_tr<W: Semiring, F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize>;
+pub trait TrSelector<W: Semiring>: Debug {
+    fn select_tr<F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize>;
 }
 
 /// Randomly selects a transition using the uniform distribution.
@@ -40,8 +43,8 @@ impl UniformTrSelector {
     }
 }
 
-impl TrSelector for UniformTrSelector {
-    fn select_tr<W: Semiring, F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize> {
+impl<W: Semiring> TrSelector<W> for UniformTrSelector {
+    fn select_tr<F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize> {
         let mut n = fst.num_trs(state)?;
         if fst.is_final(state)? {
             n += 1;
@@ -51,3 +54,248 @@ impl TrSelector for UniformTrSelector {
         Ok(res)
     }
 }
+
+/// Probabilities, up to a constant factor, of the transitions leaving `state` followed by its
+/// final weight if it is final. The weights are treated as negative log probabilities.
+fn state_probabilities<W: Semiring<Type = f32>, F: Fst<W>>(
+    fst: &F,
+    state: StateId,
+) -> Result<Vec<f64>> {
+    let mut neg_log_probs: Vec<f64> = fst
+        .get_trs(state)?
+        .trs()
+        .iter()
+        .map(|tr| *tr.weight.value() as f64)
+        .collect();
+    if let Some(final_weight) = fst.final_weight(state)? {
+        neg_log_probs.push(*final_weight.value() as f64);
+    }
+    // Shifted by the smallest weight to avoid underflows.
+    let min = neg_log_probs.iter().cloned().fold(f64::INFINITY, f64::min);
+    if !min.is_finite() {
+        bail!(
+            "Can't select a transition of state {} : no transition or final weight has a finite weight",
+            state
+        );
+    }
+    Ok(neg_log_probs.iter().map(|w| (min - w).exp()).collect())
+}
+
+/// Randomly selects a transition with a probability proportional to exp(-weight), the weights
+/// being treated as negative log probabilities normalized by the total weight leaving the
+/// state. The final weight is selected the same way.
+#[derive(Debug, Clone)]
+pub struct LogProbTrSelector {
+    rng: ChaCha8Rng,
+}
+
+impl Default for LogProbTrSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogProbTrSelector {
+    pub fn new() -> Self {
+        Self {
+            rng: ChaCha8Rng::from_entropy(),
+        }
+    }
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl<W: Semiring<Type = f32>> TrSelector<W> for LogProbTrSelector {
+    fn select_tr<F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize> {
+        let probs = state_probabilities(fst, state)?;
+        let total: f64 = probs.iter().sum();
+        let r = self.rng.gen::<f64>() * total;
+        let mut cumulative = 0.0;
+        for (idx, p) in probs.iter().enumerate() {
+            cumulative += p;
+            if r < cumulative {
+                return Ok(idx);
+            }
+        }
+        // Rounding errors : selects the last transition that can be selected.
+        Ok(probs.iter().rposition(|p| *p > 0.0).unwrap())
+    }
+}
+
+/// Same distribution as `LogProbTrSelector`, but the cumulative distribution of each state is
+/// computed once and cached, and the transition is found by binary search. A selector must
+/// therefore only be used with a single FST.
+#[derive(Debug, Clone)]
+pub struct FastLogProbTrSelector {
+    rng: ChaCha8Rng,
+    cdfs: HashMap<StateId, Vec<f64>>,
+}
+
+impl Default for FastLogProbTrSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FastLogProbTrSelector {
+    pub fn new() -> Self {
+        Self {
+            rng: ChaCha8Rng::from_entropy(),
+            cdfs: HashMap::new(),
+        }
+    }
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            cdfs: HashMap::new(),
+        }
+    }
+}
+
+impl<W: Semiring<Type = f32>> TrSelector<W> for FastLogProbTrSelector {
+    fn select_tr<F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize> {
+        if let Entry::Vacant(entry) = self.cdfs.entry(state) {
+            let probs = state_probabilities(fst, state)?;
+            let total: f64 = probs.iter().sum();
+            let cdf = probs
+                .iter()
+                .scan(0.0, |cumulative, p| {
+                    *cumulative += p;
+                    Some(*cumulative / total)
+                })
+                .collect();
+            entry.insert(cdf);
+        }
+        let cdf = &self.cdfs[&state];
+        let r = self.rng.gen::<f64>();
+        // The transitions with a probability of zero have the same cumulative probability as
+        // the previous one and can't be the first one greater than `r`.
+        let idx = cdf.partition_point(|c| *c <= r);
+        Ok(idx.min(cdf.len() - 1))
+    }
+}
+
+/// `TrSelector` chosen at runtime, e.g from a command line option. The log-prob selectors
+/// treat the weights as negative log probabilities and require `f32` weights.
+#[derive(Debug, Clone)]
+pub enum RandGenSelector {
+    Uniform(UniformTrSelector),
+    LogProb(LogProbTrSelector),
+    FastLogProb(FastLogProbTrSelector),
+}
+
+impl RandGenSelector {
+    /// Creates the selector named like in OpenFST : `uniform`, `log_prob` or `fast_log_prob`.
+    /// The selector is seeded with `seed` if any, from the entropy of the system otherwise.
+    pub fn from_name(name: &str, seed: Option<u64>) -> Result<Self> {
+        let selector = match (name, seed) {
+            ("uniform", Some(seed)) => Self::Uniform(UniformTrSelector::from_seed(seed)),
+            ("uniform", None) => Self::Uniform(UniformTrSelector::new()),
+            ("log_prob", Some(seed)) => Self::LogProb(LogProbTrSelector::from_seed(seed)),
+            ("log_prob", None) => Self::LogProb(LogProbTrSelector::new()),
+            ("fast_log_prob", Some(seed)) => {
+                Self::FastLogProb(FastLogProbTrSelector::from_seed(seed))
+            }
+            ("fast_log_prob", None) => Self::FastLogProb(FastLogProbTrSelector::new()),
+            _ => bail!("Unknown selector : {}", name),
+        };
+        Ok(selector)
+    }
+}
+
+impl<W: Semiring<Type = f32>> TrSelector<W> for RandGenSelector {
+    fn select_tr<F: Fst<W>>(&mut self, fst: &F, state: StateId) -> Result<usize> {
+        match self {
+            Self::Uniform(selector) => selector.select_tr(fst, state),
+            Self::LogProb(selector) => selector.select_tr(fst, state),
+            Self::FastLogProb(selector) => selector.select_tr(fst, state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{BooleanWeight, TropicalWeight};
+    use crate::Tr;
+
+    // State 0 has transitions with probabilities 0.6 and 0, and a final probability of 0.2.
+    // The last transition has a probability of 0.2.
+    fn fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 1, -(0.6f32.ln()), s1))?;
+        fst.add_tr(s0, Tr::new(2, 2, TropicalWeight::zero(), s1))?;
+        fst.add_tr(s0, Tr::new(3, 3, -(0.2f32.ln()), s1))?;
+        fst.set_final(s0, -(0.2f32.ln()))?;
+        Ok(fst)
+    }
+
+    fn sample_counts<S: TrSelector<TropicalWeight>>(selector: &mut S) -> Result<Vec<usize>> {
+        let fst = fst()?;
+        let mut counts = vec![0; 4];
+        for _ in 0..10000 {
+            counts[selector.select_tr(&fst, 0)?] += 1;
+        }
+        Ok(counts)
+    }
+
+    fn check_counts(counts: &[usize]) {
+        assert!(counts[0] > 5700 && counts[0] < 6300, "{:?}", counts);
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > 1700 && counts[2] < 2300, "{:?}", counts);
+        assert!(counts[3] > 1700 && counts[3] < 2300, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_log_prob_tr_selector() -> Result<()> {
+        check_counts(&sample_counts(&mut LogProbTrSelector::from_seed(42))?);
+        assert_eq!(
+            sample_counts(&mut LogProbTrSelector::from_seed(42))?,
+            sample_counts(&mut LogProbTrSelector::from_seed(42))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fast_log_prob_tr_selector() -> Result<()> {
+        check_counts(&sample_counts(&mut FastLogProbTrSelector::from_seed(42))?);
+        check_counts(&sample_counts(&mut RandGenSelector::from_name(
+            "fast_log_prob",
+            Some(7),
+        )?)?);
+        assert!(RandGenSelector::from_name("unknown", None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_prob_tr_selector_no_finite_weight() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.add_tr(s0, Tr::new(1, 1, TropicalWeight::zero(), s1))?;
+        assert!(LogProbTrSelector::from_seed(0).select_tr(&fst, s0).is_err());
+        assert!(FastLogProbTrSelector::from_seed(0)
+            .select_tr(&fst, s0)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_uniform_tr_selector_any_semiring() -> Result<()> {
+        let mut fst = VectorFst::<BooleanWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.add_tr(s0, Tr::new(1, 1, BooleanWeight::one(), s1))?;
+        fst.add_tr(s0, Tr::new(2, 2, BooleanWeight::one(), s1))?;
+        assert!(UniformTrSelector::from_seed(0).select_tr(&fst, s0)? < 2);
+        Ok(())
+    }
+}
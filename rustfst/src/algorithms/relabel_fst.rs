@@ -0,0 +1,217 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::tr_mappers::RelabelMapper;
+use crate::algorithms::TrMapFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::Label;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerFst<W, F, B> = TrMapFst<W, F, B, RelabelMapper>;
+
+/// Delayed version of `relabel_pairs`: the labels of each transition are replaced according
+/// to the relabeling pairs when the state is visited. The labels not present in the pairs are
+/// kept.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::RelabelFst;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::transducer;
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<TropicalWeight> = fst![1, 2 => 3, 4];
+/// let relabeled_fst = RelabelFst::<_, VectorFst<_>, _>::new(&fst, vec![(1, 5)], vec![(4, 6)]);
+/// let relabeled: VectorFst<_> = relabeled_fst?.compute()?;
+/// assert_eq!(relabeled, fst![5, 2 => 3, 6]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct RelabelFst<W: Semiring, F: Fst<W>, B: Borrow<F>>(InnerFst<W, F, B>);
+
+impl<W, F, B> CoreFst<W> for RelabelFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B> StateIterator<'a> for RelabelFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type Iter = <InnerFst<W, F, B> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B> FstIterator<'a, W> for RelabelFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    type FstIter = <InnerFst<W, F, B> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B> Fst<W> for RelabelFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B> Debug for RelabelFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B> RelabelFst<W, F, B>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    /// Fails if a label is present twice in the input or in the output pairs.
+    pub fn new<I, J>(fst: B, ipairs: I, opairs: J) -> Result<Self>
+    where
+        I: IntoIterator<Item = (Label, Label)>,
+        J: IntoIterator<Item = (Label, Label)>,
+    {
+        Ok(RelabelFst(TrMapFst::new(
+            fst,
+            RelabelMapper::new(ipairs, opairs)?,
+        )?))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithms::relabel_pairs;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    #[test]
+    fn test_relabel_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<RelabelFst<TropicalWeight, VectorFst<_>, VectorFst<_>>>();
+    }
+
+    #[test]
+    fn test_relabel_fst() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 1.0, s1))?;
+        fst.add_tr(s1, Tr::new(2, 1, 2.0, s0))?;
+        fst.set_final(s1, 0.5)?;
+        let ipairs = vec![(1, 3), (2, 4)];
+        let opairs = vec![(1, 5)];
+
+        let lazy: VectorFst<_> =
+            RelabelFst::<_, VectorFst<_>, _>::new(&fst, ipairs.clone(), opairs.clone())?
+                .compute()?;
+        let mut expected = fst.clone();
+        relabel_pairs(&mut expected, ipairs, opairs)?;
+        assert_eq!(lazy, expected);
+
+        let duplicated = RelabelFst::<_, VectorFst<_>, _>::new(&fst, vec![(1, 3), (1, 4)], vec![]);
+        assert!(duplicated.is_err());
+        Ok(())
+    }
+}
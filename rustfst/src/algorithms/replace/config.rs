@@ -1,4 +1,7 @@
-use crate::Label;
+use std::collections::HashMap;
+
+use crate::algorithms::ResourceLimits;
+use crate::{Label, EPS_LABEL};
 
 /// This specifies what labels to output on the call or return transition.
 #[derive(PartialOrd, PartialEq, Copy, Clone, Debug, Eq)]
@@ -9,12 +12,25 @@ pub enum ReplaceLabelType {
     Input,
     /// Epsilon on input and non-epsilon on output.
     Output,
-    #[allow(unused)]
     /// Non-epsilon labels on both input and output.
     Both,
 }
 
-#[derive(PartialOrd, PartialEq, Clone, Debug, Eq)]
+/// Options of `ReplaceFst` and `replace_with_options`, like OpenFST's `ReplaceFstOptions`.
+///
+/// The call transition to a rule keeps the input label of the transition labeled with the
+/// non-terminal and gets as output label `call_output_label`, or the non-terminal if `None`.
+/// The return transition from a rule gets `return_label` on both sides. `call_label_type` and
+/// `return_label_type` tell on which sides these labels are kept, the others being epsilon.
+///
+/// The call output label and the return label can be overridden for each rule, identified by
+/// its non-terminal, e.g to output `</NP>` when the rule `NP` returns. A label equal to
+/// `EPS_LABEL` makes the call or the return transition an epsilon transition whatever its
+/// label type.
+///
+/// The `limits` bound the size of the FST built by `replace_with_options`, they are not used by
+/// the lazy `ReplaceFst`.
+#[derive(PartialEq, Clone, Debug, Eq)]
 pub struct ReplaceFstOptions {
     /// Index of root rule for expansion.
     pub root: Label,
@@ -27,6 +43,12 @@ pub struct ReplaceFstOptions {
     pub call_output_label: Option<Label>,
     /// Specifies label to put on return transition.
     pub return_label: Label,
+    /// Output label of the call transitions to the given rules, overriding `call_output_label`.
+    pub rule_call_output_labels: HashMap<Label, Label>,
+    /// Label of the return transitions from the given rules, overriding `return_label`.
+    pub rule_return_labels: HashMap<Label, Label>,
+    /// Bounds on the size of the output of `replace_with_options`.
+    pub limits: ResourceLimits,
 }
 
 impl ReplaceFstOptions {
@@ -41,6 +63,97 @@ impl ReplaceFstOptions {
             return_label_type: ReplaceLabelType::Neither,
             call_output_label: if epsilon_on_replace { Some(0) } else { None },
             return_label: 0,
+            rule_call_output_labels: HashMap::new(),
+            rule_return_labels: HashMap::new(),
+            limits: ResourceLimits::default(),
+        }
+    }
+
+    pub fn with_root(self, root: Label) -> Self {
+        Self { root, ..self }
+    }
+
+    pub fn with_call_label_type(self, call_label_type: ReplaceLabelType) -> Self {
+        Self {
+            call_label_type,
+            ..self
+        }
+    }
+
+    pub fn with_return_label_type(self, return_label_type: ReplaceLabelType) -> Self {
+        Self {
+            return_label_type,
+            ..self
+        }
+    }
+
+    pub fn with_call_output_label(self, call_output_label: Option<Label>) -> Self {
+        Self {
+            call_output_label,
+            ..self
         }
     }
+
+    pub fn with_return_label(self, return_label: Label) -> Self {
+        Self {
+            return_label,
+            ..self
+        }
+    }
+
+    /// Sets the output label of the call transitions to the rule `nonterminal`.
+    pub fn with_rule_call_output_label(mut self, nonterminal: Label, label: Label) -> Self {
+        self.rule_call_output_labels.insert(nonterminal, label);
+        self
+    }
+
+    /// Sets the label of the return transitions from the rule `nonterminal`.
+    pub fn with_rule_return_label(mut self, nonterminal: Label, label: Label) -> Self {
+        self.rule_return_labels.insert(nonterminal, label);
+        self
+    }
+
+    pub fn with_limits(self, limits: ResourceLimits) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// Output label of the call transitions to the rule `nonterminal`, if not the
+    /// non-terminal itself.
+    pub(crate) fn call_output_label(&self, nonterminal: Label) -> Option<Label> {
+        self.rule_call_output_labels
+            .get(&nonterminal)
+            .cloned()
+            .or(self.call_output_label)
+    }
+
+    /// Label of the return transitions from the rule `nonterminal`.
+    pub(crate) fn return_label(&self, nonterminal: Label) -> Label {
+        self.rule_return_labels
+            .get(&nonterminal)
+            .cloned()
+            .unwrap_or(self.return_label)
+    }
+
+    /// Label types of the call and return transitions used to compute the properties. As in
+    /// OpenFST, a type is `Neither` if all the labels of the transitions are epsilon.
+    pub(crate) fn effective_label_types(&self) -> (ReplaceLabelType, ReplaceLabelType) {
+        let call_label_type = if self.call_output_label == Some(EPS_LABEL)
+            && self
+                .rule_call_output_labels
+                .values()
+                .all(|l| *l == EPS_LABEL)
+        {
+            ReplaceLabelType::Neither
+        } else {
+            self.call_label_type
+        };
+        let return_label_type = if self.return_label == EPS_LABEL
+            && self.rule_return_labels.values().all(|l| *l == EPS_LABEL)
+        {
+            ReplaceLabelType::Neither
+        } else {
+            self.return_label_type
+        };
+        (call_label_type, return_label_type)
+    }
 }
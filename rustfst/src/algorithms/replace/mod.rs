@@ -1,9 +1,12 @@
-pub(crate) mod config;
+mod config;
 mod replace_fst;
 pub(crate) mod replace_fst_op;
 mod replace_static;
+mod replace_util;
 pub(crate) mod state_table;
 pub(crate) mod utils;
 
+pub use config::{ReplaceFstOptions, ReplaceLabelType};
 pub use replace_fst::ReplaceFst;
-pub use replace_static::replace;
+pub use replace_static::{replace, replace_with_options};
+pub use replace_util::{ReplaceStats, ReplaceUtil};
@@ -7,6 +7,7 @@ use anyhow::Result;
 use crate::algorithms::lazy::{LazyFst, SimpleHashMapCache};
 use crate::algorithms::replace::config::ReplaceFstOptions;
 use crate::algorithms::replace::replace_fst_op::ReplaceFstOp;
+use crate::algorithms::{Progress, ResourceLimits};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::semirings::Semiring;
@@ -26,13 +27,18 @@ where
     B: Borrow<F>,
 {
     pub fn new(fst_list: Vec<(Label, B)>, root: Label, epsilon_on_replace: bool) -> Result<Self> {
+        Self::new_with_options(fst_list, ReplaceFstOptions::new(root, epsilon_on_replace))
+    }
+
+    /// Creates a `ReplaceFst` with the labels of the call and return transitions given by
+    /// `opts`, e.g rule-specific return labels marking the end of each rule in the output.
+    pub fn new_with_options(fst_list: Vec<(Label, B)>, opts: ReplaceFstOptions) -> Result<Self> {
         let mut isymt = None;
         let mut osymt = None;
         if let Some(first_elt) = fst_list.first() {
             isymt = first_elt.1.borrow().input_symbols().cloned();
             osymt = first_elt.1.borrow().output_symbols().cloned();
         }
-        let opts = ReplaceFstOptions::new(root, epsilon_on_replace);
         let fst_op = ReplaceFstOp::new(fst_list, opts)?;
         let fst_cache = SimpleHashMapCache::default();
         Ok(ReplaceFst(LazyFst::from_op_and_cache(
@@ -44,6 +50,16 @@ where
     pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
         self.0.compute()
     }
+
+    /// Turns the Lazy FST into a static one, polling `progress` after each state expanded and
+    /// failing as soon as the output exceeds `limits`.
+    pub fn compute_with_limits<F2: MutableFst<W> + AllocableFst<W>>(
+        &self,
+        progress: &Progress,
+        limits: &ResourceLimits,
+    ) -> Result<F2> {
+        self.0.compute_with_limits(progress, limits)
+    }
 }
 
 impl<W, F, B> CoreFst<W> for ReplaceFst<W, F, B>
@@ -19,11 +19,10 @@ use crate::semirings::Semiring;
 use crate::{Label, StateId, Tr, Trs, TrsVec, EPS_LABEL};
 
 pub struct ReplaceFstOp<W: Semiring, F: Fst<W>, B: Borrow<F>> {
-    call_label_type_: ReplaceLabelType,
-    return_label_type_: ReplaceLabelType,
-    call_output_label_: Option<Label>,
-    return_label_: Label,
+    opts: ReplaceFstOptions,
     fst_array: Vec<B>,
+    /// Non-terminal of each FST of `fst_array`.
+    fst_labels: Vec<Label>,
     nonterminal_set: BTreeSet<Label>,
     nonterminal_hash: HashMap<Label, Label>,
     root: Label,
@@ -39,14 +38,9 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> std::fmt::Debug for ReplaceFstOp<W, F
         let slice_fst = self.fst_array.iter().map(|fst| fst.borrow()).collect_vec();
         write!(
             f,
-            "ReplaceFstImpl {{ call_label_type_ : {:?}, \
-             return_label_type_ : {:?}, call_output_label_ : {:?}, return_label_ : {:?}, \
-             fst_array : {:?}, nonterminal_set : {:?}, nonterminal_hash : {:?}, root : {:?}, \
-             state_table : {:?} }}",
-            self.call_label_type_,
-            self.return_label_type_,
-            self.call_output_label_,
-            self.return_label_,
+            "ReplaceFstImpl {{ opts : {:?}, fst_array : {:?}, nonterminal_set : {:?}, \
+             nonterminal_hash : {:?}, root : {:?}, state_table : {:?} }}",
+            self.opts,
             slice_fst,
             self.nonterminal_set,
             self.nonterminal_hash,
@@ -200,21 +194,31 @@ fn replace_transducer(
 impl<W: Semiring, F: Fst<W>, B: Borrow<F>> ReplaceFstOp<W, F, B> {
     pub fn new(fst_list: Vec<(Label, B)>, opts: ReplaceFstOptions) -> Result<Self> {
         let mut all_non_empty_and_sorted = false;
-        let properties = replace_properties(
-            opts.root,
-            fst_list.as_slice(),
-            opts.call_label_type,
-            opts.return_label_type,
-            opts.call_output_label,
-            &mut all_non_empty_and_sorted,
-        );
+        let (call_label_type, return_label_type) = opts.effective_label_types();
+        // The properties are computed for a single call output label : when some rules get a
+        // different one, they are left unknown.
+        let properties = if opts
+            .rule_call_output_labels
+            .values()
+            .all(|l| Some(*l) == opts.call_output_label)
+        {
+            replace_properties(
+                opts.root,
+                fst_list.as_slice(),
+                call_label_type,
+                return_label_type,
+                opts.call_output_label,
+                &mut all_non_empty_and_sorted,
+            )
+        } else {
+            FstProperties::empty()
+        };
 
+        let root = opts.root;
         let mut replace_fst_impl = Self {
-            call_label_type_: opts.call_label_type,
-            return_label_type_: opts.return_label_type,
-            call_output_label_: opts.call_output_label,
-            return_label_: opts.return_label,
+            opts,
             fst_array: Vec::with_capacity(fst_list.len()),
+            fst_labels: Vec::with_capacity(fst_list.len()),
             nonterminal_set: BTreeSet::new(),
             nonterminal_hash: HashMap::new(),
             root: 0,
@@ -224,28 +228,19 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> ReplaceFstOp<W, F, B> {
             w: PhantomData,
         };
 
-        if let Some(v) = replace_fst_impl.call_output_label_ {
-            if v == EPS_LABEL {
-                replace_fst_impl.call_label_type_ = ReplaceLabelType::Neither;
-            }
-        }
-
-        if replace_fst_impl.return_label_ == 0 {
-            replace_fst_impl.return_label_type_ = ReplaceLabelType::Neither;
-        }
-
         for (label, fst) in fst_list.into_iter() {
             replace_fst_impl
                 .nonterminal_hash
                 .insert(label, replace_fst_impl.fst_array.len() as StateId);
             replace_fst_impl.nonterminal_set.insert(label);
             replace_fst_impl.fst_array.push(fst);
+            replace_fst_impl.fst_labels.push(label);
         }
 
-        match replace_fst_impl.nonterminal_hash.entry(opts.root) {
+        match replace_fst_impl.nonterminal_hash.entry(root) {
             Entry::Vacant(_) => bail!(
                 "ReplaceFstImpl: No FST corresponding to root label {} in the input tuple vector",
-                opts.root
+                root
             ),
             Entry::Occupied(e) => {
                 replace_fst_impl.root = *e.get();
@@ -267,15 +262,23 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> ReplaceFstOp<W, F, B> {
             .unwrap()
             && tuple.prefix_id > 0
         {
-            let ilabel = if epsilon_on_input(self.return_label_type_) {
+            let return_label = self
+                .opts
+                .return_label(self.fst_labels[tuple.fst_id.unwrap() as usize]);
+            let return_label_type = if return_label == EPS_LABEL {
+                ReplaceLabelType::Neither
+            } else {
+                self.opts.return_label_type
+            };
+            let ilabel = if epsilon_on_input(return_label_type) {
                 EPS_LABEL
             } else {
-                self.return_label_
+                return_label
             };
-            let olabel = if epsilon_on_output(self.return_label_type_) {
+            let olabel = if epsilon_on_output(return_label_type) {
                 0
             } else {
-                self.return_label_
+                return_label
             };
             let stack = self.state_table.prefix_table.find_tuple(tuple.prefix_id);
             let top = stack.top();
@@ -344,15 +347,21 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> ReplaceFstOp<W, F, B> {
                     let nt_nextstate = self.state_table.tuple_table.find_id(
                         ReplaceStateTuple::new(nt_prefix, Some(*nonterminal), Some(nt_start)),
                     );
-                    let ilabel = if epsilon_on_input(self.call_label_type_) {
+                    let call_output_label = self.opts.call_output_label(tr.olabel);
+                    let call_label_type = if call_output_label == Some(EPS_LABEL) {
+                        ReplaceLabelType::Neither
+                    } else {
+                        self.opts.call_label_type
+                    };
+                    let ilabel = if epsilon_on_input(call_label_type) {
                         0
                     } else {
                         tr.ilabel
                     };
-                    let olabel = if epsilon_on_output(self.call_label_type_) {
+                    let olabel = if epsilon_on_output(call_label_type) {
                         0
                     } else {
-                        self.call_output_label_.unwrap_or(tr.olabel)
+                        call_output_label.unwrap_or(tr.olabel)
                     };
                     Some(Tr::new(ilabel, olabel, tr.weight.clone(), nt_nextstate))
                 } else {
@@ -2,7 +2,9 @@ use std::borrow::Borrow;
 
 use anyhow::Result;
 
-use crate::algorithms::replace::ReplaceFst;
+use crate::algorithms::replace::replace_util::check_acyclic_dependencies;
+use crate::algorithms::replace::{ReplaceFst, ReplaceFstOptions};
+use crate::algorithms::Progress;
 use crate::fst_traits::{AllocableFst, Fst, MutableFst};
 use crate::semirings::Semiring;
 use crate::Label;
@@ -42,6 +44,12 @@ use crate::Label;
 ///
 /// ![replace_out](https://raw.githubusercontent.com/Garvys/rustfst-images-doc/master/images/replace_out.svg?sanitize=true)
 ///
+/// # Errors
+///
+/// Fails if a rule reachable from the root refers to itself, directly or through other rules,
+/// as the expansion would be infinite. Such grammars can be expanded lazily with `ReplaceFst`.
+/// `ReplaceUtil` can be used to inspect and simplify the rules before the expansion.
+///
 pub fn replace<W, F1, F2, B>(
     fst_list: Vec<(Label, B)>,
     root: Label,
@@ -53,6 +61,144 @@ where
     F2: MutableFst<W> + AllocableFst<W>,
     B: Borrow<F1>,
 {
-    let fst = ReplaceFst::new(fst_list, root, epsilon_on_replace)?;
-    fst.compute()
+    replace_with_options::<W, F1, F2, B>(fst_list, ReplaceFstOptions::new(root, epsilon_on_replace))
+}
+
+/// Recursively replaces trs in the root FSTs with other FSTs, with the labels of the call and
+/// return transitions configured by `opts`. See `replace` and `ReplaceFstOptions`.
+///
+/// Fails with `Error::ResourceLimitExceeded` if the output exceeds `opts.limits`.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::replace::{replace_with_options, ReplaceFstOptions, ReplaceLabelType};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::transducer;
+/// # fn main() -> Result<()> {
+/// // The rule 10 is called with the label 10 and returns with the label 20.
+/// let root: VectorFst<TropicalWeight> = fst![1, 10 => 1, 10];
+/// let rule: VectorFst<TropicalWeight> = fst![2 => 2];
+/// let opts = ReplaceFstOptions::new(0, false)
+///     .with_call_label_type(ReplaceLabelType::Both)
+///     .with_return_label_type(ReplaceLabelType::Both)
+///     .with_rule_return_label(10, 20);
+/// let replaced: VectorFst<TropicalWeight> = replace_with_options(vec![(0, root), (10, rule)], opts)?;
+/// assert_eq!(replaced, fst![1, 10, 2, 20 => 1, 10, 2, 20]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn replace_with_options<W, F1, F2, B>(
+    fst_list: Vec<(Label, B)>,
+    opts: ReplaceFstOptions,
+) -> Result<F2>
+where
+    F1: Fst<W>,
+    W: Semiring,
+    F2: MutableFst<W> + AllocableFst<W>,
+    B: Borrow<F1>,
+{
+    check_acyclic_dependencies::<W, F1, B>(&fst_list, opts.root)?;
+    let limits = opts.limits;
+    let fst = ReplaceFst::new_with_options(fst_list, opts)?;
+    fst.compute_with_limits(&Progress::default(), &limits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::replace::ReplaceLabelType;
+    use crate::algorithms::ResourceLimits;
+    use crate::fst;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::transducer;
+    use crate::Error;
+
+    fn grammar() -> Vec<(Label, VectorFst<TropicalWeight>)> {
+        vec![
+            (0, fst![1, 10, 11 => 1, 10, 11]),
+            (10, fst![2 => 2]),
+            (11, fst![3 => 3]),
+        ]
+    }
+
+    fn replace_grammar(opts: ReplaceFstOptions) -> Result<VectorFst<TropicalWeight>> {
+        let replaced: VectorFst<_> = replace_with_options(grammar(), opts.clone())?;
+        let lazy: VectorFst<_> = ReplaceFst::new_with_options(grammar(), opts)?.compute()?;
+        assert_eq!(replaced, lazy);
+        Ok(replaced)
+    }
+
+    #[test]
+    fn test_replace_label_both() -> Result<()> {
+        let opts = ReplaceFstOptions::new(0, false)
+            .with_call_label_type(ReplaceLabelType::Both)
+            .with_return_label_type(ReplaceLabelType::Both)
+            .with_return_label(30);
+        assert_eq!(
+            replace_grammar(opts.clone())?,
+            fst![1, 10, 2, 30, 11, 3, 30 => 1, 10, 2, 30, 11, 3, 30]
+        );
+
+        let opts = opts.with_call_output_label(Some(40));
+        assert_eq!(
+            replace_grammar(opts)?,
+            fst![1, 10, 2, 30, 11, 3, 30 => 1, 40, 2, 30, 40, 3, 30]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_rule_labels() -> Result<()> {
+        // Marks the end of each rule in the output.
+        let opts = ReplaceFstOptions::new(0, false)
+            .with_return_label_type(ReplaceLabelType::Output)
+            .with_rule_return_label(10, 20)
+            .with_rule_return_label(11, 21);
+        assert_eq!(
+            replace_grammar(opts)?,
+            fst![1, 10, 2, 0, 11, 3, 0 => 1, 0, 2, 20, 0, 3, 21]
+        );
+
+        // The rule-specific labels override the global ones, epsilon disables the label.
+        let opts = ReplaceFstOptions::new(0, false)
+            .with_call_label_type(ReplaceLabelType::Both)
+            .with_return_label_type(ReplaceLabelType::Both)
+            .with_return_label(30)
+            .with_rule_call_output_label(10, 100)
+            .with_rule_call_output_label(11, 0)
+            .with_rule_return_label(11, 0);
+        assert_eq!(
+            replace_grammar(opts.clone())?,
+            fst![1, 10, 2, 30, 0, 3, 0 => 1, 100, 2, 30, 0, 3, 0]
+        );
+        // The properties can't be computed for different call output labels.
+        let lazy = ReplaceFst::<_, VectorFst<_>, _>::new_with_options(grammar(), opts)?;
+        assert_eq!(lazy.properties(), FstProperties::empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_with_limits() -> Result<()> {
+        let opts =
+            ReplaceFstOptions::new(0, false).with_limits(ResourceLimits::new().with_max_states(4));
+        let res: Result<VectorFst<TropicalWeight>> = replace_with_options(grammar(), opts);
+        assert_eq!(
+            Error::find(&res.unwrap_err()),
+            Some(Error::ResourceLimitExceeded {
+                resource: "states",
+                limit: 4
+            })
+        );
+
+        let opts =
+            ReplaceFstOptions::new(0, false).with_limits(ResourceLimits::new().with_max_states(8));
+        let _: VectorFst<TropicalWeight> = replace_with_options(grammar(), opts)?;
+        Ok(())
+    }
 }
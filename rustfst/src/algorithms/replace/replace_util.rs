@@ -0,0 +1,456 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::algorithms::connect;
+use crate::algorithms::dfs_visit::{dfs_visit, Visitor};
+use crate::algorithms::replace::{ReplaceFst, ReplaceFstOptions};
+use crate::algorithms::top_sort::TopOrderVisitor;
+use crate::algorithms::tr_filters::AnyTrFilter;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, MutableFst};
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{Label, StateId, Tr, Trs, EPS_LABEL};
+
+/// Size statistics of a rule of a `ReplaceUtil`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReplaceStats {
+    /// Number of states.
+    pub nstates: usize,
+    /// Number of final states.
+    pub nfinal: usize,
+    /// Number of transitions.
+    pub ntrs: usize,
+    /// Number of transitions labeled with a non-terminal.
+    pub nnonterms: usize,
+    /// Number of transitions of all the rules referring to this rule.
+    pub nref: usize,
+}
+
+/// Builds the dependency graph of the rules : state `i` is the rule at index `i` in
+/// `fst_list`, final if the rule has a final state, with a transition labeled `label` to each
+/// rule `label` it refers to. The start state is the root rule.
+fn dependency_graph<W, F, B>(
+    fst_list: &[(Label, B)],
+    root: Label,
+) -> Result<VectorFst<TropicalWeight>>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    let nonterminals: HashMap<Label, StateId> = fst_list
+        .iter()
+        .enumerate()
+        .map(|(i, (label, _))| (*label, i as StateId))
+        .collect();
+    let mut graph = VectorFst::new();
+    graph.add_states(fst_list.len());
+    for (i, (_, fst)) in fst_list.iter().enumerate() {
+        let fst = fst.borrow();
+        let i = i as StateId;
+        let mut callees = HashSet::new();
+        for s in fst.states_iter() {
+            if fst.is_final(s)? && !graph.is_final(i)? {
+                graph.set_final(i, TropicalWeight::one())?;
+            }
+            for tr in fst.get_trs(s)?.trs() {
+                if tr.olabel == EPS_LABEL {
+                    continue;
+                }
+                if let Some(&callee) = nonterminals.get(&tr.olabel) {
+                    if callees.insert(callee) {
+                        graph.add_tr(
+                            i,
+                            Tr::new(tr.olabel, tr.olabel, TropicalWeight::one(), callee),
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+    match nonterminals.get(&root) {
+        Some(&root) => graph.set_start(root)?,
+        None => bail!(
+            "Replace: No FST corresponding to root label {} in the input tuple vector",
+            root
+        ),
+    };
+    Ok(graph)
+}
+
+/// Visitor stopping at the first back transition, i.e the first cycle found.
+struct CycleVisitor {
+    acyclic: bool,
+}
+
+impl<'a, W: Semiring, F: 'a + Fst<W>> Visitor<'a, W, F> for CycleVisitor {
+    fn init_visit(&mut self, _fst: &'a F) {}
+
+    fn init_state(&mut self, _s: StateId, _root: StateId) -> bool {
+        true
+    }
+
+    fn tree_tr(&mut self, _s: StateId, _tr: &Tr<W>) -> bool {
+        true
+    }
+
+    fn back_tr(&mut self, _s: StateId, _tr: &Tr<W>) -> bool {
+        self.acyclic = false;
+        false
+    }
+
+    fn forward_or_cross_tr(&mut self, _s: StateId, _tr: &Tr<W>) -> bool {
+        true
+    }
+
+    fn finish_state(&mut self, _s: StateId, _parent: Option<StateId>, _tr: Option<&Tr<W>>) {}
+
+    fn finish_visit(&mut self) {}
+}
+
+/// Returns true if the dependency graph has a cycle. If `access_only` is true, only the rules
+/// reachable from the root rule are considered.
+fn has_cycle(graph: &VectorFst<TropicalWeight>, access_only: bool) -> bool {
+    let mut visitor = CycleVisitor { acyclic: true };
+    dfs_visit(graph, &mut visitor, &AnyTrFilter {}, access_only);
+    !visitor.acyclic
+}
+
+/// Checks that the expansion of the rules from the root rule is finite, i.e that no rule
+/// reachable from the root refers to itself, directly or not.
+pub(crate) fn check_acyclic_dependencies<W, F, B>(
+    fst_list: &[(Label, B)],
+    root: Label,
+) -> Result<()>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+{
+    if fst_list.is_empty() {
+        return Ok(());
+    }
+    if has_cycle(&dependency_graph::<W, F, B>(fst_list, root)?, true) {
+        bail!(
+            "Replace: the rules reachable from the root rule {} have cyclic dependencies, their \
+             expansion is infinite. Use ReplaceFst to expand them lazily",
+            root
+        );
+    }
+    Ok(())
+}
+
+/// Utility to analyze and simplify the rules of a `replace` before expanding them, like
+/// OpenFST's `ReplaceUtil`. The rules are given as in `replace` : a vector of pairs of a
+/// non-terminal label and the corresponding FST.
+///
+/// Small or rarely used rules can be inlined in the rules referring to them with
+/// `replace_by_size` and `replace_by_instances`, while the others are kept to be expanded
+/// lazily by a `ReplaceFst`. The rules inlined are kept until `connect` removes the rules not
+/// reachable anymore from the root rule.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::replace::{ReplaceFst, ReplaceUtil};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::transducer;
+/// # fn main() -> Result<()> {
+/// let root: VectorFst<TropicalWeight> = fst![1, 10, 11 => 1, 10, 11];
+/// let rule_10: VectorFst<TropicalWeight> = fst![2 => 2];
+/// let rule_11: VectorFst<TropicalWeight> = fst![3, 4 => 3, 4];
+/// let unused: VectorFst<TropicalWeight> = fst![5 => 5];
+///
+/// let mut replace_util =
+///     ReplaceUtil::new(vec![(0, root), (10, rule_10), (11, rule_11), (12, unused)], 0, true)?;
+/// assert!(!replace_util.cyclic_dependencies()?);
+///
+/// // Inlines the rules with a single transition and removes the rules not used anymore.
+/// replace_util.replace_by_size(usize::MAX, 1, 0)?;
+/// replace_util.connect()?;
+/// let labels: Vec<_> = replace_util.fst_list().iter().map(|(label, _)| *label).collect();
+/// assert_eq!(labels, vec![0, 11]);
+///
+/// let replaced: VectorFst<TropicalWeight> =
+///     ReplaceFst::new(replace_util.into_fst_list(), 0, true)?.compute()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReplaceUtil<W: Semiring, F: MutableFst<W> + AllocableFst<W>> {
+    fst_list: Vec<(Label, F)>,
+    opts: ReplaceFstOptions,
+    w: PhantomData<W>,
+}
+
+impl<W, F> ReplaceUtil<W, F>
+where
+    W: Semiring,
+    F: MutableFst<W> + AllocableFst<W>,
+{
+    /// Creates a `ReplaceUtil` from the rules and the label of the root rule. The rules inlined
+    /// use `epsilon_on_replace` as in `replace`.
+    pub fn new(fst_list: Vec<(Label, F)>, root: Label, epsilon_on_replace: bool) -> Result<Self> {
+        Self::new_with_options(fst_list, ReplaceFstOptions::new(root, epsilon_on_replace))
+    }
+
+    /// Creates a `ReplaceUtil` whose rules are inlined with the call and return labels given
+    /// by `opts`, as in `replace_with_options`.
+    pub fn new_with_options(fst_list: Vec<(Label, F)>, opts: ReplaceFstOptions) -> Result<Self> {
+        if !fst_list.iter().any(|(label, _)| *label == opts.root) {
+            bail!(
+                "ReplaceUtil: No FST corresponding to root label {} in the input tuple vector",
+                opts.root
+            );
+        }
+        Ok(Self {
+            fst_list,
+            opts,
+            w: PhantomData,
+        })
+    }
+
+    pub fn root(&self) -> Label {
+        self.opts.root
+    }
+
+    /// The rules, in the order in which they were given.
+    pub fn fst_list(&self) -> &[(Label, F)] {
+        &self.fst_list
+    }
+
+    pub fn into_fst_list(self) -> Vec<(Label, F)> {
+        self.fst_list
+    }
+
+    /// Returns true if a rule refers to itself, directly or through other rules. The expansion
+    /// of such rules is infinite and can only be done lazily with a `ReplaceFst`.
+    pub fn cyclic_dependencies(&self) -> Result<bool> {
+        let graph = dependency_graph::<W, F, F>(&self.fst_list, self.opts.root)?;
+        Ok(has_cycle(&graph, false))
+    }
+
+    /// Size statistics of each rule, in the order of `fst_list`.
+    pub fn stats(&self) -> Result<Vec<ReplaceStats>> {
+        let index: HashMap<Label, usize> = self
+            .fst_list
+            .iter()
+            .enumerate()
+            .map(|(i, (label, _))| (*label, i))
+            .collect();
+        let mut stats = vec![ReplaceStats::default(); self.fst_list.len()];
+        for (i, (_, fst)) in self.fst_list.iter().enumerate() {
+            stats[i].nstates = fst.num_states();
+            for s in fst.states_iter() {
+                if fst.is_final(s)? {
+                    stats[i].nfinal += 1;
+                }
+                let trs = fst.get_trs(s)?;
+                stats[i].ntrs += trs.len();
+                for tr in trs.trs() {
+                    if tr.olabel == EPS_LABEL {
+                        continue;
+                    }
+                    if let Some(&callee) = index.get(&tr.olabel) {
+                        stats[i].nnonterms += 1;
+                        stats[callee].nref += 1;
+                    }
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Trims the rules with `connect` and removes the rules that are not reachable from the
+    /// root rule.
+    pub fn connect(&mut self) -> Result<()> {
+        for (_, fst) in self.fst_list.iter_mut() {
+            connect(fst)?;
+        }
+        let graph = dependency_graph::<W, F, F>(&self.fst_list, self.opts.root)?;
+        let mut reachable = vec![false; self.fst_list.len()];
+        let mut stack = graph.start().into_iter().collect::<Vec<_>>();
+        while let Some(s) = stack.pop() {
+            if !reachable[s as usize] {
+                reachable[s as usize] = true;
+                stack.extend(graph.get_trs(s)?.trs().iter().map(|tr| tr.nextstate));
+            }
+        }
+        let mut reachable = reachable.into_iter();
+        self.fst_list.retain(|_| reachable.next().unwrap_or(false));
+        Ok(())
+    }
+
+    /// Inlines the rules with the given labels in the rules referring to them. The root rule
+    /// can't be inlined and is ignored. Fails if the rules to inline have cyclic dependencies.
+    pub fn replace_labels(&mut self, labels: &[Label]) -> Result<()> {
+        let labels: HashSet<Label> = labels
+            .iter()
+            .cloned()
+            .filter(|label| *label != self.opts.root)
+            .collect();
+        // Keeps only the dependencies on the rules to inline.
+        let mut graph = dependency_graph::<W, F, F>(&self.fst_list, self.opts.root)?;
+        for s in 0..self.fst_list.len() as StateId {
+            let trs: Vec<_> = graph
+                .get_trs(s)?
+                .trs()
+                .iter()
+                .filter(|tr| labels.contains(&tr.olabel))
+                .cloned()
+                .collect();
+            graph.delete_trs(s)?;
+            for tr in trs {
+                graph.add_tr(s, tr)?;
+            }
+        }
+        let mut visitor = TopOrderVisitor::new();
+        dfs_visit(&graph, &mut visitor, &AnyTrFilter {}, false);
+        if !visitor.acyclic {
+            bail!("ReplaceUtil: the rules to replace have cyclic dependencies");
+        }
+
+        // Replaces the rules in reverse topological order so that the rules inlined are
+        // already expanded.
+        let mut states: Vec<StateId> = (0..self.fst_list.len() as StateId).collect();
+        states.sort_by_key(|s| std::cmp::Reverse(visitor.order[*s as usize]));
+        for s in states {
+            let callees = graph.get_trs(s)?;
+            if callees.is_empty() {
+                continue;
+            }
+            let mut fst_pairs = vec![];
+            for tr in callees.trs() {
+                let callee = &self.fst_list[tr.nextstate as usize];
+                fst_pairs.push((callee.0, &callee.1));
+            }
+            // The rule is given a label it doesn't refer to, so that its references to itself
+            // are kept as non-terminals.
+            let mut used_labels = HashSet::new();
+            for fst in std::iter::once(&self.fst_list[s as usize].1)
+                .chain(fst_pairs.iter().map(|(_, fst)| *fst))
+            {
+                for state in fst.states_iter() {
+                    used_labels.extend(fst.get_trs(state)?.trs().iter().map(|tr| tr.olabel));
+                }
+            }
+            let root = (1..)
+                .find(|label| !used_labels.contains(label) && !labels.contains(label))
+                .unwrap();
+            fst_pairs.insert(0, (root, &self.fst_list[s as usize].1));
+            let opts = self.opts.clone().with_root(root);
+            let replaced: F =
+                ReplaceFst::<W, F, &F>::new_with_options(fst_pairs, opts)?.compute()?;
+            self.fst_list[s as usize].1 = replaced;
+        }
+        Ok(())
+    }
+
+    /// Inlines the rules with at most `nstates` states, `ntrs` transitions and `nnonterms`
+    /// transitions labeled with a non-terminal.
+    pub fn replace_by_size(&mut self, nstates: usize, ntrs: usize, nnonterms: usize) -> Result<()> {
+        let labels: Vec<_> = self
+            .stats()?
+            .iter()
+            .zip(self.fst_list.iter())
+            .filter(|(stats, _)| {
+                stats.nstates <= nstates && stats.ntrs <= ntrs && stats.nnonterms <= nnonterms
+            })
+            .map(|(_, (label, _))| *label)
+            .collect();
+        self.replace_labels(&labels)
+    }
+
+    /// Inlines the rules referred to by at most `ninstances` transitions.
+    pub fn replace_by_instances(&mut self, ninstances: usize) -> Result<()> {
+        let labels: Vec<_> = self
+            .stats()?
+            .iter()
+            .zip(self.fst_list.iter())
+            .filter(|(stats, _)| stats.nref <= ninstances)
+            .map(|(_, (label, _))| *label)
+            .collect();
+        self.replace_labels(&labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::isomorphic;
+    use crate::algorithms::replace::replace;
+    use crate::utils::transducer;
+
+    type Rules = Vec<(Label, VectorFst<TropicalWeight>)>;
+
+    fn grammar() -> Rules {
+        vec![
+            (
+                0,
+                transducer(&[1, 10, 11], &[1, 10, 11], TropicalWeight::one()),
+            ),
+            (10, transducer(&[2, 11], &[2, 11], TropicalWeight::one())),
+            (11, transducer(&[3], &[3], TropicalWeight::one())),
+            (12, transducer(&[4], &[4], TropicalWeight::one())),
+        ]
+    }
+
+    #[test]
+    fn test_replace_util_stats() -> Result<()> {
+        let replace_util = ReplaceUtil::new(grammar(), 0, true)?;
+        let stats = replace_util.stats()?;
+        assert_eq!(stats[0].nnonterms, 2);
+        assert_eq!(stats[1].nref, 1);
+        assert_eq!(stats[2].nref, 2);
+        assert_eq!(stats[3].nref, 0);
+        assert_eq!(
+            stats[2],
+            ReplaceStats {
+                nstates: 2,
+                nfinal: 1,
+                ntrs: 1,
+                nnonterms: 0,
+                nref: 2
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_util_inline_and_connect() -> Result<()> {
+        let expected: VectorFst<TropicalWeight> = replace(grammar(), 0, true)?;
+
+        let mut replace_util = ReplaceUtil::new(grammar(), 0, true)?;
+        replace_util.replace_by_instances(1)?;
+        replace_util.connect()?;
+        let labels: Vec<_> = replace_util.fst_list().iter().map(|v| v.0).collect();
+        assert_eq!(labels, vec![0, 11]);
+        let replaced: VectorFst<TropicalWeight> = replace(replace_util.into_fst_list(), 0, true)?;
+        assert!(isomorphic(&replaced, &expected)?);
+
+        let mut replace_util = ReplaceUtil::new(grammar(), 0, true)?;
+        replace_util.replace_labels(&[10, 11])?;
+        replace_util.connect()?;
+        assert_eq!(replace_util.fst_list().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_util_cyclic_dependencies() -> Result<()> {
+        let mut rules = grammar();
+        rules[2].1 = transducer(&[3, 10], &[3, 10], TropicalWeight::one());
+        let mut replace_util = ReplaceUtil::new(rules.clone(), 0, true)?;
+        assert!(replace_util.cyclic_dependencies()?);
+        assert!(replace_util.replace_labels(&[10, 11]).is_err());
+        replace_util.replace_labels(&[11])?;
+
+        assert!(replace::<_, VectorFst<_>, VectorFst<_>, _>(rules.clone(), 0, true).is_err());
+        // The cycle is not reachable from the rule 12.
+        let _: VectorFst<TropicalWeight> = replace(rules, 12, true)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use std::mem::size_of;
+
+use anyhow::Result;
+
+use crate::fst_impls::vector_fst::VectorFstState;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{Error, Tr};
+
+/// Bounds on the size of the FST built by an algorithm that can blow up, e.g the determinization
+/// or the composition. The algorithm fails with `Error::ResourceLimitExceeded` as soon as its
+/// output exceeds one of them, and its inputs are left untouched. `None` means unlimited.
+///
+/// The memory is estimated from the number of states and transitions of the output, the heap
+/// allocated by the weights (e.g `StringWeight`) and by the internal caches of the lazy FSTs
+/// is not counted.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::algorithms::ResourceLimits;
+/// # use rustfst::algorithms::determinize::{determinize_with_config, DeterminizeConfig};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::transducer;
+/// # use rustfst::Error;
+/// let fst: VectorFst<TropicalWeight> = fst![1, 2, 3 => 1, 2, 3];
+/// let limits = ResourceLimits::new().with_max_states(2);
+/// let config = DeterminizeConfig::default().with_limits(limits);
+/// let err = determinize_with_config::<_, _, VectorFst<_>>(&fst, config).unwrap_err();
+/// assert_eq!(
+///     Error::find(&err),
+///     Some(Error::ResourceLimitExceeded { resource: "states", limit: 2 })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceLimits {
+    /// Maximum number of states of the output.
+    pub max_states: Option<usize>,
+    /// Maximum number of transitions of the output.
+    pub max_trs: Option<usize>,
+    /// Maximum estimated memory of the output, in bytes.
+    pub max_memory_bytes: Option<usize>,
+}
+
+impl ResourceLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_states(self, max_states: usize) -> Self {
+        Self {
+            max_states: Some(max_states),
+            ..self
+        }
+    }
+
+    pub fn with_max_trs(self, max_trs: usize) -> Self {
+        Self {
+            max_trs: Some(max_trs),
+            ..self
+        }
+    }
+
+    pub fn with_max_memory_bytes(self, max_memory_bytes: usize) -> Self {
+        Self {
+            max_memory_bytes: Some(max_memory_bytes),
+            ..self
+        }
+    }
+
+    /// Estimated memory of a `VectorFst` with `num_states` states and `num_trs` transitions.
+    pub fn estimated_memory_bytes<W: Semiring>(num_states: usize, num_trs: usize) -> usize {
+        num_states
+            .saturating_mul(size_of::<VectorFstState<W>>())
+            .saturating_add(num_trs.saturating_mul(size_of::<Tr<W>>()))
+    }
+
+    /// Fails if an output with `num_states` states and `num_trs` transitions exceeds the limits.
+    pub(crate) fn check<W: Semiring>(&self, num_states: usize, num_trs: usize) -> Result<()> {
+        if let Some(limit) = self.max_states {
+            ensure!(
+                num_states <= limit,
+                Error::ResourceLimitExceeded {
+                    resource: "states",
+                    limit
+                }
+            );
+        }
+        if let Some(limit) = self.max_trs {
+            ensure!(
+                num_trs <= limit,
+                Error::ResourceLimitExceeded {
+                    resource: "trs",
+                    limit
+                }
+            );
+        }
+        if let Some(limit) = self.max_memory_bytes {
+            ensure!(
+                Self::estimated_memory_bytes::<W>(num_states, num_trs) <= limit,
+                Error::ResourceLimitExceeded {
+                    resource: "memory bytes",
+                    limit
+                }
+            );
+        }
+        Ok(())
+    }
+
+    /// Fails if `fst` exceeds the limits.
+    pub(crate) fn check_fst<W: Semiring, F: ExpandedFst<W>>(&self, fst: &F) -> Result<()> {
+        if self.is_unlimited() {
+            return Ok(());
+        }
+        let num_trs = fst
+            .states_iter()
+            .map(|s| unsafe { fst.num_trs_unchecked(s) })
+            .sum();
+        self.check::<W>(fst.num_states(), num_trs)
+    }
+
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.max_states.is_none() && self.max_trs.is_none() && self.max_memory_bytes.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_resource_limits() -> Result<()> {
+        let limits = ResourceLimits::new().with_max_states(2).with_max_trs(3);
+        limits.check::<TropicalWeight>(2, 3)?;
+        let err = limits.check::<TropicalWeight>(3, 3).unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::ResourceLimitExceeded {
+                resource: "states",
+                limit: 2
+            })
+        );
+        let err = limits.check::<TropicalWeight>(2, 4).unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::ResourceLimitExceeded {
+                resource: "trs",
+                limit: 3
+            })
+        );
+
+        let max_memory_bytes = ResourceLimits::estimated_memory_bytes::<TropicalWeight>(2, 3);
+        let limits = ResourceLimits::new().with_max_memory_bytes(max_memory_bytes);
+        limits.check::<TropicalWeight>(2, 3)?;
+        assert!(limits.check::<TropicalWeight>(2, 4).is_err());
+        assert!(ResourceLimits::new().is_unlimited());
+        Ok(())
+    }
+}
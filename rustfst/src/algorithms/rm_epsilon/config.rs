@@ -1,6 +1,6 @@
 use crate::algorithms::shortest_distance::ShortestDistanceInternalConfig;
 use crate::algorithms::tr_filters::EpsilonTrFilter;
-use crate::algorithms::Queue;
+use crate::algorithms::{Progress, Queue};
 use crate::semirings::Semiring;
 use crate::{StateId, KSHORTESTDELTA};
 
@@ -35,3 +35,45 @@ impl<W: Semiring, Q: Queue> RmEpsilonInternalConfig<W, Q> {
         Self::new(queue, true, W::zero(), None, KSHORTESTDELTA)
     }
 }
+
+/// Configuration of `rm_epsilon_with_config`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RmEpsilonConfig {
+    /// Remove the states that are no longer accessible or coaccessible.
+    pub connect: bool,
+    pub delta: f32,
+    /// Polled after each state is dequeued by the epsilon closure computations.
+    pub progress: Progress,
+}
+
+impl Default for RmEpsilonConfig {
+    fn default() -> Self {
+        Self {
+            connect: true,
+            delta: KSHORTESTDELTA,
+            progress: Progress::default(),
+        }
+    }
+}
+
+impl RmEpsilonConfig {
+    pub fn new(connect: bool, delta: f32) -> Self {
+        Self {
+            connect,
+            delta,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_connect(self, connect: bool) -> Self {
+        Self { connect, ..self }
+    }
+
+    pub fn with_delta(self, delta: f32) -> Self {
+        Self { delta, ..self }
+    }
+
+    pub fn with_progress(self, progress: Progress) -> Self {
+        Self { progress, ..self }
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 
 use crate::algorithms::dfs_visit::dfs_visit;
 use crate::algorithms::queues::AutoQueue;
-use crate::algorithms::rm_epsilon::{RmEpsilonInternalConfig, RmEpsilonState};
+use crate::algorithms::rm_epsilon::{RmEpsilonConfig, RmEpsilonInternalConfig, RmEpsilonState};
 use crate::algorithms::top_sort::TopOrderVisitor;
 use crate::algorithms::tr_filters::EpsilonTrFilter;
 use crate::algorithms::visitors::SccVisitor;
@@ -63,11 +63,23 @@ use crate::{StateId, Trs, EPS_LABEL};
 /// ![rmepsilon_out](https://raw.githubusercontent.com/Garvys/rustfst-images-doc/master/images/rmepsilon_out.svg?sanitize=true)
 ///
 pub fn rm_epsilon<W: Semiring, F: MutableFst<W>>(fst: &mut F) -> Result<()> {
+    rm_epsilon_with_config(fst, RmEpsilonConfig::default())
+}
+
+/// Same as `rm_epsilon` with a configuration, e.g to cancel the operation or observe its
+/// progress.
+pub fn rm_epsilon_with_config<W: Semiring, F: MutableFst<W>>(
+    fst: &mut F,
+    config: RmEpsilonConfig,
+) -> Result<()> {
     let tr_filter = EpsilonTrFilter {};
     let queue = AutoQueue::new(fst, None, &tr_filter)?;
-    let opts = RmEpsilonInternalConfig::new_with_default(queue);
+    let mut opts =
+        RmEpsilonInternalConfig::new(queue, config.connect, W::zero(), None, config.delta);
+    opts.sd_opts.progress = config.progress;
     rm_epsilon_with_internal_config(fst, opts)
 }
+
 pub(crate) fn rm_epsilon_with_internal_config<W: Semiring, F: MutableFst<W>, Q: Queue>(
     fst: &mut F,
     opts: RmEpsilonInternalConfig<W, Q>,
@@ -179,11 +191,13 @@ pub(crate) fn rm_epsilon_with_internal_config<W: Semiring, F: MutableFst<W>, Q:
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::Progress;
     use crate::fst_traits::Fst;
     use crate::prelude::{TropicalWeight, VectorFst};
-    use crate::SymbolTable;
+    use crate::{Error, SymbolTable, Tr};
     use proptest::prelude::any;
     use proptest::proptest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
     proptest! {
@@ -199,4 +213,38 @@ mod tests {
             assert!(fst.output_symbols().is_some());
         }
     }
+
+    #[test]
+    fn test_rm_epsilon_with_progress() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(EPS_LABEL, EPS_LABEL, 1.0, s1))?;
+        fst.add_tr(s1, Tr::new(1, 1, 2.0, s2))?;
+        fst.set_final(s2, 0.0)?;
+
+        let num_steps = Arc::new(AtomicUsize::new(0));
+        let num_steps_clone = Arc::clone(&num_steps);
+        let progress = Progress::new()
+            .with_interval(1)
+            .with_callback(move |steps| num_steps_clone.store(steps, Ordering::Relaxed));
+        let mut fst_no_epsilon = fst.clone();
+        rm_epsilon_with_config(
+            &mut fst_no_epsilon,
+            RmEpsilonConfig::default().with_progress(progress.clone()),
+        )?;
+        let mut fst_ref = fst.clone();
+        rm_epsilon(&mut fst_ref)?;
+        assert_eq!(fst_no_epsilon, fst_ref);
+        assert!(num_steps.load(Ordering::Relaxed) > 0);
+
+        progress.cancel();
+        let err =
+            rm_epsilon_with_config(&mut fst, RmEpsilonConfig::default().with_progress(progress))
+                .unwrap_err();
+        assert_eq!(Error::find(&err), Some(Error::Cancelled));
+        Ok(())
+    }
 }
@@ -4,11 +4,11 @@ use anyhow::Result;
 
 use crate::algorithms::queues::AutoQueue;
 use crate::algorithms::tr_filters::{AnyTrFilter, TrFilter};
-use crate::algorithms::Queue;
+use crate::algorithms::{Progress, Queue};
 use crate::fst_impls::VectorFst;
 use crate::fst_traits::{ExpandedFst, MutableFst};
 use crate::semirings::{ReverseBack, Semiring, SemiringProperties, WeightQuantize};
-use crate::{StateId, Trs, KSHORTESTDELTA};
+use crate::{Error, StateId, Trs, KSHORTESTDELTA};
 use std::borrow::Borrow;
 
 pub(crate) struct ShortestDistanceInternalConfig<W: Semiring, Q: Queue, A: TrFilter<W>> {
@@ -17,6 +17,7 @@ pub(crate) struct ShortestDistanceInternalConfig<W: Semiring, Q: Queue, A: TrFil
     pub source: Option<StateId>,
     pub first_path: bool,
     pub delta: f32,
+    pub progress: Progress,
     // TODO: Shouldn't need that
     weight: PhantomData<W>,
 }
@@ -35,6 +36,7 @@ impl<W: Semiring, Q: Queue, A: TrFilter<W>> ShortestDistanceInternalConfig<W, Q,
             source,
             first_path,
             delta,
+            progress: Progress::default(),
             weight: PhantomData,
         }
     }
@@ -57,6 +59,8 @@ pub(crate) struct ShortestDistanceState<W: Semiring, Q: Queue, A: TrFilter<W>> {
     retain: bool,
     source_id: usize,
     delta: f32,
+    progress: Progress,
+    num_dequeued: usize,
 }
 
 impl<W: Semiring, Q: Queue, A: TrFilter<W>> std::fmt::Debug for ShortestDistanceState<W, Q, A> {
@@ -118,6 +122,8 @@ impl<W: Semiring, Q: Queue, A: TrFilter<W>> ShortestDistanceState<W, Q, A> {
             source_id: 0,
             retain,
             delta,
+            progress: Progress::default(),
+            num_dequeued: 0,
         }
     }
     pub fn new_from_config(
@@ -125,14 +131,17 @@ impl<W: Semiring, Q: Queue, A: TrFilter<W>> ShortestDistanceState<W, Q, A> {
         opts: ShortestDistanceInternalConfig<W, Q, A>,
         retain: bool,
     ) -> Self {
-        Self::new(
-            fst_num_states,
-            opts.state_queue,
-            opts.tr_filter,
-            opts.first_path,
-            retain,
-            opts.delta,
-        )
+        Self {
+            progress: opts.progress,
+            ..Self::new(
+                fst_num_states,
+                opts.state_queue,
+                opts.tr_filter,
+                opts.first_path,
+                retain,
+                opts.delta,
+            )
+        }
     }
 
     fn ensure_distance_index_is_valid(&mut self, index: usize) {
@@ -161,10 +170,16 @@ impl<W: Semiring, Q: Queue, A: TrFilter<W>> ShortestDistanceState<W, Q, A> {
         };
         let weight_properties = W::properties();
         if !weight_properties.contains(SemiringProperties::RIGHT_SEMIRING) {
-            bail!("ShortestDistance: Weight needs to be right distributive")
+            bail!(Error::MissingSemiringProperties {
+                operation: "shortest_distance",
+                properties: SemiringProperties::RIGHT_SEMIRING,
+            })
         }
         if self.first_path && !weight_properties.contains(SemiringProperties::PATH) {
-            bail!("ShortestDistance: The first_path option is disallowed when Weight does not have the path property")
+            bail!(Error::MissingSemiringProperties {
+                operation: "shortest_distance",
+                properties: SemiringProperties::PATH,
+            })
         }
         self.state_queue.clear();
         if !self.retain {
@@ -186,6 +201,8 @@ impl<W: Semiring, Q: Queue, A: TrFilter<W>> ShortestDistanceState<W, Q, A> {
         self.enqueued[source] = true;
         self.state_queue.enqueue(source as StateId);
         while let Some(state) = self.state_queue.dequeue() {
+            self.num_dequeued += 1;
+            self.progress.step(self.num_dequeued)?;
             let state = state as usize;
             //            self.ensure_distance_index_is_valid(state);
             if self.first_path && fst.borrow().is_final(state as StateId)? {
@@ -252,22 +269,32 @@ pub(crate) fn shortest_distance_with_internal_config<
 }
 
 /// Configuration for shortest distance computation
-#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ShortestDistanceConfig {
     delta: f32,
+    /// Polled after each state is dequeued.
+    progress: Progress,
 }
 
 impl Default for ShortestDistanceConfig {
     fn default() -> Self {
         Self {
             delta: KSHORTESTDELTA,
+            progress: Progress::default(),
         }
     }
 }
 
 impl ShortestDistanceConfig {
     pub fn new(delta: f32) -> Self {
-        Self { delta }
+        Self {
+            delta,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_progress(self, progress: Progress) -> Self {
+        Self { progress, ..self }
     }
 }
 
@@ -316,15 +343,19 @@ pub fn shortest_distance_with_config<W: Semiring, F: ExpandedFst<W>>(
     config: ShortestDistanceConfig,
 ) -> Result<Vec<W>> {
     let delta = config.delta;
+    let progress = config.progress;
     let tr_filter = AnyTrFilter {};
     if !reverse {
         let queue = AutoQueue::new(fst, None, &tr_filter)?;
-        let config = ShortestDistanceInternalConfig::new_with_default(tr_filter, queue, delta);
+        let mut config = ShortestDistanceInternalConfig::new_with_default(tr_filter, queue, delta);
+        config.progress = progress;
         shortest_distance_with_internal_config(fst, config)
     } else {
         let rfst: VectorFst<_> = crate::algorithms::reverse(fst)?;
         let state_queue = AutoQueue::new(&rfst, None, &tr_filter)?;
-        let ropts = ShortestDistanceInternalConfig::new_with_default(tr_filter, state_queue, delta);
+        let mut ropts =
+            ShortestDistanceInternalConfig::new_with_default(tr_filter, state_queue, delta);
+        ropts.progress = progress;
         let rdistance = shortest_distance_with_internal_config(&rfst, ropts)?;
         let mut distance = Vec::with_capacity(rdistance.len() - 1); //reversing added one state
         while distance.len() < rdistance.len() - 1 {
@@ -17,7 +17,7 @@ use crate::semirings::{
     ReverseBack, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
 };
 use crate::Tr;
-use crate::{StateId, Trs, KSHORTESTDELTA};
+use crate::{Error, StateId, Trs, KSHORTESTDELTA};
 use std::fmt::{Debug, Formatter};
 
 /// Configuration for N-shortest path computation
@@ -134,7 +134,10 @@ where
     }
 
     if !W::properties().contains(SemiringProperties::PATH | SemiringProperties::SEMIRING) {
-        bail!("ShortestPath : Weight need to have the Path property and be distributive")
+        bail!(Error::MissingSemiringProperties {
+            operation: "shortest_path",
+            properties: SemiringProperties::PATH | SemiringProperties::SEMIRING,
+        })
     }
 
     let mut distance =
@@ -194,9 +197,10 @@ where
     distance.clear();
     queue.clear();
     if !W::properties().contains(SemiringProperties::PATH | SemiringProperties::RIGHT_SEMIRING) {
-        bail!(
-            "SingleShortestPath: Weight needs to have the path property and be right distributive"
-        )
+        bail!(Error::MissingSemiringProperties {
+            operation: "shortest_path",
+            properties: SemiringProperties::PATH | SemiringProperties::RIGHT_SEMIRING,
+        })
     }
     distance.resize_with(ifst.num_states(), W::zero);
     enqueued.resize(ifst.num_states(), false);
@@ -419,7 +423,10 @@ where
     }
 
     if !W::properties().contains(SemiringProperties::PATH | SemiringProperties::SEMIRING) {
-        bail!("NShortestPath: Weight needs to have the path property and be distributive");
+        bail!(Error::MissingSemiringProperties {
+            operation: "shortest_path",
+            properties: SemiringProperties::PATH | SemiringProperties::SEMIRING,
+        });
     }
 
     let istart = ifst.start();
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::fst_traits::Fst;
+use crate::semirings::Semiring;
+use crate::{Label, SymbolTable, Trs, EPS_LABEL};
+
+/// Merges two symbol tables, like OpenFST's `MergeSymbolTable`. The merged table contains the
+/// symbols of `left` with their labels, followed by the symbols of `right` missing from `left`.
+///
+/// The returned pairs map the labels of `right` to their labels in the merged table, for the
+/// labels that change. They can be passed to `relabel_pairs` to relabel an FST using `right`.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::SymbolTable;
+/// # use rustfst::algorithms::merge_symbol_table;
+/// let lexicon = symt!["hello", "world"];
+/// let grammar = symt!["world", "again"];
+/// let (merged, relabel) = merge_symbol_table(&lexicon, &grammar);
+/// assert_eq!(merged, symt!["hello", "world", "again"]);
+/// assert_eq!(relabel, vec![(1, 2), (2, 3)]);
+/// ```
+pub fn merge_symbol_table(
+    left: &SymbolTable,
+    right: &SymbolTable,
+) -> (SymbolTable, Vec<(Label, Label)>) {
+    let mut merged = left.clone();
+    let mut relabel = vec![];
+    for (label, symbol) in right.iter() {
+        let new_label = merged.add_symbol(symbol);
+        if new_label != label {
+            relabel.push((label, new_label));
+        }
+    }
+    (merged, relabel)
+}
+
+/// Removes the symbols for which `keep` returns false and renumbers the remaining ones
+/// contiguously, in the order of their labels, like OpenFST's `CompactSymbolTable` after
+/// deletions.
+///
+/// The returned pairs map the old labels of the symbols kept to their new labels, for the
+/// labels that change.
+pub fn compact_symbol_table<P: FnMut(Label, &str) -> bool>(
+    symt: &SymbolTable,
+    mut keep: P,
+) -> (SymbolTable, Vec<(Label, Label)>) {
+    let mut compacted = SymbolTable::empty();
+    if let Some(name) = symt.name() {
+        compacted.set_name(name);
+    }
+    let mut relabel = vec![];
+    for (label, symbol) in symt.iter() {
+        if keep(label, symbol) {
+            let new_label = compacted.add_symbol(symbol);
+            if new_label != label {
+                relabel.push((label, new_label));
+            }
+        }
+    }
+    (compacted, relabel)
+}
+
+/// Keeps only the symbols of `symt` used by the input labels of `fst`, or its output labels if
+/// `input` is false, and the epsilon symbol, like OpenFST's `PruneSymbolTable`. Unlike OpenFST, the
+/// pruned table is compacted so that its labels stay dense.
+///
+/// The returned pairs map the old labels of the symbols kept to their new labels, for the
+/// labels that change. They can be passed to `relabel_pairs` to relabel `fst`.
+pub fn prune_symbol_table<W: Semiring, F: Fst<W>>(
+    fst: &F,
+    symt: &SymbolTable,
+    input: bool,
+) -> Result<(SymbolTable, Vec<(Label, Label)>)> {
+    let mut used = HashSet::new();
+    used.insert(EPS_LABEL);
+    for state in fst.states_iter() {
+        for tr in fst.get_trs(state)?.trs() {
+            used.insert(if input { tr.ilabel } else { tr.olabel });
+        }
+    }
+    Ok(compact_symbol_table(symt, |label, _| used.contains(&label)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::relabel_pairs;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, MutableFst};
+    use crate::semirings::TropicalWeight;
+    use crate::{symt, Tr};
+
+    #[test]
+    fn test_merge_symbol_table() -> Result<()> {
+        let left = symt!["a", "b"];
+        let right = symt!["c", "b", "d"];
+        let (merged, relabel) = merge_symbol_table(&left, &right);
+        assert_eq!(merged, symt!["a", "b", "c", "d"]);
+        assert_eq!(relabel, vec![(1, 3), (3, 4)]);
+        for (label, new_label) in relabel.iter() {
+            assert_eq!(right.get_symbol(*label), merged.get_symbol(*new_label));
+        }
+
+        let (merged, relabel) = merge_symbol_table(&left, &symt!["a"]);
+        assert_eq!(merged, left);
+        assert!(relabel.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_prune_symbol_table() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(3, 1, 1.0, s1))?;
+        fst.add_tr(s1, Tr::new(0, 4, 1.0, s1))?;
+        fst.set_final(s1, 0.0)?;
+        let mut symt = symt!["a", "b", "c", "d"];
+        symt.set_name("words");
+
+        let (pruned, ipairs) = prune_symbol_table(&fst, &symt, true)?;
+        assert_eq!(pruned, symt!["c"]);
+        assert_eq!(pruned.name(), Some("words"));
+        assert_eq!(ipairs, vec![(3, 1)]);
+
+        let (pruned, opairs) = prune_symbol_table(&fst, &symt, false)?;
+        assert_eq!(pruned, symt!["a", "d"]);
+        assert_eq!(opairs, vec![(4, 2)]);
+
+        relabel_pairs(&mut fst, ipairs, opairs)?;
+        let labels: Vec<_> = fst
+            .get_trs(s0)?
+            .trs()
+            .iter()
+            .chain(fst.get_trs(s1)?.trs())
+            .map(|tr| (tr.ilabel, tr.olabel))
+            .collect();
+        assert_eq!(labels, vec![(1, 1), (0, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_symbol_table() {
+        let symt = symt!["a", "b", "c"];
+        let (compacted, relabel) = compact_symbol_table(&symt, |_, symbol| symbol != "a");
+        assert_eq!(compacted, symt!["b", "c"]);
+        assert_eq!(relabel, vec![(2, 1), (3, 2)]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::{Label, StateId, Tr, Trs, EPS_LABEL};
+
+/// State of the synchronized FST : a state of the input FST, or `None` once its final
+/// weight has been consumed, along with the input and output labels not emitted yet.
+type SynchronizeElement = (Option<StateId>, Vec<Label>, Vec<Label>);
+
+fn find_state(
+    element: SynchronizeElement,
+    state_table: &mut HashMap<SynchronizeElement, StateId>,
+    queue: &mut VecDeque<(SynchronizeElement, StateId)>,
+) -> StateId {
+    let n = state_table.len() as StateId;
+    match state_table.entry(element) {
+        Entry::Occupied(e) => *e.get(),
+        Entry::Vacant(e) => {
+            queue.push_back((e.key().clone(), n));
+            e.insert(n);
+            n
+        }
+    }
+}
+
+fn push_label(string: &[Label], label: Label) -> Vec<Label> {
+    let mut res = string.to_vec();
+    if label != EPS_LABEL {
+        res.push(label);
+    }
+    res
+}
+
+/// Splits `string + [label]` into its first label (or epsilon) and the rest.
+fn split_first(string: &[Label], label: Label) -> (Label, Vec<Label>) {
+    let full = push_label(string, label);
+    match full.split_first() {
+        Some((first, rest)) => (*first, rest.to_vec()),
+        None => (EPS_LABEL, vec![]),
+    }
+}
+
+/// Synchronizes an FST : the output FST is equivalent to the input one but its input and
+/// output labels are aligned. The labels are buffered along epsilon transitions until both
+/// sides have a label to emit, so that epsilons on one side only appear at the end of
+/// successful paths, where the remaining labels of the buffers are emitted.
+///
+/// The input FST must have bounded delay, i.e the difference between the number of
+/// non-epsilon input labels and output labels must be bounded on every path, otherwise this
+/// algorithm doesn't terminate.
+pub fn synchronize<W, F1, F2>(ifst: &F1) -> Result<F2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    let mut ofst = F2::new();
+    let start = match ifst.start() {
+        Some(s) => s,
+        None => return Ok(ofst),
+    };
+    let mut state_table = HashMap::new();
+    let mut queue = VecDeque::new();
+    let ostart = find_state((Some(start), vec![], vec![]), &mut state_table, &mut queue);
+
+    while let Some(((state, istring, ostring), s)) = queue.pop_front() {
+        while ofst.num_states() <= s as usize {
+            ofst.add_state();
+        }
+        if let Some(state) = state {
+            for tr in ifst.get_trs(state)?.trs() {
+                let iempty = istring.is_empty() && tr.ilabel == EPS_LABEL;
+                let oempty = ostring.is_empty() && tr.olabel == EPS_LABEL;
+                if !iempty && !oempty {
+                    let (ilabel, irest) = split_first(&istring, tr.ilabel);
+                    let (olabel, orest) = split_first(&ostring, tr.olabel);
+                    let nextstate = find_state(
+                        (Some(tr.nextstate), irest, orest),
+                        &mut state_table,
+                        &mut queue,
+                    );
+                    ofst.emplace_tr(s, ilabel, olabel, tr.weight.clone(), nextstate)?;
+                } else {
+                    let nextstate = find_state(
+                        (
+                            Some(tr.nextstate),
+                            push_label(&istring, tr.ilabel),
+                            push_label(&ostring, tr.olabel),
+                        ),
+                        &mut state_table,
+                        &mut queue,
+                    );
+                    ofst.emplace_tr(s, EPS_LABEL, EPS_LABEL, tr.weight.clone(), nextstate)?;
+                }
+            }
+        }
+
+        let final_weight = match state {
+            Some(state) => ifst.final_weight(state)?,
+            None => Some(W::one()),
+        };
+        if let Some(final_weight) = final_weight {
+            if istring.is_empty() && ostring.is_empty() {
+                ofst.set_final(s, final_weight)?;
+            } else {
+                let (ilabel, irest) = split_first(&istring, EPS_LABEL);
+                let (olabel, orest) = split_first(&ostring, EPS_LABEL);
+                let nextstate = find_state((None, irest, orest), &mut state_table, &mut queue);
+                ofst.add_tr(s, Tr::new(ilabel, olabel, final_weight, nextstate))?;
+            }
+        }
+    }
+    while ofst.num_states() < state_table.len() {
+        ofst.add_state();
+    }
+    ofst.set_start(ostart)?;
+    ofst.set_symts_from_fst(ifst);
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, Fst, SerializableFst};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_synchronize_delayed_output() -> Result<()> {
+        let ifst: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t0\t1\n1\t2\t2\t3\n2\t4\n")?;
+        let ofst: VectorFst<TropicalWeight> = synchronize(&ifst)?;
+
+        let paths: Vec<_> = ofst.paths_iter().collect();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].ilabels, vec![1, 2]);
+        assert_eq!(paths[0].olabels, vec![3]);
+        assert_eq!(paths[0].weight, TropicalWeight::new(5.0));
+
+        let start = ofst.start().unwrap();
+        let trs = ofst.get_trs(start)?;
+        assert_eq!((trs.trs()[0].ilabel, trs.trs()[0].olabel), (0, 0));
+        let next = trs.trs()[0].nextstate;
+        let trs = ofst.get_trs(next)?;
+        assert_eq!((trs.trs()[0].ilabel, trs.trs()[0].olabel), (1, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_synchronize_already_synchronized() -> Result<()> {
+        let ifst: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t2\n1\t2\t3\t4\t0.5\n2\n")?;
+        let ofst: VectorFst<TropicalWeight> = synchronize(&ifst)?;
+        assert_eq!(ofst.num_states(), 3);
+        assert_eq!(
+            ofst.paths_iter().collect::<Vec<_>>(),
+            ifst.paths_iter().collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
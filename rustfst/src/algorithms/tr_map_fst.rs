@@ -0,0 +1,439 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstOp2, LazyFst2, SimpleHashMapCache};
+use crate::algorithms::{FinalTr, MapFinalAction, TrMapper};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, Tr, Trs, TrsVec, EPS_LABEL};
+
+#[derive(Debug)]
+struct TrMapOpState {
+    superfinal: Option<StateId>,
+    num_states: usize,
+}
+
+pub struct TrMapOp<W: Semiring, F: Fst<W>, B: Borrow<F>, M: TrMapper<W>> {
+    fst: B,
+    mapper: M,
+    properties: FstProperties,
+    state: Mutex<TrMapOpState>,
+    ghost: PhantomData<(W, F)>,
+}
+
+impl<W: Semiring, F: Fst<W>, B: Borrow<F>, M: TrMapper<W>> Debug for TrMapOp<W, F, B, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrMapOp")
+            .field("fst", self.fst.borrow())
+            .field("properties", &self.properties)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<W: Semiring, F: Fst<W>, B: Borrow<F>, M: TrMapper<W>> TrMapOp<W, F, B, M> {
+    pub fn new(fst: B, mapper: M) -> Self {
+        let properties = mapper.properties(fst.borrow().properties());
+        // As in OpenFST, a required superfinal state is always the state 0 of the output.
+        let state = if mapper.final_action() == MapFinalAction::MapRequireSuperfinal {
+            TrMapOpState {
+                superfinal: Some(0),
+                num_states: 1,
+            }
+        } else {
+            TrMapOpState {
+                superfinal: None,
+                num_states: 0,
+            }
+        };
+        Self {
+            fst,
+            mapper,
+            properties,
+            state: Mutex::new(state),
+            ghost: PhantomData,
+        }
+    }
+
+    /// Output state corresponding to the input state `is`.
+    fn find_ostate(state: &mut TrMapOpState, is: StateId) -> StateId {
+        let mut os = is;
+        if let Some(superfinal) = state.superfinal {
+            if superfinal <= os {
+                os += 1;
+            }
+        }
+        state.num_states = state.num_states.max(os as usize + 1);
+        os
+    }
+
+    /// Input state corresponding to the output state `os`, which must not be the superfinal.
+    fn find_istate(state: &TrMapOpState, os: StateId) -> StateId {
+        match state.superfinal {
+            Some(superfinal) if superfinal <= os => os - 1,
+            _ => os,
+        }
+    }
+}
+
+impl<W: Semiring, F: Fst<W>, B: Borrow<F>, M: TrMapper<W>> FstOp2<W> for TrMapOp<W, F, B, M> {
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        let mut state = self.state.lock().unwrap();
+        Ok(self
+            .fst
+            .borrow()
+            .start()
+            .map(|is| Self::find_ostate(&mut state, is)))
+    }
+
+    fn compute_trs_and_final_weight(&self, os: StateId) -> Result<(TrsVec<W>, Option<W>)> {
+        let mut state = self.state.lock().unwrap();
+        if state.superfinal == Some(os) {
+            return Ok((TrsVec::default(), Some(W::one())));
+        }
+        let fst = self.fst.borrow();
+        let is = Self::find_istate(&state, os);
+
+        let mut trs = vec![];
+        for tr in fst.get_trs(is)?.trs() {
+            let mut tr = tr.clone();
+            self.mapper.tr_map(&mut tr)?;
+            tr.nextstate = Self::find_ostate(&mut state, tr.nextstate);
+            trs.push(tr);
+        }
+
+        let mut final_weight = None;
+        if let Some(w) = fst.final_weight(is)? {
+            let mut final_tr = FinalTr {
+                ilabel: EPS_LABEL,
+                olabel: EPS_LABEL,
+                weight: w,
+            };
+            self.mapper.final_tr_map(&mut final_tr)?;
+            let has_labels = final_tr.ilabel != EPS_LABEL || final_tr.olabel != EPS_LABEL;
+            match self.mapper.final_action() {
+                MapFinalAction::MapNoSuperfinal => {
+                    if has_labels {
+                        bail!("TrMap: Non-zero tr labels for superfinal tr")
+                    }
+                    final_weight = Some(final_tr.weight);
+                }
+                MapFinalAction::MapAllowSuperfinal => {
+                    if has_labels {
+                        let superfinal = match state.superfinal {
+                            Some(superfinal) => superfinal,
+                            None => {
+                                // The states already created all have a smaller id.
+                                let superfinal = state.num_states as StateId;
+                                state.superfinal = Some(superfinal);
+                                state.num_states += 1;
+                                superfinal
+                            }
+                        };
+                        trs.push(Tr::new(
+                            final_tr.ilabel,
+                            final_tr.olabel,
+                            final_tr.weight,
+                            superfinal,
+                        ));
+                    } else {
+                        final_weight = Some(final_tr.weight);
+                    }
+                }
+                MapFinalAction::MapRequireSuperfinal => {
+                    if has_labels || !final_tr.weight.is_zero() {
+                        trs.push(Tr::new(
+                            final_tr.ilabel,
+                            final_tr.olabel,
+                            final_tr.weight,
+                            state.superfinal.unwrap(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok((TrsVec(Arc::new(trs)), final_weight))
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
+
+type InnerLazyFst<W, F, B, M> = LazyFst2<W, TrMapOp<W, F, B, M>, SimpleHashMapCache<W>>;
+
+/// Delayed version of `tr_map`: the trs and final weights of the input FST are mapped with
+/// the `TrMapper` only when the corresponding states are visited. The superfinal state
+/// possibly required by the mapper is created on demand.
+///
+/// Unlike `tr_map`, the input FST is not modified and can be borrowed.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::TrMapFst;
+/// # use rustfst::algorithms::tr_mappers::OutputEpsilonMapper;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::transducer;
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<TropicalWeight> = fst![1, 2 => 3, 4];
+/// let mapped_fst = TrMapFst::<_, VectorFst<_>, _, _>::new(&fst, OutputEpsilonMapper {})?;
+/// let mapped: VectorFst<_> = mapped_fst.compute()?;
+/// assert_eq!(mapped, fst![1, 2 => 0, 0]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TrMapFst<W: Semiring, F: Fst<W>, B: Borrow<F>, M: TrMapper<W>>(InnerLazyFst<W, F, B, M>);
+
+impl<W, F, B, M> CoreFst<W> for TrMapFst<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, M> StateIterator<'a> for TrMapFst<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    type Iter = <InnerLazyFst<W, F, B, M> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, M> FstIterator<'a, W> for TrMapFst<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    type FstIter = <InnerLazyFst<W, F, B, M> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, M> Fst<W> for TrMapFst<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, M> Debug for TrMapFst<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B, M> TrMapFst<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    /// The symbol tables of the input FST are kept.
+    pub fn new(fst: B, mapper: M) -> Result<Self> {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        Ok(Self::new_with_symts(fst, mapper, isymt, osymt))
+    }
+
+    pub(crate) fn new_with_symts(
+        fst: B,
+        mapper: M,
+        isymt: Option<Arc<SymbolTable>>,
+        osymt: Option<Arc<SymbolTable>>,
+    ) -> Self {
+        let fst_op = TrMapOp::new(fst, mapper);
+        let fst_cache = SimpleHashMapCache::default();
+        TrMapFst(LazyFst2::from_op_and_cache(fst_op, fst_cache, isymt, osymt))
+    }
+
+    pub(crate) fn mapper(&self) -> &M {
+        &self.0.op.mapper
+    }
+
+    pub(crate) fn into_mapper(self) -> M {
+        self.0.op.mapper
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithms::tr_map;
+    use crate::algorithms::tr_mappers::{IdentityTrMapper, PlusMapper};
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_tr_map_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<TrMapFst<TropicalWeight, VectorFst<_>, VectorFst<_>, IdentityTrMapper>>();
+    }
+
+    /// Marks each final state with a transition labeled 5 to a superfinal state.
+    struct FinalLabelMapper {}
+
+    impl TrMapper<TropicalWeight> for FinalLabelMapper {
+        fn tr_map(&self, _tr: &mut Tr<TropicalWeight>) -> Result<()> {
+            Ok(())
+        }
+
+        fn final_tr_map(&self, final_tr: &mut FinalTr<TropicalWeight>) -> Result<()> {
+            final_tr.ilabel = 5;
+            final_tr.olabel = 5;
+            Ok(())
+        }
+
+        fn final_action(&self) -> MapFinalAction {
+            MapFinalAction::MapAllowSuperfinal
+        }
+
+        fn properties(&self, _inprops: FstProperties) -> FstProperties {
+            FstProperties::empty()
+        }
+    }
+
+    fn fst_in() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 1.0, s1))?;
+        fst.add_tr(s0, Tr::new(3, 4, 2.0, s2))?;
+        fst.add_tr(s1, Tr::new(5, 6, 3.0, s2))?;
+        fst.set_final(s1, 0.5)?;
+        fst.set_final(s2, 1.5)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_tr_map_fst_no_superfinal() -> Result<()> {
+        let fst = fst_in()?;
+        let lazy: VectorFst<_> =
+            TrMapFst::<_, VectorFst<_>, _, _>::new(&fst, PlusMapper::new(0.2))?.compute()?;
+        let mut expected = fst;
+        tr_map(&mut expected, &PlusMapper::new(0.2))?;
+        assert_eq!(lazy, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_map_fst_allow_superfinal() -> Result<()> {
+        let fst = fst_in()?;
+        let lazy: VectorFst<_> =
+            TrMapFst::<_, VectorFst<_>, _, _>::new(&fst, FinalLabelMapper {})?.compute()?;
+
+        let mut expected = VectorFst::new();
+        let s0 = expected.add_state();
+        let s1 = expected.add_state();
+        let s2 = expected.add_state();
+        let s3 = expected.add_state();
+        expected.set_start(s0)?;
+        expected.add_tr(s0, Tr::new(1, 2, 1.0, s1))?;
+        expected.add_tr(s0, Tr::new(3, 4, 2.0, s2))?;
+        expected.add_tr(s1, Tr::new(5, 6, 3.0, s2))?;
+        expected.add_tr(s1, Tr::new(5, 5, 0.5, s3))?;
+        expected.add_tr(s2, Tr::new(5, 5, 1.5, s3))?;
+        expected.set_final(s3, TropicalWeight::one())?;
+        assert_eq!(lazy, expected);
+        Ok(())
+    }
+}
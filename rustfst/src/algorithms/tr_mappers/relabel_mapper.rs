@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+
+use crate::algorithms::relabel_pairs::iterator_to_hashmap;
+use crate::algorithms::{FinalTr, MapFinalAction, TrMapper, WeightConverter};
+use crate::fst_properties::mutable_properties::relabel_properties;
+use crate::fst_properties::FstProperties;
+use crate::semirings::Semiring;
+use crate::{Label, Tr};
+
+/// Mapper that replaces the labels of the transitions according to relabeling pairs, as
+/// `relabel_pairs`. The labels not present in the pairs are kept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelabelMapper {
+    map_ilabels: HashMap<Label, Label>,
+    map_olabels: HashMap<Label, Label>,
+}
+
+impl RelabelMapper {
+    /// Fails if a label is present twice in the input or in the output pairs.
+    pub fn new<I, J>(ipairs: I, opairs: J) -> Result<Self>
+    where
+        I: IntoIterator<Item = (Label, Label)>,
+        J: IntoIterator<Item = (Label, Label)>,
+    {
+        let map_ilabels = iterator_to_hashmap(ipairs)
+            .with_context(|| format_err!("Error while creating the HashMap for ipairs"))?;
+        let map_olabels = iterator_to_hashmap(opairs)
+            .with_context(|| format_err!("Error while creating the HashMap for opairs"))?;
+        Ok(Self {
+            map_ilabels,
+            map_olabels,
+        })
+    }
+}
+
+impl<S: Semiring> TrMapper<S> for RelabelMapper {
+    fn tr_map(&self, tr: &mut Tr<S>) -> Result<()> {
+        if let Some(ilabel) = self.map_ilabels.get(&tr.ilabel) {
+            tr.ilabel = *ilabel;
+        }
+        if let Some(olabel) = self.map_olabels.get(&tr.olabel) {
+            tr.olabel = *olabel;
+        }
+        Ok(())
+    }
+
+    fn final_tr_map(&self, _final_tr: &mut FinalTr<S>) -> Result<()> {
+        Ok(())
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+
+    fn properties(&self, inprops: FstProperties) -> FstProperties {
+        relabel_properties(inprops)
+    }
+}
+
+tr_mapper_to_weight_convert_mapper!(RelabelMapper);
@@ -0,0 +1,279 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstOp2, LazyFst2, SimpleHashMapCache};
+use crate::algorithms::tr_compares::TrCompare;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, Trs, TrsVec};
+
+pub struct TrSortOp<W: Semiring, F: Fst<W>, B: Borrow<F>, C: TrCompare> {
+    fst: B,
+    properties: FstProperties,
+    ghost: PhantomData<(W, F, C)>,
+}
+
+impl<W: Semiring, F: Fst<W>, B: Borrow<F>, C: TrCompare> Debug for TrSortOp<W, F, B, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrSortOp")
+            .field("fst", self.fst.borrow())
+            .field("properties", &self.properties)
+            .finish()
+    }
+}
+
+impl<W: Semiring, F: Fst<W>, B: Borrow<F>, C: TrCompare> TrSortOp<W, F, B, C> {
+    pub fn new(fst: B) -> Self {
+        let properties = C::properties(fst.borrow().properties());
+        Self {
+            fst,
+            properties,
+            ghost: PhantomData,
+        }
+    }
+}
+
+impl<W: Semiring, F: Fst<W>, B: Borrow<F>, C: TrCompare> FstOp2<W> for TrSortOp<W, F, B, C> {
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.borrow().start())
+    }
+
+    fn compute_trs_and_final_weight(&self, id: StateId) -> Result<(TrsVec<W>, Option<W>)> {
+        let fst = self.fst.borrow();
+        let mut trs = fst.get_trs(id)?.trs().to_vec();
+        trs.sort_by(C::compare);
+        Ok((TrsVec(Arc::new(trs)), fst.final_weight(id)?))
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
+
+type InnerLazyFst<W, F, B, C> = LazyFst2<W, TrSortOp<W, F, B, C>, SimpleHashMapCache<W>>;
+
+/// Delayed version of `tr_sort`: the trs leaving a state are sorted with the compare function
+/// when the state is visited. This is useful to prepare an FST for composition without
+/// copying it.
+///
+/// # Example
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::TrSortFst;
+/// # use rustfst::algorithms::tr_compares::ILabelCompare;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::{Tr, Trs};
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, TropicalWeight::new(0.0))?;
+/// fst.add_tr(s0, Tr::new(2, 2, 1.0, s1))?;
+/// fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+///
+/// let sorted_fst = TrSortFst::<_, VectorFst<_>, _, _>::new(&fst, ILabelCompare {})?;
+/// let trs = sorted_fst.get_trs(s0)?;
+/// assert_eq!(trs.trs()[0].ilabel, 1);
+/// assert_eq!(trs.trs()[1].ilabel, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TrSortFst<W: Semiring, F: Fst<W>, B: Borrow<F>, C: TrCompare>(InnerLazyFst<W, F, B, C>);
+
+impl<W, F, B, C> CoreFst<W> for TrSortFst<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, C> StateIterator<'a> for TrSortFst<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    type Iter = <InnerLazyFst<W, F, B, C> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, C> FstIterator<'a, W> for TrSortFst<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    type FstIter = <InnerLazyFst<W, F, B, C> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, C> Fst<W> for TrSortFst<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, C> Debug for TrSortFst<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<W, F, B, C> TrSortFst<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    // As in `tr_sort`, the compare function is passed as a value for readability.
+    pub fn new(fst: B, _comp: C) -> Result<Self> {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let fst_op = TrSortOp::new(fst);
+        let fst_cache = SimpleHashMapCache::default();
+        let lazy_fst = LazyFst2::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
+        Ok(TrSortFst(lazy_fst))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithms::tr_compares::{ILabelCompare, OLabelCompare};
+    use crate::algorithms::tr_sort;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    #[test]
+    fn test_tr_sort_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<TrSortFst<TropicalWeight, VectorFst<_>, VectorFst<_>, ILabelCompare>>();
+    }
+
+    #[test]
+    fn test_tr_sort_fst() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(3, 1, 1.0, s1))?;
+        fst.add_tr(s0, Tr::new(1, 2, 2.0, s1))?;
+        fst.add_tr(s0, Tr::new(2, 3, 3.0, s0))?;
+        fst.add_tr(s1, Tr::new(2, 1, 1.0, s1))?;
+        fst.add_tr(s1, Tr::new(1, 2, 1.0, s0))?;
+        fst.set_final(s1, 0.5)?;
+
+        let lazy: VectorFst<_> =
+            TrSortFst::<_, VectorFst<_>, _, _>::new(&fst, ILabelCompare {})?.compute()?;
+        let mut expected = fst.clone();
+        tr_sort(&mut expected, ILabelCompare {});
+        assert_eq!(lazy, expected);
+        assert_eq!(lazy.properties(), expected.properties());
+
+        let lazy: VectorFst<_> =
+            TrSortFst::<_, VectorFst<_>, _, _>::new(&fst, OLabelCompare {})?.compute()?;
+        let mut expected = fst;
+        tr_sort(&mut expected, OLabelCompare {});
+        assert_eq!(lazy, expected);
+        assert_eq!(lazy.properties(), expected.properties());
+        Ok(())
+    }
+}
@@ -74,7 +74,7 @@ impl<'a, W: Semiring, F: 'a + ExpandedFst<W>> Visitor<'a, W, F> for SccVisitor<'
             }
         } else {
             if let Some(ref mut access) = self.access {
-                access[s] = true;
+                access[s] = false;
             }
             self.props |= FstProperties::NOT_ACCESSIBLE;
             self.props &= !FstProperties::ACCESSIBLE;
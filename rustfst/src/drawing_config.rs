@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{FstPath, SymbolTable};
+
 /// Struct to configure how the FST should be drawn.
 #[derive(Debug, Clone, PartialEq)]
-pub struct DrawingConfig {
+pub struct DrawingConfig<W: Semiring = TropicalWeight> {
     /// Draw bottom-to-top instead of left-to-right.
     pub vertical: bool,
     /// Set pair (width, height)
@@ -21,9 +26,24 @@ pub struct DrawingConfig {
     pub show_weight_one: bool,
     /// Print/draw transition weights and final weights.
     pub print_weight: bool,
+    /// State label symbol table. If `None`, the state ids are drawn.
+    pub ssymbols: Option<Arc<SymbolTable>>,
+    /// Maximum number of states to draw, the start state first and then the others in
+    /// increasing order. The transitions to the states not drawn lead to a `...` node.
+    pub max_states: Option<usize>,
+    /// Path whose states and transitions are drawn with `highlight_color`. All the
+    /// successful paths with the labels of the path are highlighted, whatever their weight.
+    pub highlight_path: Option<FstPath<W>>,
+    /// Color of the highlighted states and transitions (see dot documentation).
+    pub highlight_color: String,
+    /// Number of decimals of the weights. If `None`, the weights are drawn as displayed.
+    pub float_precision: Option<usize>,
+    /// Draw the labels that are negative once read as 32 bits signed integers, as OpenFST
+    /// uses them, instead of failing.
+    pub allow_negative_labels: bool,
 }
 
-impl Default for DrawingConfig {
+impl<W: Semiring> Default for DrawingConfig<W> {
     fn default() -> Self {
         Self {
             vertical: false,
@@ -36,6 +56,12 @@ impl Default for DrawingConfig {
             acceptor: false,
             show_weight_one: true,
             print_weight: true,
+            ssymbols: None,
+            max_states: None,
+            highlight_path: None,
+            highlight_color: "red".to_string(),
+            float_precision: None,
+            allow_negative_labels: false,
         }
     }
 }
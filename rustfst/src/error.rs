@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::fst_properties::FstProperties;
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
+use crate::semirings::{DivideType, SemiringProperties};
+use crate::StateId;
+
+/// Typed failure modes of the library. The functions of rustfst return `anyhow::Result` : these
+/// errors are wrapped in the `anyhow::Error` and can be retrieved with `Error::find`, so that
+/// callers can match on them while the `?` chains and the contexts keep working.
+///
+/// The public signatures are not migrated to `Result<T, Error>` : the failures that have no
+/// variant yet, e.g invalid configurations or symbols missing from a table, are only described
+/// by their message. New variants are added as the failure modes are typed, hence
+/// `#[non_exhaustive]`.
+///
+/// # Example
+/// ```
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::semirings::TropicalWeight;
+/// # use rustfst::Error;
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let err = fst.set_start(3).unwrap_err();
+/// assert_eq!(Error::find(&err), Some(Error::StateOutOfRange { state: 3 }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The state is not a state of the FST.
+    StateOutOfRange { state: StateId },
+    /// The division is not defined, e.g a left division in a semiring which is only weakly
+    /// right divisible.
+    UnsupportedDivision { divide_type: DivideType },
+    /// Division by the zero of the semiring.
+    DivisionByZero,
+    /// The operation only supports acceptors.
+    NotAcceptor { operation: &'static str },
+    /// The operation requires FST properties that the FST doesn't have.
+    MissingFstProperties {
+        operation: &'static str,
+        properties: FstProperties,
+    },
+    /// The operation requires semiring properties that the weight doesn't have.
+    MissingSemiringProperties {
+        operation: &'static str,
+        properties: SemiringProperties,
+    },
+    /// A binary FST, symbol table or encode table can't be read.
+    Read(FstReadError),
+    /// A line of an FST in text format can't be parsed. The column is the position in
+    /// characters of the faulty field, the cause of the error is its source.
+    TextParse { line: usize, column: usize },
+    /// The algorithm has been cancelled with `Progress::cancel`.
+    Cancelled,
+    /// The output of the algorithm exceeds one of its `ResourceLimits`.
+    ResourceLimitExceeded {
+        resource: &'static str,
+        limit: usize,
+    },
+    /// The output of the determinization has more states than its `state_threshold` and
+    /// partial results are not allowed.
+    DeterminizeStateThreshold { state_threshold: StateId },
+}
+
+impl Error {
+    /// Retrieves the typed error wrapped in an error returned by rustfst, whatever the context
+    /// added to it. The `FstReadError` of the binary parsers are returned as `Error::Read`.
+    /// Returns `None` for the errors that have no variant yet.
+    pub fn find(error: &anyhow::Error) -> Option<Error> {
+        // The errors added as context are only found by the downcast of the `anyhow::Error`.
+        error.downcast_ref::<Error>().cloned().or_else(|| {
+            error.chain().find_map(|e| {
+                e.downcast_ref::<Error>()
+                    .cloned()
+                    .or_else(|| e.downcast_ref::<FstReadError>().cloned().map(Error::Read))
+            })
+        })
+    }
+}
+
+impl From<FstReadError> for Error {
+    fn from(e: FstReadError) -> Self {
+        Error::Read(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::StateOutOfRange { state } => write!(f, "State {:?} doesn't exist", state),
+            Error::UnsupportedDivision { divide_type } => {
+                write!(
+                    f,
+                    "Division {:?} is not defined for this semiring",
+                    divide_type
+                )
+            }
+            Error::DivisionByZero => write!(f, "Division by 0"),
+            Error::NotAcceptor { operation } => {
+                write!(f, "{} : expected acceptor as argument", operation)
+            }
+            Error::MissingFstProperties {
+                operation,
+                properties,
+            } => write!(
+                f,
+                "{} : the FST needs to have the properties {:?}",
+                operation, properties
+            ),
+            Error::MissingSemiringProperties {
+                operation,
+                properties,
+            } => write!(
+                f,
+                "{} : the weight needs to have the properties {:?}",
+                operation, properties
+            ),
+            Error::Read(e) => write!(f, "{}", e),
+            Error::TextParse { line, column } => {
+                write!(f, "Text FST : line {}, column {}", line, column)
+            }
+            Error::Cancelled => write!(f, "The algorithm has been cancelled"),
+            Error::ResourceLimitExceeded { resource, limit } => {
+                write!(f, "The limit of {} {} has been exceeded", limit, resource)
+            }
+            Error::DeterminizeStateThreshold { state_threshold } => write!(
+                f,
+                "The number of states of the determinization exceeds the state threshold {}",
+                state_threshold
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Read(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, MutableFst, SerializableFst};
+    use crate::parsers::text_fst::TextFstParseConfig;
+    use crate::semirings::{Semiring, StringWeightLeft, TropicalWeight, WeaklyDivisibleSemiring};
+    use anyhow::{Context, Result};
+
+    #[test]
+    fn test_find_error() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s = fst.add_state();
+        let err = fst
+            .get_trs(s + 1)
+            .context("Can't get the transitions")
+            .unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::StateOutOfRange { state: s + 1 })
+        );
+
+        let err = StringWeightLeft::one()
+            .divide(&StringWeightLeft::one(), DivideType::DivideRight)
+            .unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::UnsupportedDivision {
+                divide_type: DivideType::DivideRight
+            })
+        );
+
+        let err = VectorFst::<TropicalWeight>::load(&[0, 1]).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::Read(_))));
+        let err = VectorFst::<TropicalWeight>::from_text_string_with_config(
+            "0 1 2 3\n1 x\n",
+            &TextFstParseConfig::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            Error::find(&err),
+            Some(Error::TextParse { line: 2, column: 3 })
+        );
+        assert_eq!(Error::find(&format_err!("Other error")), None);
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
-use anyhow::{format_err, Result};
+use anyhow::Result;
 
 use crate::fst_impls::ConstFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, Fst};
 use crate::semirings::Semiring;
-use crate::{StateId, SymbolTable, TrsConst};
+use crate::{Error, StateId, SymbolTable, TrsConst};
 
 impl<W: Semiring> Fst<W> for ConstFst<W> {
     fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
@@ -45,7 +45,7 @@ impl<W: Semiring> CoreFst<W> for ConstFst<W> {
         let s = self
             .states
             .get(state_id as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
+            .ok_or(Error::StateOutOfRange { state: state_id })?;
         Ok(s.final_weight.clone())
     }
 
@@ -60,7 +60,7 @@ impl<W: Semiring> CoreFst<W> for ConstFst<W> {
         Ok(self
             .states
             .get(s as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", s))?
+            .ok_or(Error::StateOutOfRange { state: s })?
             .ntrs)
     }
 
@@ -72,7 +72,7 @@ impl<W: Semiring> CoreFst<W> for ConstFst<W> {
         let state = self
             .states
             .get(state_id as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
+            .ok_or(Error::StateOutOfRange { state: state_id })?;
         Ok(TrsConst {
             trs: Arc::clone(&self.trs),
             pos: state.pos,
@@ -97,7 +97,7 @@ impl<W: Semiring> CoreFst<W> for ConstFst<W> {
         Ok(self
             .states
             .get(state as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))?
+            .ok_or(Error::StateOutOfRange { state })?
             .niepsilons)
     }
 
@@ -105,7 +105,7 @@ impl<W: Semiring> CoreFst<W> for ConstFst<W> {
         Ok(self
             .states
             .get(state as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))?
+            .ok_or(Error::StateOutOfRange { state })?
             .noepsilons)
     }
 }
@@ -3,9 +3,6 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use itertools::Itertools;
-use nom::bytes::complete::take;
-use nom::multi::count;
-use nom::IResult;
 
 use crate::fst_impls::const_fst::data_structure::ConstState;
 use crate::fst_impls::const_fst::{
@@ -14,12 +11,12 @@ use crate::fst_impls::const_fst::{
 use crate::fst_impls::ConstFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{ExpandedFst, Fst, SerializableFst};
+use crate::parsers::bin_fst::bin_reader::BinReader;
 use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
 use crate::parsers::bin_fst::utils_parsing::{
-    parse_bin_fst_tr, parse_final_weight, parse_start_state,
+    parse_final_weight, parse_start_state, read_bin_fst_tr, MIN_BIN_FST_TR_SIZE,
 };
-use crate::parsers::nom_utils::NomCustomError;
-use crate::parsers::parse_bin_i32;
 use crate::parsers::text_fst::ParsedTextFst;
 use crate::parsers::write_bin_i32;
 use crate::semirings::SerializableSemiring;
@@ -31,56 +28,12 @@ impl<W: SerializableSemiring> SerializableFst<W> for ConstFst<W> {
     }
 
     fn load(data: &[u8]) -> Result<Self> {
-        let (_, parsed_fst) = parse_const_fst(data)
-            .map_err(|_| format_err!("Error while parsing binary ConstFst"))?;
-
-        Ok(parsed_fst)
+        read_const_fst(data)
+            .map_err(|e| anyhow::Error::new(e).context("Error while parsing binary ConstFst"))
     }
 
-    fn store<O: Write>(&self, mut output: O) -> Result<()> {
-        let mut flags = FstFlags::empty();
-        if self.input_symbols().is_some() {
-            flags |= FstFlags::HAS_ISYMBOLS;
-        }
-        if self.output_symbols().is_some() {
-            flags |= FstFlags::HAS_OSYMBOLS;
-        }
-
-        let hdr = FstHeader {
-            magic_number: FST_MAGIC_NUMBER,
-            fst_type: OpenFstString::new(Self::fst_type()),
-            tr_type: OpenFstString::new(Tr::<W>::tr_type()),
-            version: CONST_FILE_VERSION,
-            // TODO: Set flags if the content is aligned
-            flags,
-            properties: self.properties.bits() | ConstFst::<W>::static_properties(),
-            start: self.start.map(|v| v as i64).unwrap_or(-1),
-            num_states: self.num_states() as i64,
-            num_trs: self.trs.len() as i64,
-            isymt: self.input_symbols().cloned(),
-            osymt: self.output_symbols().cloned(),
-        };
-        hdr.write(&mut output)?;
-
-        let zero = W::zero();
-        for const_state in &self.states {
-            let f_weight = const_state.final_weight.as_ref().unwrap_or(&zero);
-            f_weight.write_binary(&mut output)?;
-
-            write_bin_i32(&mut output, const_state.pos as i32)?;
-            write_bin_i32(&mut output, const_state.ntrs as i32)?;
-            write_bin_i32(&mut output, const_state.niepsilons as i32)?;
-            write_bin_i32(&mut output, const_state.noepsilons as i32)?;
-        }
-
-        for tr in &*self.trs {
-            write_bin_i32(&mut output, tr.ilabel as i32)?;
-            write_bin_i32(&mut output, tr.olabel as i32)?;
-            tr.weight.write_binary(&mut output)?;
-            write_bin_i32(&mut output, tr.nextstate as i32)?;
-        }
-
-        Ok(())
+    fn store<O: Write>(&self, output: O) -> Result<()> {
+        self.store_with_alignment(output, false)
     }
 
     fn from_parsed_fst_text(mut parsed_fst_text: ParsedTextFst<W>) -> Result<Self> {
@@ -175,63 +128,189 @@ impl<W: SerializableSemiring> SerializableFst<W> for ConstFst<W> {
     }
 }
 
-fn parse_const_state<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], ConstState<W>, NomCustomError<&[u8]>> {
-    let (i, final_weight) = W::parse_binary(i)?;
-    let (i, pos) = parse_bin_i32(i)?;
-    let (i, ntrs) = parse_bin_i32(i)?;
-    let (i, niepsilons) = parse_bin_i32(i)?;
-    let (i, noepsilons) = parse_bin_i32(i)?;
-
-    Ok((
-        i,
-        ConstState {
-            final_weight: parse_final_weight(final_weight),
-            pos: pos as usize,
-            ntrs: ntrs as usize,
-            niepsilons: niepsilons as usize,
-            noepsilons: noepsilons as usize,
-        },
-    ))
+impl<W: SerializableSemiring> ConstFst<W> {
+    /// Stores the FST in the aligned binary format, like OpenFST does with `--fst_align` :
+    /// version 1 of the format with the `IS_ALIGNED` flag set, where the states and the
+    /// transitions start at offsets that are multiples of 16 bytes.
+    pub fn store_aligned<O: Write>(&self, output: O) -> Result<()> {
+        self.store_with_alignment(output, true)
+    }
+
+    fn store_with_alignment<O: Write>(&self, output: O, aligned: bool) -> Result<()> {
+        let mut output = PositionWriter {
+            inner: output,
+            position: 0,
+        };
+        let mut flags = FstFlags::empty();
+        if self.input_symbols().is_some() {
+            flags |= FstFlags::HAS_ISYMBOLS;
+        }
+        if self.output_symbols().is_some() {
+            flags |= FstFlags::HAS_OSYMBOLS;
+        }
+        if aligned {
+            flags |= FstFlags::IS_ALIGNED;
+        }
+
+        let hdr = FstHeader {
+            magic_number: FST_MAGIC_NUMBER,
+            fst_type: OpenFstString::new(Self::fst_type()),
+            tr_type: OpenFstString::new(Tr::<W>::tr_type()),
+            version: if aligned {
+                CONST_ALIGNED_FILE_VERSION
+            } else {
+                CONST_FILE_VERSION
+            },
+            flags,
+            properties: self.properties.bits() | ConstFst::<W>::static_properties(),
+            start: self.start.map(|v| v as i64).unwrap_or(-1),
+            num_states: self.num_states() as i64,
+            num_trs: self.trs.len() as i64,
+            isymt: self.input_symbols().cloned(),
+            osymt: self.output_symbols().cloned(),
+        };
+        hdr.write(&mut output)?;
+
+        if aligned {
+            output.align(CONST_ARCH_ALIGNMENT)?;
+        }
+        let zero = W::zero();
+        for const_state in &self.states {
+            let f_weight = const_state.final_weight.as_ref().unwrap_or(&zero);
+            f_weight.write_binary(&mut output)?;
+
+            write_bin_i32(&mut output, const_state.pos as i32)?;
+            write_bin_i32(&mut output, const_state.ntrs as i32)?;
+            write_bin_i32(&mut output, const_state.niepsilons as i32)?;
+            write_bin_i32(&mut output, const_state.noepsilons as i32)?;
+        }
+
+        if aligned {
+            output.align(CONST_ARCH_ALIGNMENT)?;
+        }
+        for tr in &*self.trs {
+            write_bin_i32(&mut output, tr.ilabel as i32)?;
+            write_bin_i32(&mut output, tr.olabel as i32)?;
+            tr.weight.write_binary(&mut output)?;
+            write_bin_i32(&mut output, tr.nextstate as i32)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writer keeping track of the number of bytes written to pad the output.
+struct PositionWriter<O: Write> {
+    inner: O,
+    position: usize,
+}
+
+impl<O: Write> PositionWriter<O> {
+    fn align(&mut self, alignment: usize) -> Result<()> {
+        if !self.position.is_multiple_of(alignment) {
+            let padding = vec![0u8; alignment - self.position % alignment];
+            self.write_all(&padding)?;
+        }
+        Ok(())
+    }
+}
+
+impl<O: Write> Write for PositionWriter<O> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.position += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
 }
 
-fn parse_const_fst<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], ConstFst<W>, NomCustomError<&[u8]>> {
-    let stream_len = i.len();
+/// Size in bytes of a state without its final weight : its position in the transition array,
+/// its number of transitions and its numbers of input and output epsilons.
+static MIN_CONST_STATE_SIZE: usize = 16;
+
+fn read_const_state<W: SerializableSemiring>(
+    reader: &mut BinReader,
+    state: usize,
+    num_trs: i64,
+) -> Result<ConstState<W>, FstReadError> {
+    let offset = reader.offset();
+    let final_weight = reader.parse(W::parse_binary)?;
+    let pos = reader.read_i32()?;
+    let ntrs = reader.read_i32()?;
+    let niepsilons = reader.read_i32()?;
+    let noepsilons = reader.read_i32()?;
+    let (pos, ntrs) = (i64::from(pos), i64::from(ntrs));
+    if pos < 0 || ntrs < 0 || pos + ntrs > num_trs {
+        return Err(FstReadError::InvalidTrsRange {
+            offset,
+            state,
+            pos,
+            ntrs,
+            num_trs,
+        });
+    }
+
+    Ok(ConstState {
+        final_weight: parse_final_weight(final_weight),
+        pos: pos as usize,
+        ntrs: ntrs as usize,
+        niepsilons: niepsilons as usize,
+        noepsilons: noepsilons as usize,
+    })
+}
 
-    let (mut i, hdr) = FstHeader::parse(
-        i,
+fn read_const_fst<W: SerializableSemiring>(data: &[u8]) -> Result<ConstFst<W>, FstReadError> {
+    let mut reader = BinReader::new(data);
+    let hdr = FstHeader::read(
+        &mut reader,
         CONST_MIN_FILE_VERSION,
         ConstFst::<W>::fst_type(),
         Tr::<W>::tr_type(),
     )?;
-    let aligned = hdr.version == CONST_ALIGNED_FILE_VERSION;
-    let pos = stream_len - i.len();
+    // OpenFST marks the aligned files with both the aligned version and the `IS_ALIGNED` flag,
+    // either of them is enough to read the file as aligned.
+    let aligned =
+        hdr.version == CONST_ALIGNED_FILE_VERSION || hdr.flags.contains(FstFlags::IS_ALIGNED);
 
     // Align input
-    if aligned && hdr.num_states > 0 && pos % CONST_ARCH_ALIGNMENT > 0 {
-        i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
+    if aligned && hdr.num_states > 0 {
+        reader.align(CONST_ARCH_ALIGNMENT)?;
+    }
+    let num_states = reader.check_count(
+        "number of states",
+        hdr.num_states,
+        MIN_CONST_STATE_SIZE,
+        reader.offset(),
+    )?;
+    let mut const_states = Vec::with_capacity(num_states);
+    for state in 0..num_states {
+        const_states.push(read_const_state(&mut reader, state, hdr.num_trs)?);
     }
-    let (mut i, const_states) = count(parse_const_state, hdr.num_states as usize)(i)?;
-    let pos = stream_len - i.len();
 
     // Align input
-    if aligned && hdr.num_trs > 0 && pos % CONST_ARCH_ALIGNMENT > 0 {
-        i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
+    if aligned && hdr.num_trs > 0 {
+        reader.align(CONST_ARCH_ALIGNMENT)?;
+    }
+    let num_trs = reader.check_count(
+        "number of transitions",
+        hdr.num_trs,
+        MIN_BIN_FST_TR_SIZE,
+        reader.offset(),
+    )?;
+    let mut const_trs = Vec::with_capacity(num_trs);
+    for _ in 0..num_trs {
+        const_trs.push(read_bin_fst_tr(&mut reader, hdr.num_states)?);
     }
-    let (i, const_trs) = count(parse_bin_fst_tr, hdr.num_trs as usize)(i)?;
 
-    Ok((
-        i,
-        ConstFst {
-            start: parse_start_state(hdr.start),
-            states: const_states,
-            trs: Arc::new(const_trs),
-            isymt: hdr.isymt,
-            osymt: hdr.osymt,
-            properties: FstProperties::from_bits_truncate(hdr.properties),
-        },
-    ))
+    Ok(ConstFst {
+        start: parse_start_state(hdr.start),
+        states: const_states,
+        trs: Arc::new(const_trs),
+        isymt: hdr.isymt,
+        osymt: hdr.osymt,
+        properties: FstProperties::from_bits_truncate(hdr.properties),
+    })
 }
@@ -1,5 +1,7 @@
 mod arc;
 pub(crate) mod const_fst;
+#[cfg(feature = "serde")]
+mod serde_fst;
 pub(crate) mod vector_fst;
 
 pub use self::const_fst::ConstFst;
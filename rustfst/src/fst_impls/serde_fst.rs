@@ -0,0 +1,197 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::{Fst, MutableFst};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, Tr, Trs};
+
+/// Serde representation shared by `VectorFst` and `ConstFst`. The schema is described in the
+/// documentation of the crate. The data is borrowed from the FST when serializing.
+#[derive(Serialize, Deserialize)]
+struct SerdeFst<'a, W: Clone> {
+    start: Option<StateId>,
+    states: Vec<SerdeFstState<'a, W>>,
+    isymt: Option<Cow<'a, SymbolTable>>,
+    osymt: Option<Cow<'a, SymbolTable>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerdeFstState<'a, W: Clone> {
+    final_weight: Option<Cow<'a, W>>,
+    trs: Cow<'a, [Tr<W>]>,
+}
+
+impl<'a, W: Semiring> SerdeFst<'a, W> {
+    fn into_vector_fst(self) -> Result<VectorFst<W>> {
+        let num_states = self.states.len();
+        let mut fst = VectorFst::new();
+        fst.add_states(num_states);
+        for (state, serde_state) in self.states.into_iter().enumerate() {
+            let state = state as StateId;
+            for tr in serde_state.trs.into_owned() {
+                if tr.nextstate as usize >= num_states {
+                    bail!(
+                        "Transition from state {} to invalid state {}, the FST has {} states",
+                        state,
+                        tr.nextstate,
+                        num_states
+                    );
+                }
+                fst.add_tr(state, tr)?;
+            }
+            if let Some(final_weight) = serde_state.final_weight {
+                fst.set_final(state, final_weight.into_owned())?;
+            }
+        }
+        if let Some(start) = self.start {
+            fst.set_start(start)?;
+        }
+        if let Some(isymt) = self.isymt {
+            fst.set_input_symbols(Arc::new(isymt.into_owned()));
+        }
+        if let Some(osymt) = self.osymt {
+            fst.set_output_symbols(Arc::new(osymt.into_owned()));
+        }
+        Ok(fst)
+    }
+}
+
+impl<W: Semiring + Serialize> Serialize for VectorFst<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerdeFst {
+            start: self.start_state,
+            states: self
+                .states
+                .iter()
+                .map(|s| SerdeFstState {
+                    final_weight: s.final_weight.as_ref().map(Cow::Borrowed),
+                    trs: Cow::Borrowed(s.trs.trs()),
+                })
+                .collect(),
+            isymt: self.isymt.as_deref().map(Cow::Borrowed),
+            osymt: self.osymt.as_deref().map(Cow::Borrowed),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, W: Semiring + Deserialize<'de>> Deserialize<'de> for VectorFst<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SerdeFst::<W>::deserialize(deserializer)?
+            .into_vector_fst()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl<W: Semiring + Serialize> Serialize for ConstFst<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerdeFst {
+            start: self.start,
+            states: self
+                .states
+                .iter()
+                .map(|s| SerdeFstState {
+                    final_weight: s.final_weight.as_ref().map(Cow::Borrowed),
+                    trs: Cow::Borrowed(&self.trs[s.pos..s.pos + s.ntrs]),
+                })
+                .collect(),
+            isymt: self.isymt.as_deref().map(Cow::Borrowed),
+            osymt: self.osymt.as_deref().map(Cow::Borrowed),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, W: Semiring + Deserialize<'de>> Deserialize<'de> for ConstFst<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SerdeFst::<W>::deserialize(deserializer)?
+            .into_vector_fst()
+            .map(ConstFst::from)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semirings::{StringWeightLeft, TropicalWeight};
+    use crate::symt;
+
+    fn build_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 0.5, s1))?;
+        fst.add_tr(s0, Tr::new(2, 0, TropicalWeight::zero(), s0))?;
+        fst.set_final(s1, 1.5)?;
+        fst.set_input_symbols(Arc::new(symt!["a", "b"]));
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_serde_fst_schema() -> Result<()> {
+        let fst = build_fst()?;
+        let json = serde_json::to_value(&fst)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "start": 0,
+                "states": [
+                    {
+                        "final_weight": null,
+                        "trs": [
+                            {"ilabel": 1, "olabel": 2, "weight": 0.5, "nextstate": 1},
+                            {"ilabel": 2, "olabel": 0, "weight": "Infinity", "nextstate": 0}
+                        ]
+                    },
+                    {"final_weight": 1.5, "trs": []}
+                ],
+                "isymt": {"symbols": ["<eps>", "a", "b"]},
+                "osymt": null
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_fst_round_trip() -> Result<()> {
+        let fst = build_fst()?;
+        let json = serde_json::to_string(&fst)?;
+
+        let vector_fst: VectorFst<TropicalWeight> = serde_json::from_str(&json)?;
+        assert_eq!(vector_fst, fst);
+        assert_eq!(vector_fst.input_symbols(), fst.input_symbols());
+        assert!(vector_fst.output_symbols().is_none());
+
+        let const_fst: ConstFst<TropicalWeight> = serde_json::from_str(&json)?;
+        assert_eq!(const_fst, ConstFst::from(fst.clone()));
+        assert_eq!(serde_json::to_string(&const_fst)?, json);
+
+        let mut string_fst = VectorFst::<StringWeightLeft>::new();
+        let s0 = string_fst.add_state();
+        string_fst.set_start(s0)?;
+        string_fst.add_tr(s0, Tr::new(1, 1, vec![1, 2], s0))?;
+        string_fst.set_final(s0, StringWeightLeft::zero())?;
+        let json = serde_json::to_string(&string_fst)?;
+        let parsed: VectorFst<StringWeightLeft> = serde_json::from_str(&json)?;
+        assert_eq!(parsed, string_fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_fst_invalid_states() {
+        let json = r#"{"start": 0, "states": [{"final_weight": null, "trs": [
+            {"ilabel": 1, "olabel": 1, "weight": 0.0, "nextstate": 3}]}],
+            "isymt": null, "osymt": null}"#;
+        assert!(serde_json::from_str::<VectorFst<TropicalWeight>>(json).is_err());
+
+        let json = r#"{"start": 2, "states": [], "isymt": null, "osymt": null}"#;
+        assert!(serde_json::from_str::<ConstFst<TropicalWeight>>(json).is_err());
+    }
+}
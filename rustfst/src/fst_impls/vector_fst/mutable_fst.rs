@@ -14,7 +14,7 @@ use crate::fst_traits::CoreFst;
 use crate::fst_traits::MutableFst;
 use crate::semirings::Semiring;
 use crate::trs_iter_mut::TrsIterMut;
-use crate::{StateId, Tr, Trs, EPS_LABEL};
+use crate::{Error, StateId, Tr, Trs, EPS_LABEL};
 
 #[inline]
 fn equal_tr<W: Semiring>(tr_1: &Tr<W>, tr_2: &Tr<W>) -> bool {
@@ -35,8 +35,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
     fn set_start(&mut self, state_id: StateId) -> Result<()> {
         ensure!(
             self.states.get(state_id as usize).is_some(),
-            "The state {:?} doesn't exist",
-            state_id
+            Error::StateOutOfRange { state: state_id }
         );
         self.start_state = Some(state_id);
         self.properties = set_start_properties(self.properties);
@@ -59,7 +58,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
             state.final_weight = Some(new_final_weight);
             Ok(())
         } else {
-            bail!("Stateid {:?} doesn't exist", state_id);
+            bail!(Error::StateOutOfRange { state: state_id });
         }
     }
 
@@ -94,7 +93,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = self
             .states
             .get_mut(state_id as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
+            .ok_or(Error::StateOutOfRange { state: state_id })?;
         let trs = Arc::make_mut(&mut state.trs.0);
         Ok(TrsIterMut::new(
             trs,
@@ -121,8 +120,9 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
 
         ensure!(
             (state_to_remove as usize) < self.states.len(),
-            "State id {:?} doesn't exist",
-            state_to_remove
+            Error::StateOutOfRange {
+                state: state_to_remove
+            }
         );
         self.properties = delete_states_properties(self.properties);
         let v = vec![state_to_remove];
@@ -237,7 +237,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?;
+            .ok_or(Error::StateOutOfRange { state: source })?;
         state.increment_num_epsilons(&tr);
         state.trs.push(tr);
         self.update_properties_after_add_tr(source);
@@ -285,7 +285,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
             self.properties = set_final_properties(self.properties, s.final_weight.as_ref(), None);
             s.final_weight = None;
         } else {
-            bail!("State {:?} doesn't exist", source)
+            bail!(Error::StateOutOfRange { state: source })
         }
         Ok(())
     }
@@ -300,7 +300,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?;
+            .ok_or(Error::StateOutOfRange { state: source })?;
 
         state.trs.clear();
         state.niepsilons = 0;
@@ -313,7 +313,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = &mut self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?;
+            .ok_or(Error::StateOutOfRange { state: source })?;
 
         let v = Arc::make_mut(&mut state.trs.0).drain(..).collect();
         state.niepsilons = 0;
@@ -334,7 +334,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let s = self
             .states
             .get_mut(state_id as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
+            .ok_or(Error::StateOutOfRange { state: state_id })?;
 
         self.properties = set_final_properties(self.properties, s.final_weight.as_ref(), None);
         Ok(s.final_weight.take())
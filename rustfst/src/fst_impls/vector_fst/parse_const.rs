@@ -3,10 +3,6 @@ use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use nom::bytes::complete::take;
-use nom::multi::count;
-use nom::number::complete::le_i32;
-use nom::IResult;
 
 use crate::fst_impls::const_fst::{
     CONST_ALIGNED_FILE_VERSION, CONST_ARCH_ALIGNMENT, CONST_MIN_FILE_VERSION,
@@ -15,11 +11,12 @@ use crate::fst_impls::vector_fst::VectorFstState;
 use crate::fst_impls::{ConstFst, VectorFst};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::SerializableFst;
-use crate::parsers::bin_fst::fst_header::FstHeader;
+use crate::parsers::bin_fst::bin_reader::BinReader;
+use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader};
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
 use crate::parsers::bin_fst::utils_parsing::{
-    parse_bin_fst_tr, parse_final_weight, parse_start_state,
+    parse_final_weight, parse_start_state, read_bin_fst_tr, MIN_BIN_FST_TR_SIZE,
 };
-use crate::parsers::nom_utils::NomCustomError;
 use crate::semirings::SerializableSemiring;
 use crate::{Tr, TrsVec};
 
@@ -33,71 +30,80 @@ impl<W: SerializableSemiring> VectorFst<W> {
             )
         })?;
 
-        let (_, parsed_fst) = parse_const_fst(&data)
-            .map_err(|_| format_err!("Error while parsing binary ConstFst file as a VectorFst"))?;
-
-        Ok(parsed_fst)
+        read_const_fst(&data).map_err(|e| {
+            anyhow::Error::new(e).context("Error while parsing binary ConstFst file as a VectorFst")
+        })
     }
 }
 
 struct TempState<W> {
     final_weight: Option<W>,
-    ntrs: usize,
+    ntrs: i64,
+    ntrs_offset: usize,
     niepsilons: usize,
     noepsilons: usize,
 }
 
-fn parse_const_state<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], TempState<W>, NomCustomError<&[u8]>> {
-    let (i, final_weight) = W::parse_binary(i)?;
-    let (i, _pos) = le_i32(i)?;
-    let (i, ntrs) = le_i32(i)?;
-    let (i, niepsilons) = le_i32(i)?;
-    let (i, noepsilons) = le_i32(i)?;
+fn read_const_state<W: SerializableSemiring>(
+    reader: &mut BinReader,
+) -> Result<TempState<W>, FstReadError> {
+    let final_weight = reader.parse(W::parse_binary)?;
+    let _pos = reader.read_i32()?;
+    let ntrs_offset = reader.offset();
+    let ntrs = reader.read_i32()?;
+    let niepsilons = reader.read_i32()?;
+    let noepsilons = reader.read_i32()?;
 
-    Ok((
-        i,
-        TempState {
-            final_weight: parse_final_weight(final_weight),
-            ntrs: ntrs as usize,
-            niepsilons: niepsilons as usize,
-            noepsilons: noepsilons as usize,
-        },
-    ))
+    Ok(TempState {
+        final_weight: parse_final_weight(final_weight),
+        ntrs: i64::from(ntrs),
+        ntrs_offset,
+        niepsilons: niepsilons as usize,
+        noepsilons: noepsilons as usize,
+    })
 }
 
-fn parse_const_fst<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], VectorFst<W>, NomCustomError<&[u8]>> {
-    let stream_len = i.len();
-
-    let (mut i, hdr) = FstHeader::parse(
-        i,
+fn read_const_fst<W: SerializableSemiring>(data: &[u8]) -> Result<VectorFst<W>, FstReadError> {
+    let mut reader = BinReader::new(data);
+    let hdr = FstHeader::read(
+        &mut reader,
         CONST_MIN_FILE_VERSION,
         // Intentional as the ConstFst file is being parsed.
         ConstFst::<W>::fst_type(),
         Tr::<W>::tr_type(),
     )?;
-    let aligned = hdr.version == CONST_ALIGNED_FILE_VERSION;
-    let pos = stream_len - i.len();
+    let aligned =
+        hdr.version == CONST_ALIGNED_FILE_VERSION || hdr.flags.contains(FstFlags::IS_ALIGNED);
 
     // Align input
-    if aligned && hdr.num_states > 0 && pos % CONST_ARCH_ALIGNMENT > 0 {
-        i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
+    if aligned && hdr.num_states > 0 {
+        reader.align(CONST_ARCH_ALIGNMENT)?;
+    }
+    // Each state contains at least its position, its number of transitions and its numbers
+    // of input and output epsilons.
+    let num_states = reader.check_count("number of states", hdr.num_states, 16, reader.offset())?;
+    let mut temp_states = Vec::with_capacity(num_states);
+    for _ in 0..num_states {
+        temp_states.push(read_const_state::<W>(&mut reader)?);
     }
-    let (mut i, temp_states) = count(parse_const_state::<W>, hdr.num_states as usize)(i)?;
-    let pos = stream_len - i.len();
 
     // Align input
-    if aligned && hdr.num_trs > 0 && pos % CONST_ARCH_ALIGNMENT > 0 {
-        i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
+    if aligned && hdr.num_trs > 0 {
+        reader.align(CONST_ARCH_ALIGNMENT)?;
     }
 
     let mut vector_states = Vec::with_capacity(temp_states.len());
     for temp_state in temp_states {
-        let (j, trs) = count(parse_bin_fst_tr, temp_state.ntrs)(i)?;
-        i = j;
+        let ntrs = reader.check_count(
+            "number of transitions",
+            temp_state.ntrs,
+            MIN_BIN_FST_TR_SIZE,
+            temp_state.ntrs_offset,
+        )?;
+        let mut trs = Vec::with_capacity(ntrs);
+        for _ in 0..ntrs {
+            trs.push(read_bin_fst_tr(&mut reader, hdr.num_states)?);
+        }
         vector_states.push(VectorFstState {
             final_weight: temp_state.final_weight,
             trs: TrsVec(Arc::new(trs)),
@@ -106,14 +112,11 @@ fn parse_const_fst<W: SerializableSemiring>(
         });
     }
 
-    Ok((
-        i,
-        VectorFst {
-            start_state: parse_start_state(hdr.start),
-            states: vector_states,
-            isymt: hdr.isymt,
-            osymt: hdr.osymt,
-            properties: FstProperties::from_bits_truncate(hdr.properties),
-        },
-    ))
+    Ok(VectorFst {
+        start_state: parse_start_state(hdr.start),
+        states: vector_states,
+        isymt: hdr.isymt,
+        osymt: hdr.osymt,
+        properties: FstProperties::from_bits_truncate(hdr.properties),
+    })
 }
@@ -2,20 +2,18 @@ use std::io::Write;
 use std::sync::Arc;
 
 use anyhow::Result;
-use nom::multi::count;
-use nom::number::complete::le_i64;
-use nom::IResult;
 
 use crate::fst_impls::vector_fst::VectorFstState;
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst, SerializableFst};
+use crate::parsers::bin_fst::bin_reader::BinReader;
 use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
 use crate::parsers::bin_fst::utils_parsing::{
-    parse_bin_fst_tr, parse_final_weight, parse_start_state,
+    parse_final_weight, parse_start_state, read_bin_fst_tr, MIN_BIN_FST_TR_SIZE,
 };
 use crate::parsers::bin_fst::utils_serialization::write_bin_fst_tr;
-use crate::parsers::nom_utils::NomCustomError;
 use crate::parsers::text_fst::ParsedTextFst;
 use crate::parsers::write_bin_i64;
 use crate::semirings::SerializableSemiring;
@@ -27,19 +25,8 @@ impl<W: SerializableSemiring> SerializableFst<W> for VectorFst<W> {
     }
 
     fn load(data: &[u8]) -> Result<Self> {
-        let (_, parsed_fst) = parse_vector_fst(data).map_err(|e| {
-            e.map(|e_inner| match e_inner {
-                NomCustomError::Nom(_, k) => {
-                    format_err!("Error while parsing binary VectorFst. Error kind {:?}", k)
-                }
-                NomCustomError::SymbolTableError(e) => format_err!(
-                    "Error while parsing symbolTable from binary VectorFst : {}",
-                    e
-                ),
-            })
-        })?;
-
-        Ok(parsed_fst)
+        read_vector_fst(data)
+            .map_err(|e| anyhow::Error::new(e).context("Error while parsing binary VectorFst"))
     }
 
     fn store<O: Write>(&self, mut output: O) -> Result<()> {
@@ -126,43 +113,53 @@ impl<W: SerializableSemiring> SerializableFst<W> for VectorFst<W> {
 
 static VECTOR_MIN_FILE_VERSION: i32 = 2;
 
-fn parse_vector_fst_state<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], VectorFstState<W>, NomCustomError<&[u8]>> {
-    let (i, final_weight) = W::parse_binary(i)?;
-    let (i, num_trs) = le_i64(i)?;
-    let (i, trs) = count(parse_bin_fst_tr, num_trs as usize)(i)?;
+fn read_vector_fst_state<W: SerializableSemiring>(
+    reader: &mut BinReader,
+    num_states: i64,
+) -> Result<VectorFstState<W>, FstReadError> {
+    let final_weight = reader.parse(W::parse_binary)?;
+    let offset = reader.offset();
+    let num_trs = reader.read_i64()?;
+    let num_trs = reader.check_count(
+        "number of transitions",
+        num_trs,
+        MIN_BIN_FST_TR_SIZE,
+        offset,
+    )?;
+    let mut trs = Vec::with_capacity(num_trs);
+    for _ in 0..num_trs {
+        trs.push(read_bin_fst_tr(reader, num_states)?);
+    }
     let niepsilons = trs.iter().filter(|t| t.ilabel == EPS_LABEL).count();
     let noepsilons = trs.iter().filter(|t| t.olabel == EPS_LABEL).count();
-    Ok((
-        i,
-        VectorFstState {
-            final_weight: parse_final_weight(final_weight),
-            trs: TrsVec(Arc::new(trs)),
-            niepsilons,
-            noepsilons,
-        },
-    ))
+    Ok(VectorFstState {
+        final_weight: parse_final_weight(final_weight),
+        trs: TrsVec(Arc::new(trs)),
+        niepsilons,
+        noepsilons,
+    })
 }
 
-fn parse_vector_fst<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], VectorFst<W>, NomCustomError<&[u8]>> {
-    let (i, header) = FstHeader::parse(
-        i,
+fn read_vector_fst<W: SerializableSemiring>(data: &[u8]) -> Result<VectorFst<W>, FstReadError> {
+    let mut reader = BinReader::new(data);
+    let header = FstHeader::read(
+        &mut reader,
         VECTOR_MIN_FILE_VERSION,
         VectorFst::<W>::fst_type(),
         Tr::<W>::tr_type(),
     )?;
-    let (i, states) = count(parse_vector_fst_state, header.num_states as usize)(i)?;
-    Ok((
-        i,
-        VectorFst {
-            start_state: parse_start_state(header.start),
-            states,
-            isymt: header.isymt,
-            osymt: header.osymt,
-            properties: FstProperties::from_bits_truncate(header.properties),
-        },
-    ))
+    // Each state contains at least its number of transitions.
+    let num_states =
+        reader.check_count("number of states", header.num_states, 8, reader.offset())?;
+    let mut states = Vec::with_capacity(num_states);
+    for _ in 0..num_states {
+        states.push(read_vector_fst_state(&mut reader, header.num_states)?);
+    }
+    Ok(VectorFst {
+        start_state: parse_start_state(header.start),
+        states,
+        isymt: header.isymt,
+        osymt: header.osymt,
+        properties: FstProperties::from_bits_truncate(header.properties),
+    })
 }
@@ -8,9 +8,12 @@ mod tests {
     use crate::fst_traits::{
         CoreFst, ExpandedFst, Fst, MutableFst, SerializableFst, StateIterator,
     };
-    use crate::semirings::{ProbabilityWeight, Semiring, TropicalWeight};
+    use crate::semirings::{ProbabilityWeight, Semiring, SerializableSemiring, TropicalWeight};
+    use crate::symt;
     use crate::tr::Tr;
-    use crate::{SymbolTable, Trs};
+    use crate::{
+        DrawingConfig, FstPath, Label, MissingSymbolPolicy, SymbolTable, TextWriteConfig, Trs,
+    };
     use rand::seq::SliceRandom;
     use std::sync::Arc;
 
@@ -329,4 +332,151 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_load_from_reader() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 0.5, s1))?;
+        fst.set_final(s1, 1.5)?;
+
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        let loaded = VectorFst::<TropicalWeight>::load_from_reader(data.as_slice())?;
+        assert_eq!(loaded, fst);
+
+        assert!(VectorFst::<TropicalWeight>::load_from_reader(&data[..data.len() / 2]).is_err());
+        Ok(())
+    }
+
+    fn draw_to_string<W: SerializableSemiring>(
+        fst: &VectorFst<W>,
+        config: &DrawingConfig<W>,
+    ) -> Result<String> {
+        let mut dot = vec![];
+        fst.draw_to_writer(&mut dot, config)?;
+        Ok(String::from_utf8(dot)?)
+    }
+
+    #[test]
+    fn test_draw_to_writer_highlight_path() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = SerializableFst::from_text_string(
+            "0\t1\t1\t1\t1.234\n0\t2\t2\t2\n1\t3\t3\t3\n2\t3\t3\t3\n3\n",
+        )?;
+        let config = DrawingConfig {
+            highlight_path: Some(FstPath::new(vec![1, 3], vec![1, 3], TropicalWeight::one())),
+            float_precision: Some(1),
+            ..DrawingConfig::default()
+        };
+        let dot = draw_to_string(&fst, &config)?;
+        assert!(dot.contains(
+            "0 -> 1 [label = \"1:1/1.2\", fontsize = 14, color = \"red\", fontcolor = \"red\"];"
+        ));
+        assert!(dot.contains(
+            "1 -> 3 [label = \"3:3/0.0\", fontsize = 14, color = \"red\", fontcolor = \"red\"];"
+        ));
+        assert!(dot.contains("0 -> 2 [label = \"2:2/0.0\", fontsize = 14];"));
+        assert!(dot.contains("2 -> 3 [label = \"3:3/0.0\", fontsize = 14];"));
+        assert!(dot.contains(
+            "1 [label = \"1\", shape = circle, style = solid, color = \"red\", fontsize = 14]"
+        ));
+        assert!(dot.contains("2 [label = \"2\", shape = circle, style = solid, fontsize = 14]"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw_to_writer_state_symbols_and_truncation() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(Label::MAX, 1, 0.5, s1))?;
+        fst.add_tr(s1, Tr::new(2, 2, 0.5, s2))?;
+        fst.set_final(s2, TropicalWeight::one())?;
+
+        let mut ssymt = SymbolTable::empty();
+        ssymt.add_symbols(vec!["start", "middle", "end"]);
+        let config = DrawingConfig {
+            ssymbols: Some(Arc::new(ssymt)),
+            max_states: Some(2),
+            ..DrawingConfig::default()
+        };
+        assert!(draw_to_string(&fst, &config).is_err());
+
+        let config = DrawingConfig {
+            allow_negative_labels: true,
+            ..config
+        };
+        let dot = draw_to_string(&fst, &config)?;
+        assert!(dot.contains("0 [label = \"start\", shape = circle, style = bold, fontsize = 14]"));
+        assert!(dot.contains("0 -> 1 [label = \"-1:1/0.5\", fontsize = 14];"));
+        assert!(dot.contains("1 -> truncated [label = \"2:2/0.5\", fontsize = 14];"));
+        assert!(dot.contains("truncated [label = \"...\", shape = plaintext, fontsize = 14]"));
+        assert!(!dot.contains("\"end"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_with_config_symbols() -> Result<()> {
+        let mut fst: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t2\t0.5\n1\t2\t3\t3\n1\t2\n2\n")?;
+        fst.set_input_symbols(Arc::new(symt!["a", "b", "c"]));
+        fst.set_output_symbols(Arc::new(symt!["x", "y"]));
+
+        assert_eq!(
+            fst.text_with_config(&TextWriteConfig::default())?,
+            fst.text()?
+        );
+
+        let config = TextWriteConfig::default()
+            .with_use_symbols(true)
+            .with_show_weight_one(false);
+        assert!(fst.text_with_config(&config).is_err());
+
+        let config = config.with_missing_symbol_policy(MissingSymbolPolicy::Symbol("?".into()));
+        assert_eq!(
+            fst.text_with_config(&config)?,
+            "0\t1\ta\ty\t0.5\n1\t2\tc\t?\n1\t2\n2\n"
+        );
+
+        let config = config.with_missing_symbol_policy(MissingSymbolPolicy::Numeric);
+        assert_eq!(
+            fst.text_with_config(&config)?,
+            "0\t1\ta\ty\t0.5\n1\t2\tc\t3\n1\t2\n2\n"
+        );
+
+        // Display writes the symbols and the weights equal to one.
+        assert_eq!(
+            format!("{}", fst),
+            fst.text_with_config(&config.with_show_weight_one(true))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_with_config_acceptor_and_states() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> =
+            SerializableFst::from_text_string("0\t1\t1\t1\n1\t2\t2\t2\t0.5\n2\n")?;
+        let mut ssymt = SymbolTable::empty();
+        ssymt.add_symbols(vec!["start", "middle", "end"]);
+        let config = TextWriteConfig::default()
+            .with_acceptor(true)
+            .with_show_weight_one(false)
+            .with_ssymbols(Some(Arc::new(ssymt)));
+        assert_eq!(
+            fst.text_with_config(&config)?,
+            "start\tmiddle\t1\nmiddle\tend\t2\t0.5\nend\n"
+        );
+
+        // The acceptor format is not used for transducers.
+        let mut transducer = fst.clone();
+        transducer.add_tr(2, Tr::new(1, 2, TropicalWeight::one(), 0))?;
+        let text = transducer.text_with_config(&config)?;
+        assert!(text.starts_with("start\tmiddle\t1\t1\n"));
+        assert!(text.contains("end\tstart\t1\t2\n"));
+        Ok(())
+    }
 }
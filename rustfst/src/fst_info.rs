@@ -0,0 +1,380 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::tr_filters::AnyTrFilter;
+use crate::algorithms::visitors::SccVisitor;
+use crate::fst_properties::{compute_fst_properties, known_properties, FstProperties};
+use crate::fst_traits::SerializableFst;
+use crate::semirings::SerializableSemiring;
+use crate::{StateId, SymbolTable, Tr, Trs, EPS_LABEL};
+
+/// Name of each property reported, along with its positive and negative bits.
+const PROPERTIES: [(&str, FstProperties, FstProperties); 16] = [
+    (
+        "acceptor",
+        FstProperties::ACCEPTOR,
+        FstProperties::NOT_ACCEPTOR,
+    ),
+    (
+        "input deterministic",
+        FstProperties::I_DETERMINISTIC,
+        FstProperties::NOT_I_DETERMINISTIC,
+    ),
+    (
+        "output deterministic",
+        FstProperties::O_DETERMINISTIC,
+        FstProperties::NOT_O_DETERMINISTIC,
+    ),
+    (
+        "input/output epsilons",
+        FstProperties::EPSILONS,
+        FstProperties::NO_EPSILONS,
+    ),
+    (
+        "input epsilons",
+        FstProperties::I_EPSILONS,
+        FstProperties::NO_I_EPSILONS,
+    ),
+    (
+        "output epsilons",
+        FstProperties::O_EPSILONS,
+        FstProperties::NO_O_EPSILONS,
+    ),
+    (
+        "input label sorted",
+        FstProperties::I_LABEL_SORTED,
+        FstProperties::NOT_I_LABEL_SORTED,
+    ),
+    (
+        "output label sorted",
+        FstProperties::O_LABEL_SORTED,
+        FstProperties::NOT_O_LABEL_SORTED,
+    ),
+    (
+        "weighted",
+        FstProperties::WEIGHTED,
+        FstProperties::UNWEIGHTED,
+    ),
+    ("cyclic", FstProperties::CYCLIC, FstProperties::ACYCLIC),
+    (
+        "cyclic at initial state",
+        FstProperties::INITIAL_CYCLIC,
+        FstProperties::INITIAL_ACYCLIC,
+    ),
+    (
+        "top sorted",
+        FstProperties::TOP_SORTED,
+        FstProperties::NOT_TOP_SORTED,
+    ),
+    (
+        "accessible",
+        FstProperties::ACCESSIBLE,
+        FstProperties::NOT_ACCESSIBLE,
+    ),
+    (
+        "coaccessible",
+        FstProperties::COACCESSIBLE,
+        FstProperties::NOT_COACCESSIBLE,
+    ),
+    ("string", FstProperties::STRING, FstProperties::NOT_STRING),
+    (
+        "weighted cycles",
+        FstProperties::WEIGHTED_CYCLES,
+        FstProperties::UNWEIGHTED_CYCLES,
+    ),
+];
+
+/// Summary of a symbol table attached to an FST.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolTableInfo {
+    pub num_symbols: usize,
+    /// Labeled checksum of the symbol table, see `SymbolTable::labeled_checksum`.
+    pub checksum: String,
+}
+
+impl SymbolTableInfo {
+    pub fn new(symt: &SymbolTable) -> Self {
+        Self {
+            num_symbols: symt.len(),
+            checksum: symt.labeled_checksum(),
+        }
+    }
+}
+
+/// Value of a property of an FST. `value` is `None` when the property is unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FstPropertyInfo {
+    pub name: String,
+    pub value: Option<bool>,
+}
+
+/// Report about an FST, equivalent of the output of OpenFST's `fstinfo`. It implements
+/// `Serialize` and `Deserialize` with the `serde` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FstInfo {
+    pub fst_type: String,
+    pub tr_type: String,
+    pub input_symbols: Option<SymbolTableInfo>,
+    pub output_symbols: Option<SymbolTableInfo>,
+    pub start: Option<StateId>,
+    pub num_states: usize,
+    pub num_trs: usize,
+    pub num_final_states: usize,
+    pub num_input_epsilons: usize,
+    pub num_output_epsilons: usize,
+    /// Number of transitions with both an input and an output epsilon.
+    pub num_io_epsilons: usize,
+    pub num_accessible_states: usize,
+    pub num_coaccessible_states: usize,
+    /// Number of states both accessible and coaccessible.
+    pub num_connected_states: usize,
+    pub num_sccs: usize,
+    /// Whether the properties have been computed (long mode) or are the ones stored in the FST.
+    pub long: bool,
+    pub properties: Vec<FstPropertyInfo>,
+}
+
+impl FstInfo {
+    /// Computes the report of `fst`. In long mode, all the properties are computed with
+    /// `compute_fst_properties`, otherwise only the properties known by the FST are reported.
+    pub fn new<W, F>(fst: &F, long: bool) -> Result<Self>
+    where
+        W: SerializableSemiring,
+        F: SerializableFst<W>,
+    {
+        let mut num_trs = 0;
+        let mut num_final_states = 0;
+        let mut num_input_epsilons = 0;
+        let mut num_output_epsilons = 0;
+        let mut num_io_epsilons = 0;
+        for s in 0..(fst.num_states() as StateId) {
+            for tr in fst.get_trs(s)?.trs() {
+                num_trs += 1;
+                if tr.ilabel == EPS_LABEL {
+                    num_input_epsilons += 1;
+                }
+                if tr.olabel == EPS_LABEL {
+                    num_output_epsilons += 1;
+                }
+                if tr.ilabel == EPS_LABEL && tr.olabel == EPS_LABEL {
+                    num_io_epsilons += 1;
+                }
+            }
+            if fst.is_final(s)? {
+                num_final_states += 1;
+            }
+        }
+
+        let mut visitor = SccVisitor::new(fst, true, true);
+        dfs_visit(fst, &mut visitor, &AnyTrFilter {}, false);
+        let access = visitor.access.take().unwrap_or_default();
+        let num_accessible_states = access.iter().filter(|a| **a).count();
+        let num_coaccessible_states = visitor.coaccess.iter().filter(|c| **c).count();
+        let num_connected_states = access
+            .iter()
+            .zip(visitor.coaccess.iter())
+            .filter(|(a, c)| **a && **c)
+            .count();
+        let num_sccs = visitor.nscc as usize;
+
+        let (properties, known) = if long {
+            let mut known = FstProperties::empty();
+            let props =
+                compute_fst_properties(fst, FstProperties::all_properties(), &mut known, false)?;
+            (props, known)
+        } else {
+            (fst.properties(), known_properties(fst.properties()))
+        };
+        let properties = PROPERTIES
+            .iter()
+            .map(|(name, pos, neg)| FstPropertyInfo {
+                name: name.to_string(),
+                value: if !known.contains(*pos) {
+                    None
+                } else if properties.contains(*pos) {
+                    Some(true)
+                } else if properties.contains(*neg) {
+                    Some(false)
+                } else {
+                    None
+                },
+            })
+            .collect();
+
+        Ok(Self {
+            fst_type: F::fst_type(),
+            tr_type: Tr::<W>::tr_type(),
+            input_symbols: fst.input_symbols().map(|s| SymbolTableInfo::new(s)),
+            output_symbols: fst.output_symbols().map(|s| SymbolTableInfo::new(s)),
+            start: fst.start(),
+            num_states: fst.num_states(),
+            num_trs,
+            num_final_states,
+            num_input_epsilons,
+            num_output_epsilons,
+            num_io_epsilons,
+            num_accessible_states,
+            num_coaccessible_states,
+            num_connected_states,
+            num_sccs,
+            long,
+            properties,
+        })
+    }
+}
+
+impl fmt::Display for FstInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symt = |s: &Option<SymbolTableInfo>| match s {
+            Some(s) => format!("{} symbols, checksum {}", s.num_symbols, s.checksum),
+            None => "none".to_string(),
+        };
+        writeln!(f, "{:<50}{}", "fst type", self.fst_type)?;
+        writeln!(f, "{:<50}{}", "arc type", self.tr_type)?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "input symbol table",
+            symt(&self.input_symbols)
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "output symbol table",
+            symt(&self.output_symbols)
+        )?;
+        writeln!(f, "{:<50}{}", "# of states", self.num_states)?;
+        writeln!(f, "{:<50}{}", "# of arcs", self.num_trs)?;
+        match self.start {
+            Some(start) => writeln!(f, "{:<50}{}", "initial state", start)?,
+            None => writeln!(f, "{:<50}{}", "initial state", -1)?,
+        };
+        writeln!(f, "{:<50}{}", "# of final states", self.num_final_states)?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of input/output epsilons", self.num_io_epsilons
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of input epsilons", self.num_input_epsilons
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of output epsilons", self.num_output_epsilons
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of accessible states", self.num_accessible_states
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of coaccessible states", self.num_coaccessible_states
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of connected states", self.num_connected_states
+        )?;
+        writeln!(
+            f,
+            "{:<50}{}",
+            "# of strongly conn components", self.num_sccs
+        )?;
+        for property in self.properties.iter() {
+            let value = match property.value {
+                Some(true) => "y",
+                Some(false) => "n",
+                None => "?",
+            };
+            writeln!(f, "{:<50}{}", property.name, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, Fst, MutableFst};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_fst_info() -> Result<()> {
+        let mut fst: VectorFst<TropicalWeight> = SerializableFst::from_text_string(
+            "0\t1\t0\t0\t0.5\n1\t2\t1\t0\n1\t1\t2\t2\n3\t2\t1\t1\n2\n",
+        )?;
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(vec!["a", "b"]);
+        fst.set_input_symbols(Arc::new(symt));
+
+        let info = FstInfo::new(&fst, true)?;
+        assert_eq!(info.fst_type, "vector");
+        assert_eq!(info.tr_type, "standard");
+        assert_eq!(info.input_symbols.as_ref().unwrap().num_symbols, 3);
+        assert!(info.output_symbols.is_none());
+        assert_eq!(info.num_states, 4);
+        assert_eq!(info.num_trs, 4);
+        assert_eq!(info.num_final_states, 1);
+        assert_eq!(info.num_input_epsilons, 1);
+        assert_eq!(info.num_output_epsilons, 2);
+        assert_eq!(info.num_io_epsilons, 1);
+        assert_eq!(info.num_accessible_states, 3);
+        assert_eq!(info.num_coaccessible_states, 4);
+        assert_eq!(info.num_connected_states, 3);
+        assert_eq!(info.num_sccs, 4);
+
+        let property = |name: &str| {
+            info.properties
+                .iter()
+                .find(|p| p.name == name)
+                .unwrap()
+                .value
+        };
+        assert_eq!(property("acceptor"), Some(false));
+        assert_eq!(property("cyclic"), Some(true));
+        assert_eq!(property("accessible"), Some(false));
+        assert_eq!(property("weighted"), Some(true));
+
+        let display = format!("{}", info);
+        assert!(display.contains("# of strongly conn components"));
+        assert!(display
+            .lines()
+            .any(|l| l.starts_with("cyclic ") && l.ends_with('y')));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fst_info_short_mode() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s = fst.add_state();
+        fst.set_start(s)?;
+        let info = FstInfo::new(&fst, false)?;
+        assert!(!info.long);
+        assert_eq!(info.start, Some(0));
+        let known = known_properties(fst.properties());
+        for (property, (_, pos, _)) in info.properties.iter().zip(PROPERTIES.iter()) {
+            assert_eq!(property.value.is_some(), known.contains(*pos));
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&info)?;
+            let parsed: FstInfo = serde_json::from_str(&json)?;
+            assert_eq!(parsed, info);
+        }
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use crate::{Label, StateId, Trs, EPS_LABEL};
 /// Structure representing a path in a FST
 /// (list of input labels, list of output labels and total weight).
 #[derive(PartialEq, Debug, Clone, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FstPath<W: Semiring> {
     /// List of input labels.
     pub ilabels: Vec<Label>,
@@ -4,6 +4,7 @@ pub use self::fst::{CoreFst, Fst};
 pub use self::iterators::{FstIntoIterator, FstIterData, FstIterator, StateIterator};
 pub use self::mutable_fst::MutableFst;
 pub use self::paths_iterator::PathsIterator;
+pub(crate) use self::serializable_fst::display_text_fst;
 pub use self::serializable_fst::SerializableFst;
 pub use self::string_paths_iterator::StringPathsIterator;
 
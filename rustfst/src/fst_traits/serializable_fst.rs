@@ -1,15 +1,21 @@
-use std::fs::File;
-use std::io::{BufWriter, LineWriter, Write};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{read_to_string, File};
+use std::io::{BufWriter, LineWriter, Read, Write};
 use std::path::Path;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use unsafe_unwrap::UnsafeUnwrap;
 
 use crate::fst_traits::ExpandedFst;
-use crate::parsers::text_fst::ParsedTextFst;
-use crate::semirings::SerializableSemiring;
+use crate::parsers::text_fst::{parse_text_fst_with_config, ParsedTextFst, TextFstParseConfig};
+use crate::semirings::{Semiring, SerializableSemiring};
 use crate::Trs;
-use crate::{DrawingConfig, StateId};
+use crate::{
+    DrawingConfig, FstPath, Label, MissingSymbolPolicy, StateId, SymbolTable, TextWriteConfig,
+    EPS_LABEL,
+};
 
 /// Trait definining the methods an Fst must implement to be serialized and deserialized.
 pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
@@ -25,6 +31,19 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
     /// Store the FST in binary format to a `Write`.
     fn store<O: Write>(&self, output: O) -> Result<()>;
 
+    /// Loads an FST in binary format from a `Read`, e.g stdin. The stream is consumed until
+    /// its end, so its length doesn't need to be known beforehand.
+    fn load_from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data).with_context(|| {
+            format!(
+                "Can't read {}Fst binary data from the input stream",
+                Self::fst_type()
+            )
+        })?;
+        Self::load(&data)
+    }
+
     /// Loads an FST from a file in binary format.
     fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
         let data: Vec<u8> = std::fs::read(path_bin_fst.as_ref()).with_context(|| {
@@ -65,27 +84,79 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
         Self::from_parsed_fst_text(parsed_text_fst)
     }
 
+    /// Deserializes a wFST in text whose labels and states may be symbols resolved through the
+    /// symbol tables of `config`. The input and output symbol tables are attached to the wFST if
+    /// `config.keep_isymbols` and `config.keep_osymbols` are set respectively.
+    fn from_text_string_with_config(fst_string: &str, config: &TextFstParseConfig) -> Result<Self> {
+        let parsed = parse_text_fst_with_config(fst_string, config)?;
+        let mut fst = Self::from_parsed_fst_text(parsed.parsed_fst)?;
+        if config.keep_isymbols {
+            if let Some(isymt) = parsed.isymt {
+                fst.set_input_symbols(isymt);
+            }
+        }
+        if config.keep_osymbols {
+            if let Some(osymt) = parsed.osymt {
+                fst.set_output_symbols(osymt);
+            }
+        }
+        Ok(fst)
+    }
+
+    /// Deserializes a wFST in text from a path, resolving the labels and states through the
+    /// symbol tables of `config`. Equivalent of OpenFST's `fstcompile`.
+    fn read_text_with_config<P: AsRef<Path>>(
+        path_text_fst: P,
+        config: &TextFstParseConfig,
+    ) -> Result<Self> {
+        let fst_string = read_to_string(path_text_fst.as_ref())
+            .with_context(|| format!("Can't open text fst file : {:?}", path_text_fst.as_ref()))?;
+        Self::from_text_string_with_config(&fst_string, config)
+    }
+
     /// Serializes the FST as a text file in a format compatible with OpenFST.
     fn write_text<P: AsRef<Path>>(&self, path_output: P) -> Result<()> {
-        let buffer = File::create(path_output.as_ref())?;
-        let mut line_writer = LineWriter::new(buffer);
-        write_fst!(self, line_writer, true, false);
-        Ok(())
+        self.write_text_with_config(path_output, &TextWriteConfig::default())
     }
 
     /// Writes the text representation of the FST into a String.
     fn text(&self) -> Result<String> {
-        let buffer = Vec::<u8>::new();
+        self.text_with_config(&TextWriteConfig::default())
+    }
+
+    /// Serializes the FST as a text file, with the labels and states written as configured
+    /// by `config`. Equivalent of OpenFST's `fstprint`.
+    fn write_text_with_config<P: AsRef<Path>>(
+        &self,
+        path_output: P,
+        config: &TextWriteConfig,
+    ) -> Result<()> {
+        let buffer = File::create(path_output.as_ref())?;
         let mut line_writer = LineWriter::new(buffer);
-        write_fst!(self, line_writer, true, false);
-        Ok(String::from_utf8(line_writer.into_inner()?)?)
+        write_text_fst(self, &mut line_writer, config)?;
+        line_writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the text representation of the FST into a String, e.g with the symbols of the
+    /// labels when `config.use_symbols` is set.
+    fn text_with_config(&self, config: &TextWriteConfig) -> Result<String> {
+        let mut buffer = Vec::<u8>::new();
+        write_text_fst(self, &mut buffer, config)?;
+        Ok(String::from_utf8(buffer)?)
     }
 
     /// Serializes the FST as a DOT file compatible with GraphViz binaries.
-    fn draw<P: AsRef<Path>>(&self, path_output: P, config: &DrawingConfig) -> Result<()> {
+    fn draw<P: AsRef<Path>>(&self, path_output: P, config: &DrawingConfig<W>) -> Result<()> {
         let buffer = File::create(path_output.as_ref())?;
         let mut f = BufWriter::new(LineWriter::new(buffer));
+        self.draw_to_writer(&mut f, config)?;
+        f.flush()?;
+        Ok(())
+    }
 
+    /// Writes the DOT representation of the FST to a `Write`, e.g to build it in memory.
+    fn draw_to_writer<O: Write>(&self, f: &mut O, config: &DrawingConfig<W>) -> Result<()> {
         if let Some(start_state) = self.start() {
             writeln!(f, "digraph FST {{")?;
 
@@ -99,7 +170,7 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
                 writeln!(f, "size = \"{},{}\";", width, height)?;
             }
 
-            writeln!(f, "label = \"{}\";", config.title)?;
+            writeln!(f, "label = \"{}\";", escape_dot(&config.title))?;
             writeln!(f, "center = 1;")?;
 
             if config.portrait {
@@ -116,13 +187,32 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
                 writeln!(f, "nodesep = {}", nodesep)?;
             }
 
+            let highlight = config
+                .highlight_path
+                .as_ref()
+                .map(|path| Highlight::new(self, start_state, path))
+                .transpose()?;
+
             // Start state first
-            draw_single_fst_state(self, &mut f, start_state, config)?;
+            let states: Vec<_> = std::iter::once(start_state)
+                .chain(self.states_iter().filter(|s| *s != start_state))
+                .take(config.max_states.unwrap_or(usize::MAX))
+                .collect();
+            let mut drawn = vec![false; self.num_states()];
+            for state in &states {
+                drawn[*state as usize] = true;
+            }
+
+            for state in &states {
+                draw_single_fst_state(self, f, *state, config, &drawn, highlight.as_ref())?;
+            }
 
-            for state in self.states_iter() {
-                if state != start_state {
-                    draw_single_fst_state(self, &mut f, state, config)?;
-                }
+            if states.len() < self.num_states() {
+                writeln!(
+                    f,
+                    "truncated [label = \"...\", shape = plaintext, fontsize = {}]",
+                    config.fontsize
+                )?;
             }
 
             writeln!(f, "}}")?;
@@ -131,20 +221,243 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
     }
 }
 
+fn text_symbol(
+    id: Label,
+    symt: Option<&Arc<SymbolTable>>,
+    symt_name: &str,
+    policy: &MissingSymbolPolicy,
+) -> Result<String> {
+    let symt = match symt {
+        Some(symt) => symt,
+        None => return Ok(id.to_string()),
+    };
+    match (symt.get_symbol(id), policy) {
+        (Some(symbol), _) => Ok(symbol.to_string()),
+        (None, MissingSymbolPolicy::Error) => {
+            bail!("Missing {} in {} SymbolTable", id, symt_name)
+        }
+        (None, MissingSymbolPolicy::Numeric) => Ok(id.to_string()),
+        (None, MissingSymbolPolicy::Symbol(symbol)) => Ok(symbol.clone()),
+    }
+}
+
+fn write_text_fst<W, F, O>(fst: &F, f: &mut O, config: &TextWriteConfig) -> Result<()>
+where
+    W: SerializableSemiring,
+    F: ExpandedFst<W>,
+    O: Write,
+{
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+    let (isymt, osymt) = if config.use_symbols {
+        (fst.input_symbols(), fst.output_symbols())
+    } else {
+        (None, None)
+    };
+    let ssymt = config.ssymbols.as_ref();
+    let policy = &config.missing_symbol_policy;
+    // Like `fstprint`, the acceptor format is only used if the FST is an acceptor.
+    let acceptor = config.acceptor
+        && fst.states_iter().all(|s| {
+            fst.get_trs(s)
+                .map(|trs| trs.trs().iter().all(|tr| tr.ilabel == tr.olabel))
+                .unwrap_or(false)
+        });
+
+    // Trs leaving the start state first, then the other states and finally the final states.
+    let states =
+        std::iter::once(start_state).chain(fst.states_iter().filter(|s| *s != start_state));
+    for state in states {
+        let state_symbol = text_symbol(state, ssymt, "state", policy)?;
+        for tr in fst.get_trs(state)?.trs() {
+            write!(
+                f,
+                "{}\t{}\t{}",
+                state_symbol,
+                text_symbol(tr.nextstate, ssymt, "state", policy)?,
+                text_symbol(tr.ilabel, isymt, "input", policy)?
+            )?;
+            if !acceptor {
+                write!(f, "\t{}", text_symbol(tr.olabel, osymt, "output", policy)?)?;
+            }
+            if config.show_weight_one || !tr.weight.is_one() {
+                write!(f, "\t{}", tr.weight)?;
+            }
+            writeln!(f)?;
+        }
+    }
+    for final_state in fst.final_states_iter() {
+        let final_weight = unsafe { fst.final_weight_unchecked(final_state).unsafe_unwrap() };
+        write!(f, "{}", text_symbol(final_state, ssymt, "state", policy)?)?;
+        if config.show_weight_one || !final_weight.is_one() {
+            write!(f, "\t{}", final_weight)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// Writes the FST for `Display`, with the symbols of the labels when the FST has symbol
+/// tables and the numeric labels otherwise.
+pub(crate) fn display_text_fst<W, F>(fst: &F, f: &mut fmt::Formatter) -> fmt::Result
+where
+    W: SerializableSemiring,
+    F: ExpandedFst<W>,
+{
+    let config = TextWriteConfig::default()
+        .with_use_symbols(true)
+        .with_missing_symbol_policy(MissingSymbolPolicy::Numeric);
+    let mut buffer = Vec::<u8>::new();
+    write_text_fst(fst, &mut buffer, &config).map_err(|_| fmt::Error)?;
+    f.write_str(&String::from_utf8_lossy(&buffer))
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn draw_weight<W: SerializableSemiring>(weight: &W, config: &DrawingConfig<W>) -> String {
+    match config.float_precision {
+        Some(precision) => format!("{:.*}", precision, weight),
+        None => format!("{}", weight),
+    }
+}
+
+fn draw_label<W: Semiring>(
+    label: Label,
+    symt: Option<&Arc<SymbolTable>>,
+    symt_name: &str,
+    config: &DrawingConfig<W>,
+) -> Result<String> {
+    if let Some(symt) = symt {
+        return symt
+            .get_symbol(label)
+            .map(escape_dot)
+            .ok_or_else(|| format_err!("Missing {} in {} SymbolTable", label, symt_name));
+    }
+    // OpenFST labels are signed integers.
+    let signed_label = label as i32;
+    if signed_label >= 0 {
+        Ok(label.to_string())
+    } else if config.allow_negative_labels {
+        Ok(signed_label.to_string())
+    } else {
+        bail!(
+            "Negative {} label {}, set allow_negative_labels to draw it",
+            symt_name,
+            signed_label
+        )
+    }
+}
+
+/// States and transitions on the successful paths of the FST with the labels of a `FstPath`.
+struct Highlight {
+    states: HashSet<StateId>,
+    // Pairs of (state, index of the transition).
+    trs: HashSet<(StateId, usize)>,
+}
+
+impl Highlight {
+    fn new<W: Semiring, F: ExpandedFst<W>>(
+        fst: &F,
+        start_state: StateId,
+        path: &FstPath<W>,
+    ) -> Result<Self> {
+        // Configurations (state, number of input labels read, number of output labels read)
+        // reachable from the start, explored in BFS.
+        let mut configs = vec![(start_state, 0, 0)];
+        let mut config_ids = HashMap::new();
+        config_ids.insert((start_state, 0, 0), 0);
+        let mut edges = vec![];
+        let mut accepting = vec![];
+        let mut idx = 0;
+        while idx < configs.len() {
+            let (state, i, o) = configs[idx];
+            if i == path.ilabels.len() && o == path.olabels.len() && fst.is_final(state)? {
+                accepting.push(idx);
+            }
+            for (tr_idx, tr) in fst.get_trs(state)?.trs().iter().enumerate() {
+                let next_i = if tr.ilabel == EPS_LABEL {
+                    i
+                } else if path.ilabels.get(i) == Some(&tr.ilabel) {
+                    i + 1
+                } else {
+                    continue;
+                };
+                let next_o = if tr.olabel == EPS_LABEL {
+                    o
+                } else if path.olabels.get(o) == Some(&tr.olabel) {
+                    o + 1
+                } else {
+                    continue;
+                };
+                let next_config = (tr.nextstate, next_i, next_o);
+                let next_idx = *config_ids.entry(next_config).or_insert_with(|| {
+                    configs.push(next_config);
+                    configs.len() - 1
+                });
+                edges.push((idx, next_idx, state, tr_idx));
+            }
+            idx += 1;
+        }
+
+        // Only keep the configurations from which an accepting one can be reached.
+        let mut reverse_edges = vec![vec![]; configs.len()];
+        for (from, to, _, _) in &edges {
+            reverse_edges[*to].push(*from);
+        }
+        let mut useful = vec![false; configs.len()];
+        let mut stack = accepting;
+        while let Some(idx) = stack.pop() {
+            if !useful[idx] {
+                useful[idx] = true;
+                stack.extend(reverse_edges[idx].iter().copied());
+            }
+        }
+
+        Ok(Self {
+            states: configs
+                .iter()
+                .zip(useful.iter())
+                .filter(|(_, useful)| **useful)
+                .map(|((state, _, _), _)| *state)
+                .collect(),
+            trs: edges
+                .into_iter()
+                .filter(|(from, to, _, _)| useful[*from] && useful[*to])
+                .map(|(_, _, state, tr_idx)| (state, tr_idx))
+                .collect(),
+        })
+    }
+}
+
 fn draw_single_fst_state<S: SerializableSemiring, F: SerializableFst<S>, W: Write>(
     fst: &F,
     writer: &mut W,
     state_id: StateId,
-    config: &DrawingConfig,
+    config: &DrawingConfig<S>,
+    drawn: &[bool],
+    highlight: Option<&Highlight>,
 ) -> Result<()> {
     let opt_isymt = fst.input_symbols();
     let opt_osymt = fst.output_symbols();
 
+    let state_label = match &config.ssymbols {
+        Some(ssymt) => ssymt
+            .get_symbol(state_id)
+            .map(escape_dot)
+            .ok_or_else(|| format_err!("Missing {} in state SymbolTable", state_id))?,
+        None => state_id.to_string(),
+    };
+    let highlight_color = escape_dot(&config.highlight_color);
+
     write!(writer, "{}", state_id)?;
-    write!(writer, " [label = \"{}", state_id)?;
+    write!(writer, " [label = \"{}", state_label)?;
     if let Some(final_weight) = fst.final_weight(state_id)? {
         if config.print_weight && (config.show_weight_one || !final_weight.is_one()) {
-            write!(writer, "/{}", final_weight)?;
+            write!(writer, "/{}", draw_weight(&final_weight, config))?;
         }
         write!(writer, "\", shape = doublecircle,")?;
     } else {
@@ -157,38 +470,38 @@ fn draw_single_fst_state<S: SerializableSemiring, F: SerializableFst<S>, W: Writ
         write!(writer, " style = solid,")?;
     }
 
+    if matches!(highlight, Some(h) if h.states.contains(&state_id)) {
+        write!(writer, " color = \"{}\",", highlight_color)?;
+    }
+
     writeln!(writer, " fontsize = {}]", config.fontsize)?;
 
-    for tr in fst.get_trs(state_id).unwrap().trs() {
-        write!(writer, "\t{} -> {}", state_id, tr.nextstate)?;
-
-        let ilabel = opt_isymt.map_or_else(
-            || Ok(format!("{}", tr.ilabel)),
-            |symt| {
-                symt.get_symbol(tr.ilabel)
-                    .map(|v| v.to_string())
-                    .ok_or_else(|| format_err!("Missing {} in input SymbolTable", tr.ilabel))
-            },
-        )?;
-
-        let olabel = opt_osymt.map_or_else(
-            || Ok(format!("{}", tr.olabel)),
-            |symt| {
-                symt.get_symbol(tr.olabel)
-                    .map(|v| v.to_string())
-                    .ok_or_else(|| format_err!("Missing {} in output SymbolTable", tr.olabel))
-            },
-        )?;
+    for (tr_idx, tr) in fst.get_trs(state_id)?.trs().iter().enumerate() {
+        if drawn[tr.nextstate as usize] {
+            write!(writer, "\t{} -> {}", state_id, tr.nextstate)?;
+        } else {
+            write!(writer, "\t{} -> truncated", state_id)?;
+        }
 
+        let ilabel = draw_label(tr.ilabel, opt_isymt, "input", config)?;
         write!(writer, " [label = \"{}", ilabel)?;
         if !config.acceptor {
+            let olabel = draw_label(tr.olabel, opt_osymt, "output", config)?;
             write!(writer, ":{}", olabel)?;
         }
 
         if config.print_weight && (config.show_weight_one || !tr.weight.is_one()) {
-            write!(writer, "/{}", tr.weight)?;
+            write!(writer, "/{}", draw_weight(&tr.weight, config))?;
+        }
+        write!(writer, "\", fontsize = {}", config.fontsize)?;
+        if matches!(highlight, Some(h) if h.trs.contains(&(state_id, tr_idx))) {
+            write!(
+                writer,
+                ", color = \"{}\", fontcolor = \"{}\"",
+                highlight_color, highlight_color
+            )?;
         }
-        writeln!(writer, "\", fontsize = {}];", config.fontsize)?;
+        writeln!(writer, "];")?;
     }
 
     Ok(())
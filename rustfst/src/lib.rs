@@ -149,6 +149,44 @@
 //! }
 //! ```
 //!
+//! ## Serde
+//!
+//! With the `serde` feature, [`VectorFst`](fst_impls::VectorFst),
+//! [`ConstFst`](fst_impls::ConstFst), [`Tr`], [`FstPath`], [`SymbolTable`] and the weights
+//! implementing [`SerializableSemiring`](semirings::SerializableSemiring) implement
+//! `Serialize` and `Deserialize`. Both FST types share the following schema, shown in JSON:
+//!
+//! ```json
+//! {
+//!     "start": 0,
+//!     "states": [
+//!         {
+//!             "final_weight": null,
+//!             "trs": [{"ilabel": 1, "olabel": 2, "weight": 0.5, "nextstate": 1}]
+//!         },
+//!         {"final_weight": 1.5, "trs": []}
+//!     ],
+//!     "isymt": {"symbols": ["<eps>", "a", "b"]},
+//!     "osymt": null
+//! }
+//! ```
+//!
+//! - `start` is the start state or `null`, `final_weight` is `null` for the non-final states.
+//! - The states are identified by their index in `states`, and the labels of a symbol table
+//!   by the index of the symbols in `symbols`. A symbol table with a name also has a `name`
+//!   field, and a symbol table whose labels are not the indices of its symbols has a `labels`
+//!   field listing the label of each symbol.
+//! - The float weights are numbers, except the infinities and NaN which are the strings
+//!   `"Infinity"`, `"-Infinity"` and `"NaN"` in human-readable formats.
+//!   `IntegerWeight` is a number.
+//! - The weights made of several weights are sequences : `[w1, w2]` for `ProductWeight`,
+//!   `LatticeWeight` and the gallic weights, `[lattice_weight, labels]` for
+//!   `CompactLatticeWeight` and `[w1, ..., wn]` for `UnionWeight` and `GallicWeight`.
+//! - The string weights are the sequence of their labels, or the string `"Infinity"`.
+//!
+//! The FSTs are validated when deserialized, the transitions leading to states that don't
+//! exist and invalid start states are rejected.
+//!
 //! ## Differences from OpenFST
 //!
 //! Here is a non-exhaustive list of ways in which Rustfst's API
@@ -198,9 +236,13 @@ extern crate serde;
 extern crate serde_json;
 
 pub use crate::drawing_config::DrawingConfig;
+pub use crate::fst_info::{FstInfo, FstPropertyInfo, SymbolTableInfo};
 pub use crate::fst_path::{check_path_in_fst, FstPath};
 pub use crate::string_path::StringPath;
-pub use crate::symbol_table::SymbolTable;
+pub use crate::symbol_table::{
+    compat_symbols, compat_symbols_check, set_compat_symbols_check, SymbolTable,
+};
+pub use crate::text_write_config::{MissingSymbolPolicy, TextWriteConfig};
 
 pub use self::tr::Tr;
 pub use self::trs::{Trs, TrsConst, TrsVec};
@@ -258,12 +300,24 @@ pub mod fst_impls;
 pub mod semirings;
 
 mod drawing_config;
+mod error;
+/// Report about an FST, similar to OpenFST's `fstinfo`.
+mod fst_info;
 /// Implementation of a successful path inside a wFST.
 mod fst_path;
 mod parsers;
 mod string_path;
+mod text_write_config;
 
+pub use crate::error::Error;
+pub use crate::parsers::bin_fst::fst_header::read_bin_fst_tr_type;
+pub use crate::parsers::bin_fst::fst_read_error::FstReadError;
+pub use crate::parsers::kaldi_lattice::{
+    read_kaldi_compact_lattice_file, read_kaldi_compact_lattice_text, read_kaldi_lattice_file,
+    read_kaldi_lattice_text,
+};
 pub use crate::parsers::nom_utils::NomCustomError;
+pub use crate::parsers::text_fst::TextFstParseConfig;
 
 /// A representable float near .001. (Used in Quantize)
 pub const KDELTA: f32 = 1.0f32 / 1024.0f32;
@@ -0,0 +1,138 @@
+use nom::error::ErrorKind;
+use nom::IResult;
+
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::{parse_bin_i32, parse_bin_i64};
+
+/// Cursor over a binary buffer running the nom parsers one after the other and turning
+/// their errors into `FstReadError` with the offset at which they happened.
+pub(crate) struct BinReader<'a> {
+    data: &'a [u8],
+    i: &'a [u8],
+}
+
+impl<'a> BinReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, i: data }
+    }
+
+    /// Number of bytes read so far.
+    pub(crate) fn offset(&self) -> usize {
+        self.data.len() - self.i.len()
+    }
+
+    /// Number of bytes not read yet.
+    pub(crate) fn remaining(&self) -> usize {
+        self.i.len()
+    }
+
+    pub(crate) fn parse<O, P>(&mut self, mut parser: P) -> Result<O, FstReadError>
+    where
+        P: FnMut(&'a [u8]) -> IResult<&'a [u8], O, NomCustomError<&'a [u8]>>,
+    {
+        let offset = self.offset();
+        match parser(self.i) {
+            Ok((i, o)) => {
+                self.i = i;
+                Ok(o)
+            }
+            Err(nom::Err::Incomplete(_)) => Err(FstReadError::Truncated { offset }),
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(match e {
+                NomCustomError::Nom(i, ErrorKind::Eof) => FstReadError::Truncated {
+                    offset: self.data.len() - i.len(),
+                },
+                NomCustomError::Nom(i, kind) => FstReadError::Parse {
+                    offset: self.data.len() - i.len(),
+                    kind,
+                },
+                NomCustomError::SymbolTableError(message) => {
+                    FstReadError::InvalidSymbolTable { offset, message }
+                }
+            }),
+        }
+    }
+
+    /// Reads the magic number identifying the data. The data written on big-endian platforms
+    /// is recognized from the byte-swapped magic number and rejected.
+    pub(crate) fn read_magic_number(&mut self, expected: i32) -> Result<(), FstReadError> {
+        let offset = self.offset();
+        let magic_number = self.read_i32()?;
+        if magic_number == expected {
+            Ok(())
+        } else if magic_number.swap_bytes() == expected {
+            Err(FstReadError::BigEndian { offset })
+        } else {
+            Err(FstReadError::InvalidMagicNumber {
+                offset,
+                magic_number,
+            })
+        }
+    }
+
+    pub(crate) fn read_i32(&mut self) -> Result<i32, FstReadError> {
+        self.parse(parse_bin_i32)
+    }
+
+    pub(crate) fn read_i64(&mut self) -> Result<i64, FstReadError> {
+        self.parse(parse_bin_i64)
+    }
+
+    /// Reads a string stored as its length followed by its UTF-8 bytes.
+    pub(crate) fn read_string(&mut self) -> Result<String, FstReadError> {
+        let offset = self.offset();
+        let n = self.read_i32()?;
+        if n < 0 {
+            return Err(FstReadError::InvalidString { offset });
+        }
+        let bytes = self.take(n as usize)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| FstReadError::InvalidString { offset })
+    }
+
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8], FstReadError> {
+        if n > self.i.len() {
+            return Err(FstReadError::Truncated {
+                offset: self.offset(),
+            });
+        }
+        let (bytes, i) = self.i.split_at(n);
+        self.i = i;
+        Ok(bytes)
+    }
+
+    /// Skips the padding needed for the next read to start at a multiple of `alignment`.
+    pub(crate) fn align(&mut self, alignment: usize) -> Result<(), FstReadError> {
+        let offset = self.offset();
+        if !offset.is_multiple_of(alignment) {
+            self.take(alignment - offset % alignment)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that `count` items of at least `min_item_size` bytes each can be read from the
+    /// remaining bytes, so that a corrupted count can't trigger a huge allocation.
+    pub(crate) fn check_count(
+        &self,
+        name: &'static str,
+        count: i64,
+        min_item_size: usize,
+        offset: usize,
+    ) -> Result<usize, FstReadError> {
+        let remaining = self.remaining();
+        let fits = count >= 0
+            && matches!(
+                (count as u64).checked_mul(min_item_size as u64),
+                Some(size) if size <= remaining as u64
+            );
+        if fits {
+            Ok(count as usize)
+        } else {
+            Err(FstReadError::InvalidCount {
+                offset,
+                name,
+                count,
+                remaining,
+            })
+        }
+    }
+}
@@ -1,15 +1,13 @@
 use std::io::Write;
 
 use anyhow::Result;
-use nom::bytes::complete::take;
-use nom::combinator::{map_res, verify};
-use nom::IResult;
 
 use bitflags::bitflags;
 
-use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
-use crate::parsers::nom_utils::NomCustomError;
-use crate::parsers::{parse_bin_i32, parse_bin_i64, parse_bin_u32, parse_bin_u64};
+use crate::parsers::bin_fst::bin_reader::BinReader;
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
+use crate::parsers::bin_symt::nom_parser::{read_symbol_table_bin, write_bin_symt};
+use crate::parsers::{parse_bin_u32, parse_bin_u64};
 use crate::parsers::{write_bin_i32, write_bin_i64, write_bin_u32, write_bin_u64};
 use crate::SymbolTable;
 use std::sync::Arc;
@@ -47,18 +45,6 @@ pub(crate) struct OpenFstString {
     s: String,
 }
 
-fn optionally_parse_symt(
-    i: &[u8],
-    parse_symt: bool,
-) -> IResult<&[u8], Option<SymbolTable>, NomCustomError<&[u8]>> {
-    if parse_symt {
-        let (i, symt) = parse_symbol_table_bin(i)?;
-        Ok((i, Some(symt)))
-    } else {
-        Ok((i, None))
-    }
-}
-
 fn optionally_write_symt<W: Write>(file: &mut W, symt: &Option<Arc<SymbolTable>>) -> Result<()> {
     if let Some(symt) = symt {
         write_bin_symt(file, symt)
@@ -68,47 +54,106 @@ fn optionally_write_symt<W: Write>(file: &mut W, symt: &Option<Arc<SymbolTable>>
 }
 
 impl FstHeader {
-    pub(crate) fn parse<S1: AsRef<str>, S2: AsRef<str>>(
-        i: &[u8],
+    /// Reads and validates the header of a binary FST, checking that it contains an FST of
+    /// type `fst_loading_type` over the arc type `tr_loading_type`.
+    pub(crate) fn read<S1: AsRef<str>, S2: AsRef<str>>(
+        reader: &mut BinReader,
         min_file_version: i32,
         fst_loading_type: S1,
         tr_loading_type: S2,
-    ) -> IResult<&[u8], FstHeader, NomCustomError<&[u8]>> {
-        let (i, magic_number) = verify(parse_bin_i32, |v: &i32| *v == FST_MAGIC_NUMBER)(i)?;
-        let (i, fst_type) = verify(OpenFstString::parse, |v| {
-            v.s.as_str() == fst_loading_type.as_ref()
-        })(i)?;
-        let (i, tr_type) = verify(OpenFstString::parse, |v| {
-            v.s.as_str() == tr_loading_type.as_ref()
-        })(i)?;
-        let (i, version) = verify(parse_bin_i32, |v: &i32| *v >= min_file_version)(i)?;
-        let (i, flags) = map_res(parse_bin_u32, |v: u32| {
-            FstFlags::from_bits(v).ok_or("Could not parse Fst Flags")
-        })(i)?;
-        let (i, properties) = parse_bin_u64(i)?;
-        let (i, start) = parse_bin_i64(i)?;
-        let (i, num_states) = parse_bin_i64(i)?;
-        let (i, num_trs) = parse_bin_i64(i)?;
-
-        let (i, isymt) = optionally_parse_symt(i, flags.contains(FstFlags::HAS_ISYMBOLS))?;
-        let (i, osymt) = optionally_parse_symt(i, flags.contains(FstFlags::HAS_OSYMBOLS))?;
-
-        Ok((
-            i,
-            FstHeader {
-                magic_number,
-                fst_type,
-                tr_type,
+    ) -> Result<FstHeader, FstReadError> {
+        reader.read_magic_number(FST_MAGIC_NUMBER)?;
+
+        let offset = reader.offset();
+        let fst_type = reader.read_string()?;
+        if fst_type != fst_loading_type.as_ref() {
+            return Err(FstReadError::FstTypeMismatch {
+                offset,
+                expected: fst_loading_type.as_ref().to_string(),
+                found: fst_type,
+            });
+        }
+
+        let offset = reader.offset();
+        let tr_type = reader.read_string()?;
+        if tr_type != tr_loading_type.as_ref() {
+            return Err(FstReadError::TrTypeMismatch {
+                offset,
+                expected: tr_loading_type.as_ref().to_string(),
+                found: tr_type,
+            });
+        }
+
+        let offset = reader.offset();
+        let version = reader.read_i32()?;
+        if version < min_file_version {
+            return Err(FstReadError::UnsupportedVersion {
+                offset,
                 version,
-                flags,
-                properties,
+                min_version: min_file_version,
+            });
+        }
+
+        let offset = reader.offset();
+        let flags = reader.parse(parse_bin_u32)?;
+        let flags =
+            FstFlags::from_bits(flags).ok_or(FstReadError::InvalidFlags { offset, flags })?;
+        let properties = reader.parse(parse_bin_u64)?;
+
+        let start_offset = reader.offset();
+        let start = reader.read_i64()?;
+        let num_states_offset = reader.offset();
+        let num_states = reader.read_i64()?;
+        if num_states < 0 {
+            return Err(FstReadError::InvalidCount {
+                offset: num_states_offset,
+                name: "number of states",
+                count: num_states,
+                remaining: reader.remaining(),
+            });
+        }
+        if start < -1 || start >= num_states {
+            return Err(FstReadError::InvalidStartState {
+                offset: start_offset,
                 start,
                 num_states,
-                num_trs,
-                isymt: isymt.map(Arc::new),
-                osymt: osymt.map(Arc::new),
-            },
-        ))
+            });
+        }
+        let num_trs_offset = reader.offset();
+        let num_trs = reader.read_i64()?;
+        if num_trs < 0 {
+            return Err(FstReadError::InvalidCount {
+                offset: num_trs_offset,
+                name: "number of transitions",
+                count: num_trs,
+                remaining: reader.remaining(),
+            });
+        }
+
+        let isymt = if flags.contains(FstFlags::HAS_ISYMBOLS) {
+            Some(Arc::new(read_symbol_table_bin(reader)?))
+        } else {
+            None
+        };
+        let osymt = if flags.contains(FstFlags::HAS_OSYMBOLS) {
+            Some(Arc::new(read_symbol_table_bin(reader)?))
+        } else {
+            None
+        };
+
+        Ok(FstHeader {
+            magic_number: FST_MAGIC_NUMBER,
+            fst_type: OpenFstString::new(fst_type),
+            tr_type: OpenFstString::new(tr_type),
+            version,
+            flags,
+            properties,
+            start,
+            num_states,
+            num_trs,
+            isymt,
+            osymt,
+        })
     }
 
     pub(crate) fn write<W: Write>(&self, file: &mut W) -> Result<()> {
@@ -136,6 +181,15 @@ impl FstHeader {
     }
 }
 
+/// Reads the arc type of a binary FST, e.g to choose the semiring to load it with. Only the
+/// magic number and the FST type preceding the arc type are read from `data`.
+pub fn read_bin_fst_tr_type(data: &[u8]) -> Result<String, FstReadError> {
+    let mut reader = BinReader::new(data);
+    reader.read_magic_number(FST_MAGIC_NUMBER)?;
+    reader.read_string()?;
+    reader.read_string()
+}
+
 impl OpenFstString {
     pub(crate) fn new<I: Into<String>>(s: I) -> Self {
         let _s = s.into();
@@ -144,18 +198,6 @@ impl OpenFstString {
             s: _s,
         }
     }
-    pub(crate) fn parse(i: &[u8]) -> IResult<&[u8], OpenFstString, NomCustomError<&[u8]>> {
-        let (i, n) = parse_bin_i32(i)?;
-        let (i, s) = take(n as usize)(i)?;
-        Ok((
-            i,
-            OpenFstString {
-                n,
-                s: String::from_utf8(s.to_vec()).unwrap(),
-            },
-        ))
-    }
-
     pub(crate) fn write<W: Write>(&self, file: &mut W) -> Result<()> {
         write_bin_i32(file, self.n)?;
         file.write_all(self.s.as_bytes()).map_err(|e| e.into())
@@ -167,3 +209,26 @@ impl From<OpenFstString> for String {
         os.s
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{MutableFst, SerializableFst};
+    use crate::semirings::LogWeight;
+
+    #[test]
+    fn test_read_bin_fst_tr_type() -> Result<()> {
+        let mut data = vec![];
+        VectorFst::<LogWeight>::new().store(&mut data)?;
+        assert_eq!(read_bin_fst_tr_type(&data)?, "log");
+
+        let mut data = FST_MAGIC_NUMBER.to_le_bytes().to_vec();
+        data.extend_from_slice(&i32::MAX.to_le_bytes());
+        assert_eq!(
+            read_bin_fst_tr_type(&data),
+            Err(FstReadError::Truncated { offset: 8 })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+
+use nom::error::ErrorKind;
+
+/// Error returned when a binary FST or symbol table can't be read. The offset of each variant
+/// is the position in bytes in the input where the problem was detected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FstReadError {
+    /// The input ended before the end of the data. The offset is the start of the value that
+    /// could not be read entirely.
+    Truncated { offset: usize },
+    /// The magic number doesn't identify the expected kind of data.
+    InvalidMagicNumber { offset: usize, magic_number: i32 },
+    /// The data has been written on a big-endian platform, which is not supported.
+    BigEndian { offset: usize },
+    /// The type of the FST, e.g `vector` or `const`, is not the one being loaded.
+    FstTypeMismatch {
+        offset: usize,
+        expected: String,
+        found: String,
+    },
+    /// The arc type, i.e the semiring, is not the one of the FST being loaded.
+    TrTypeMismatch {
+        offset: usize,
+        expected: String,
+        found: String,
+    },
+    /// The version of the file format is not supported.
+    UnsupportedVersion {
+        offset: usize,
+        version: i32,
+        min_version: i32,
+    },
+    /// The header flags contain unknown bits.
+    InvalidFlags { offset: usize, flags: u32 },
+    /// A string has a negative length or is not valid UTF-8.
+    InvalidString { offset: usize },
+    /// A count is negative or too large for the remaining bytes of the input.
+    InvalidCount {
+        offset: usize,
+        name: &'static str,
+        count: i64,
+        remaining: usize,
+    },
+    /// The start state is not a state of the FST.
+    InvalidStartState {
+        offset: usize,
+        start: i64,
+        num_states: i64,
+    },
+    /// A transition leads to a state that doesn't exist.
+    InvalidNextState {
+        offset: usize,
+        nextstate: i64,
+        num_states: i64,
+    },
+    /// The transitions of a state of a ConstFst are out of the transition array.
+    InvalidTrsRange {
+        offset: usize,
+        state: usize,
+        pos: i64,
+        ntrs: i64,
+        num_trs: i64,
+    },
+    /// The content of a symbol table is invalid.
+    InvalidSymbolTable { offset: usize, message: String },
+    /// The content of an encode table is invalid.
+    InvalidEncodeTable { offset: usize, message: String },
+    /// Any other parsing error.
+    Parse { offset: usize, kind: ErrorKind },
+}
+
+impl FstReadError {
+    /// Position in bytes in the input where the error was detected.
+    pub fn offset(&self) -> usize {
+        match self {
+            FstReadError::Truncated { offset }
+            | FstReadError::InvalidMagicNumber { offset, .. }
+            | FstReadError::BigEndian { offset }
+            | FstReadError::FstTypeMismatch { offset, .. }
+            | FstReadError::TrTypeMismatch { offset, .. }
+            | FstReadError::UnsupportedVersion { offset, .. }
+            | FstReadError::InvalidFlags { offset, .. }
+            | FstReadError::InvalidString { offset }
+            | FstReadError::InvalidCount { offset, .. }
+            | FstReadError::InvalidStartState { offset, .. }
+            | FstReadError::InvalidNextState { offset, .. }
+            | FstReadError::InvalidTrsRange { offset, .. }
+            | FstReadError::InvalidSymbolTable { offset, .. }
+            | FstReadError::InvalidEncodeTable { offset, .. }
+            | FstReadError::Parse { offset, .. } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for FstReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FstReadError::Truncated { offset } => {
+                write!(f, "unexpected end of input at byte {}", offset)
+            }
+            FstReadError::InvalidMagicNumber {
+                offset,
+                magic_number,
+            } => write!(
+                f,
+                "invalid magic number {} at byte {}",
+                magic_number, offset
+            ),
+            FstReadError::BigEndian { offset } => write!(
+                f,
+                "big-endian data at byte {}, only little-endian data is supported",
+                offset
+            ),
+            FstReadError::FstTypeMismatch {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected FST type {:?} but found {:?} at byte {}",
+                expected, found, offset
+            ),
+            FstReadError::TrTypeMismatch {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected arc type {:?} but found {:?} at byte {}",
+                expected, found, offset
+            ),
+            FstReadError::UnsupportedVersion {
+                offset,
+                version,
+                min_version,
+            } => write!(
+                f,
+                "unsupported version {} at byte {}, the minimum version is {}",
+                version, offset, min_version
+            ),
+            FstReadError::InvalidFlags { offset, flags } => {
+                write!(f, "invalid flags {:#x} at byte {}", flags, offset)
+            }
+            FstReadError::InvalidString { offset } => {
+                write!(f, "invalid string at byte {}", offset)
+            }
+            FstReadError::InvalidCount {
+                offset,
+                name,
+                count,
+                remaining,
+            } => write!(
+                f,
+                "invalid {} {} at byte {}, only {} bytes remaining",
+                name, count, offset, remaining
+            ),
+            FstReadError::InvalidStartState {
+                offset,
+                start,
+                num_states,
+            } => write!(
+                f,
+                "invalid start state {} at byte {}, the FST has {} states",
+                start, offset, num_states
+            ),
+            FstReadError::InvalidNextState {
+                offset,
+                nextstate,
+                num_states,
+            } => write!(
+                f,
+                "transition to invalid state {} at byte {}, the FST has {} states",
+                nextstate, offset, num_states
+            ),
+            FstReadError::InvalidTrsRange {
+                offset,
+                state,
+                pos,
+                ntrs,
+                num_trs,
+            } => write!(
+                f,
+                "state {} has {} transitions starting at {} at byte {}, the FST has {} transitions",
+                state, ntrs, pos, offset, num_trs
+            ),
+            FstReadError::InvalidSymbolTable { offset, message } => {
+                write!(f, "invalid symbol table at byte {} : {}", offset, message)
+            }
+            FstReadError::InvalidEncodeTable { offset, message } => {
+                write!(f, "invalid encode table at byte {} : {}", offset, message)
+            }
+            FstReadError::Parse { offset, kind } => {
+                write!(f, "parsing error {:?} at byte {}", kind, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FstReadError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_traits::{MutableFst, SerializableFst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::{SymbolTable, Tr};
+    use anyhow::Result;
+
+    // Offsets in the binary representation of the FST built by `fst_data`.
+    const NUM_STATES_OFFSET: usize = 50;
+    const BODY_OFFSET: usize = 66;
+    const NEXTSTATE_OFFSET: usize = 90;
+    // Number of trs of the last state, the last value of the file.
+    const LAST_NUM_TRS_OFFSET: usize = 98;
+
+    fn fst_data() -> Result<Vec<u8>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 2, 0.5, s1))?;
+        fst.set_final(s1, 1.5)?;
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        Ok(data)
+    }
+
+    fn read_error<T>(res: Result<T>) -> FstReadError {
+        match res {
+            Ok(_) => panic!("Expected an error"),
+            Err(e) => e.downcast_ref::<FstReadError>().unwrap().clone(),
+        }
+    }
+
+    #[test]
+    fn test_read_error_truncated() -> Result<()> {
+        let data = fst_data()?;
+        let err = read_error(VectorFst::<TropicalWeight>::load(&data[..data.len() - 1]));
+        assert_eq!(
+            err,
+            FstReadError::Truncated {
+                offset: LAST_NUM_TRS_OFFSET
+            }
+        );
+        assert_eq!(
+            read_error(SymbolTable::load(&[0, 1, 2])),
+            FstReadError::Truncated { offset: 0 }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_error_type_mismatch() -> Result<()> {
+        let data = fst_data()?;
+        assert_eq!(
+            read_error(VectorFst::<LogWeight>::load(&data)),
+            FstReadError::TrTypeMismatch {
+                offset: 14,
+                expected: "log".to_string(),
+                found: "standard".to_string(),
+            }
+        );
+        assert_eq!(
+            read_error(ConstFst::<TropicalWeight>::load(&data)),
+            FstReadError::FstTypeMismatch {
+                offset: 4,
+                expected: "const".to_string(),
+                found: "vector".to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_error_invalid_counts() -> Result<()> {
+        let mut data = fst_data()?;
+        data[NUM_STATES_OFFSET..NUM_STATES_OFFSET + 8].copy_from_slice(&i64::MAX.to_le_bytes());
+        let err = read_error(VectorFst::<TropicalWeight>::load(&data));
+        assert_eq!(
+            err,
+            FstReadError::InvalidCount {
+                offset: BODY_OFFSET,
+                name: "number of states",
+                count: i64::MAX,
+                remaining: data.len() - BODY_OFFSET,
+            }
+        );
+        assert_eq!(err.offset(), BODY_OFFSET);
+
+        let mut data = fst_data()?;
+        data[NEXTSTATE_OFFSET..NEXTSTATE_OFFSET + 4].copy_from_slice(&5i32.to_le_bytes());
+        assert_eq!(
+            read_error(VectorFst::<TropicalWeight>::load(&data)),
+            FstReadError::InvalidNextState {
+                offset: NEXTSTATE_OFFSET,
+                nextstate: 5,
+                num_states: 2,
+            }
+        );
+        Ok(())
+    }
+}
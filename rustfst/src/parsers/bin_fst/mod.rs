@@ -1,3 +1,5 @@
+pub(crate) mod bin_reader;
 pub(crate) mod fst_header;
+pub(crate) mod fst_read_error;
 pub(crate) mod utils_parsing;
 pub(crate) mod utils_serialization;
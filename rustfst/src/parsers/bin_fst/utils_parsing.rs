@@ -1,7 +1,5 @@
-use nom::number::complete::le_i32;
-use nom::IResult;
-
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::bin_fst::bin_reader::BinReader;
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
 use crate::semirings::SerializableSemiring;
 use crate::{Label, StateId, Tr};
 
@@ -25,20 +23,29 @@ pub(crate) fn parse_final_weight<W: SerializableSemiring>(weight: W) -> Option<W
     }
 }
 
-pub(crate) fn parse_bin_fst_tr<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], Tr<W>, NomCustomError<&[u8]>> {
-    let (i, ilabel) = le_i32(i)?;
-    let (i, olabel) = le_i32(i)?;
-    let (i, weight) = W::parse_binary(i)?;
-    let (i, nextstate) = le_i32(i)?;
-    Ok((
-        i,
-        Tr {
-            ilabel: ilabel as Label,
-            olabel: olabel as Label,
-            weight,
-            nextstate: nextstate as StateId,
-        },
-    ))
+/// Minimum size in bytes of a transition : the labels and the nextstate, without the weight.
+pub(crate) static MIN_BIN_FST_TR_SIZE: usize = 12;
+
+pub(crate) fn read_bin_fst_tr<W: SerializableSemiring>(
+    reader: &mut BinReader,
+    num_states: i64,
+) -> Result<Tr<W>, FstReadError> {
+    let ilabel = reader.read_i32()?;
+    let olabel = reader.read_i32()?;
+    let weight = reader.parse(W::parse_binary)?;
+    let offset = reader.offset();
+    let nextstate = reader.read_i32()?;
+    if nextstate < 0 || i64::from(nextstate) >= num_states {
+        return Err(FstReadError::InvalidNextState {
+            offset,
+            nextstate: i64::from(nextstate),
+            num_states,
+        });
+    }
+    Ok(Tr {
+        ilabel: ilabel as Label,
+        olabel: olabel as Label,
+        weight,
+        nextstate: nextstate as StateId,
+    })
 }
@@ -1,44 +1,55 @@
-use nom::combinator::verify;
-use nom::multi::count;
-use nom::IResult;
 use std::hash::BuildHasher;
 
+use crate::parsers::bin_fst::bin_reader::BinReader;
 use crate::parsers::bin_fst::fst_header::OpenFstString;
-use crate::parsers::nom_utils::NomCustomError;
-use crate::parsers::{parse_bin_i32, parse_bin_i64};
+use crate::parsers::bin_fst::fst_read_error::FstReadError;
 use crate::parsers::{write_bin_i32, write_bin_i64};
+use crate::symbol_table::{key_from_label, label_from_key};
 use crate::{Label, SymbolTable};
 use anyhow::Result;
 use std::io::Write;
 
 static SYMBOL_TABLE_MAGIC_NUMBER: i32 = 2_125_658_996;
 
-fn parse_row_symt(i: &[u8]) -> IResult<&[u8], (i64, OpenFstString), NomCustomError<&[u8]>> {
-    let (i, symbol) = OpenFstString::parse(i)?;
-    let (i, key) = parse_bin_i64(i)?;
-    Ok((i, (key, symbol)))
-}
-
-pub(crate) fn parse_symbol_table_bin(
-    i: &[u8],
-) -> IResult<&[u8], SymbolTable, NomCustomError<&[u8]>> {
-    let (i, _magic_number) = verify(parse_bin_i32, |v| *v == SYMBOL_TABLE_MAGIC_NUMBER)(i)?;
-    let (i, _name) = OpenFstString::parse(i)?;
-    let (i, _available_key) = parse_bin_i64(i)?;
-    let (i, num_symbols) = parse_bin_i64(i)?;
-    let (i, pairs_idx_symbols) = count(parse_row_symt, num_symbols as usize)(i)?;
+/// Reads and validates a symbol table in binary format.
+pub(crate) fn read_symbol_table_bin(reader: &mut BinReader) -> Result<SymbolTable, FstReadError> {
+    reader.read_magic_number(SYMBOL_TABLE_MAGIC_NUMBER)?;
+    let name = reader.read_string()?;
+    let available_key = reader.read_i64()?;
+    let offset = reader.offset();
+    let num_symbols = reader.read_i64()?;
+    // Each row contains at least the length of the symbol and the key.
+    let num_symbols = reader.check_count("number of symbols", num_symbols, 12, offset)?;
 
     let mut symt = SymbolTable::empty();
-    for (key, symbol) in pairs_idx_symbols.into_iter() {
-        let inserted_label = symt.add_symbol(symbol);
-        if inserted_label != key as Label {
-            return Err(nom::Err::Error(NomCustomError::SymbolTableError(
-                format!("SymbolTable must contain increasing labels with no hole. Expected : {} and Got : {}", inserted_label, key)
-            )));
+    symt.set_name(name);
+    for _ in 0..num_symbols {
+        let offset = reader.offset();
+        let symbol = reader.read_string()?;
+        let key = reader.read_i64()?;
+        let label = label_from_key(key).ok_or_else(|| FstReadError::InvalidSymbolTable {
+            offset,
+            message: format!("Invalid key {} for the symbol {:?}", key, symbol),
+        })?;
+        let inserted_label = symt
+            .add_symbol_with_label(symbol.as_str(), label)
+            .map_err(|e| FstReadError::InvalidSymbolTable {
+                offset,
+                message: e.to_string(),
+            })?;
+        if inserted_label != label {
+            return Err(FstReadError::InvalidSymbolTable {
+                offset,
+                message: format!("Duplicate symbol {:?}", symbol),
+            });
         }
     }
+    // The available key written by OpenFST can be above the largest key, e.g after removals.
+    if available_key > key_from_label(symt.available_key()) && available_key <= i32::MAX as i64 {
+        symt.set_available_key(available_key as Label);
+    }
 
-    Ok((i, symt))
+    Ok(symt)
 }
 
 pub(crate) fn write_bin_symt<W: Write, H: BuildHasher>(
@@ -46,13 +57,12 @@ pub(crate) fn write_bin_symt<W: Write, H: BuildHasher>(
     symt: &SymbolTable<H>,
 ) -> Result<()> {
     write_bin_i32(file, SYMBOL_TABLE_MAGIC_NUMBER)?;
-    OpenFstString::new("rustfst_symboltable").write(file)?;
-    // TODO: Might not be available
-    write_bin_i64(file, symt.len() as i64)?;
+    OpenFstString::new(symt.name().unwrap_or("rustfst_symboltable")).write(file)?;
+    write_bin_i64(file, key_from_label(symt.available_key()))?;
     write_bin_i64(file, symt.len() as i64)?;
     for (label, symbol) in symt.iter() {
         OpenFstString::new(symbol).write(file)?;
-        write_bin_i64(file, label as i64)?;
+        write_bin_i64(file, key_from_label(label))?;
     }
 
     Ok(())
@@ -0,0 +1,208 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::SerializableFst;
+use crate::parsers::text_fst::{FinalState, ParsedTextFst, Transition};
+use crate::semirings::{CompactLatticeWeight, LatticeWeight, SerializableSemiring};
+use crate::{Label, StateId};
+
+fn parse_field<T: std::str::FromStr>(field: &str, name: &str) -> Result<T> {
+    field
+        .parse()
+        .map_err(|_| format_err!("Invalid {} : {:?}", name, field))
+}
+
+fn parse_weight<W: SerializableSemiring>(field: &str) -> Result<W> {
+    match W::parse_text(field) {
+        Ok(("", w)) => Ok(w),
+        _ => bail!("Invalid weight : {:?}", field),
+    }
+}
+
+/// Parses the lines of an archive in Kaldi text format. `num_labels` is the number of label
+/// columns of the transitions : 2 for lattices and 1 for compact lattices.
+fn parse_kaldi_archive<W: SerializableSemiring>(
+    text: &str,
+    num_labels: usize,
+) -> Result<Vec<(String, VectorFst<W>)>> {
+    let mut res = vec![];
+    let mut current: Option<(String, ParsedTextFst<W>)> = None;
+
+    for (line_idx, line) in text.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() {
+            if let Some((key, parsed_fst)) = current.take() {
+                res.push((key, VectorFst::from_parsed_fst_text(parsed_fst)?));
+            }
+            continue;
+        }
+        let (key, parsed_fst) = match current.as_mut() {
+            Some(c) => c,
+            None => {
+                if fields.len() != 1 {
+                    bail!(
+                        "Kaldi lattice : line {} : expected an utterance key, got {:?}",
+                        line_idx + 1,
+                        line
+                    );
+                }
+                current = Some((fields[0].to_string(), ParsedTextFst::default()));
+                continue;
+            }
+        };
+        let mut row = || -> Result<()> {
+            let state: StateId = parse_field(fields[0], "state")?;
+            if parsed_fst.start_state.is_none() {
+                parsed_fst.start_state = Some(state);
+            }
+            if fields.len() <= 2 {
+                let weight = fields.get(1).map(|f| parse_weight(f)).transpose()?;
+                parsed_fst.final_states.push(FinalState { state, weight });
+            } else if fields.len() == num_labels + 2 || fields.len() == num_labels + 3 {
+                let nextstate: StateId = parse_field(fields[1], "state")?;
+                let ilabel: Label = parse_field(fields[2], "label")?;
+                let olabel: Label = parse_field(fields[num_labels + 1], "label")?;
+                let weight = fields
+                    .get(num_labels + 2)
+                    .map(|f| parse_weight(f))
+                    .transpose()?;
+                parsed_fst.transitions.push(Transition {
+                    state,
+                    ilabel,
+                    olabel,
+                    weight,
+                    nextstate,
+                });
+            } else {
+                bail!("Unexpected number of fields : {}", fields.len());
+            }
+            Ok(())
+        };
+        row().with_context(|| {
+            format!(
+                "Kaldi lattice : line {} of utterance {:?} : {:?}",
+                line_idx + 1,
+                key,
+                line
+            )
+        })?;
+    }
+    if let Some((key, parsed_fst)) = current.take() {
+        res.push((key, VectorFst::from_parsed_fst_text(parsed_fst)?));
+    }
+    Ok(res)
+}
+
+/// Reads an archive of lattices in Kaldi text format, as written by
+/// `lattice-copy ark:in.lats ark,t:-`.
+///
+/// Each lattice starts with a line containing the utterance key and ends with an empty line.
+///
+/// ## Example:
+/// ```text
+/// utt1
+/// 0    1    12    1    1.5,2.25
+/// 1    2    15    0    0,1
+/// 2    0.5,0
+///
+/// ```
+pub fn read_kaldi_lattice_text(text: &str) -> Result<Vec<(String, VectorFst<LatticeWeight>)>> {
+    parse_kaldi_archive(text, 2)
+}
+
+/// Reads an archive of compact lattices in Kaldi text format, as written by
+/// `lattice-copy --write-compact=true ark:in.lats ark,t:-`. The strings of the weights are
+/// separated by `_`.
+///
+/// ## Example:
+/// ```text
+/// utt1
+/// 0    1    12    1.5,2.25,1_1_3
+/// 1    0.5,0,
+///
+/// ```
+pub fn read_kaldi_compact_lattice_text(
+    text: &str,
+) -> Result<Vec<(String, VectorFst<CompactLatticeWeight>)>> {
+    parse_kaldi_archive(text, 1)
+}
+
+/// Reads an archive of lattices in Kaldi text format from a file.
+pub fn read_kaldi_lattice_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<(String, VectorFst<LatticeWeight>)>> {
+    read_kaldi_lattice_text(&read_to_string(path)?)
+}
+
+/// Reads an archive of compact lattices in Kaldi text format from a file.
+pub fn read_kaldi_compact_lattice_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<(String, VectorFst<CompactLatticeWeight>)>> {
+    read_kaldi_compact_lattice_text(&read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_traits::{CoreFst, ExpandedFst};
+    use crate::semirings::Semiring;
+    use crate::Trs;
+
+    #[test]
+    fn test_read_kaldi_lattice_text() -> Result<()> {
+        let text =
+            "utt1\n0\t1\t12\t1\t1.5,2.25\n1\t2\t15\t0\t0,1\n2\t0.5,0\n\nutt2\n0\t1\t3\t4\n1\n";
+        let lattices = read_kaldi_lattice_text(text)?;
+        assert_eq!(lattices.len(), 2);
+
+        let (key, fst) = &lattices[0];
+        assert_eq!(key, "utt1");
+        assert_eq!(fst.num_states(), 3);
+        assert_eq!(fst.start(), Some(0));
+        let trs = fst.get_trs(0)?;
+        let tr = &trs.trs()[0];
+        assert_eq!((tr.ilabel, tr.olabel), (12, 1));
+        assert_eq!(tr.weight, LatticeWeight::from((1.5, 2.25)));
+        assert_eq!(fst.final_weight(2)?, Some(LatticeWeight::from((0.5, 0.0))));
+
+        let (key, fst) = &lattices[1];
+        assert_eq!(key, "utt2");
+        assert_eq!(fst.get_trs(0)?.trs()[0].weight, LatticeWeight::one());
+        assert_eq!(fst.final_weight(1)?, Some(LatticeWeight::one()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_kaldi_compact_lattice_text() -> Result<()> {
+        let text = "utt1\n0 1 12 1.5,2.25,1_1_3\n1 0.5,0,\n";
+        let lattices = read_kaldi_compact_lattice_text(text)?;
+        assert_eq!(lattices.len(), 1);
+
+        let (_, fst) = &lattices[0];
+        let trs = fst.get_trs(0)?;
+        let tr = &trs.trs()[0];
+        assert_eq!((tr.ilabel, tr.olabel), (12, 12));
+        assert_eq!(
+            tr.weight,
+            CompactLatticeWeight::new((LatticeWeight::from((1.5, 2.25)), vec![1, 1, 3]))
+        );
+        assert_eq!(
+            fst.final_weight(1)?,
+            Some(CompactLatticeWeight::new((
+                LatticeWeight::from((0.5, 0.0)),
+                vec![]
+            )))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_kaldi_lattice_text_error() {
+        let text = "utt1\n0\t1\t12\t1\t1.5;2.25\n";
+        let err = read_kaldi_lattice_text(text).unwrap_err();
+        assert!(format!("{:#}", err).contains("line 2"));
+    }
+}
@@ -1,8 +1,8 @@
 pub(crate) mod bin_fst;
 pub(crate) mod bin_symt;
+pub mod kaldi_lattice;
 pub mod nom_utils;
 pub mod text_fst;
-pub(crate) mod text_symt;
 pub mod utils_parsing;
 pub mod utils_serialization;
 
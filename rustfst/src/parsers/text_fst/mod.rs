@@ -1,4 +1,6 @@
 mod nom_parser;
 mod parsed_text_fst;
+mod symbolic_parser;
 
-pub use self::parsed_text_fst::ParsedTextFst;
+pub use self::parsed_text_fst::{FinalState, ParsedTextFst, Transition};
+pub use self::symbolic_parser::{parse_text_fst_with_config, TextFstParseConfig};
@@ -0,0 +1,357 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::parsers::text_fst::{FinalState, ParsedTextFst, Transition};
+use crate::semirings::SerializableSemiring;
+use crate::{Error, Label, SymbolTable};
+
+/// Struct to configure how an FST in text format is parsed. Mirrors the options of the
+/// `fstcompile` binary of OpenFST.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextFstParseConfig {
+    /// Symbol table used to resolve the input labels. If `None`, input labels must be integers.
+    pub isymt: Option<Arc<SymbolTable>>,
+    /// Symbol table used to resolve the output labels. If `None`, output labels must be integers.
+    pub osymt: Option<Arc<SymbolTable>>,
+    /// Symbol table used to resolve the states. If `None`, states must be integers.
+    pub ssymt: Option<Arc<SymbolTable>>,
+    /// Input in acceptor format : transitions only have one label.
+    pub acceptor: bool,
+    /// Attach the input symbol table to the parsed FST.
+    pub keep_isymbols: bool,
+    /// Attach the output symbol table to the parsed FST.
+    pub keep_osymbols: bool,
+    /// Add the symbols missing from the symbol tables instead of failing.
+    pub allow_unknown_symbols: bool,
+}
+
+impl TextFstParseConfig {
+    pub fn new(
+        isymt: Option<Arc<SymbolTable>>,
+        osymt: Option<Arc<SymbolTable>>,
+        ssymt: Option<Arc<SymbolTable>>,
+    ) -> Self {
+        Self {
+            isymt,
+            osymt,
+            ssymt,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_acceptor(self, acceptor: bool) -> Self {
+        Self { acceptor, ..self }
+    }
+
+    pub fn with_keep_isymbols(self, keep_isymbols: bool) -> Self {
+        Self {
+            keep_isymbols,
+            ..self
+        }
+    }
+
+    pub fn with_keep_osymbols(self, keep_osymbols: bool) -> Self {
+        Self {
+            keep_osymbols,
+            ..self
+        }
+    }
+
+    pub fn with_allow_unknown_symbols(self, allow_unknown_symbols: bool) -> Self {
+        Self {
+            allow_unknown_symbols,
+            ..self
+        }
+    }
+}
+
+/// Result of the parsing of an FST in text format with symbol tables. The symbol tables
+/// contain the symbols added during the parsing when `allow_unknown_symbols` is set.
+#[derive(Debug)]
+pub struct ParsedSymbolicTextFst<W: SerializableSemiring> {
+    pub parsed_fst: ParsedTextFst<W>,
+    pub isymt: Option<Arc<SymbolTable>>,
+    pub osymt: Option<Arc<SymbolTable>>,
+}
+
+struct SymbolResolver<'a> {
+    symt: Option<&'a mut Arc<SymbolTable>>,
+    allow_unknown_symbols: bool,
+    name: &'a str,
+}
+
+impl<'a> SymbolResolver<'a> {
+    /// Returns the label of a symbol, or parses it as an integer without symbol table.
+    /// States are resolved the same way as `StateId` and `Label` are the same type.
+    fn resolve(&mut self, field: &str) -> Result<Label> {
+        match self.symt.as_mut() {
+            Some(symt) => match symt.get_label(field) {
+                Some(label) => Ok(label),
+                None if self.allow_unknown_symbols => Ok(Arc::make_mut(symt).add_symbol(field)),
+                None => bail!("Unknown {} symbol {:?}", self.name, field),
+            },
+            None => field
+                .parse()
+                .map_err(|_| format_err!("Invalid {} {:?}", self.name, field)),
+        }
+    }
+}
+
+fn parse_weight<W: SerializableSemiring>(field: &str) -> Result<W> {
+    match W::parse_text(field) {
+        Ok(("", w)) => Ok(w),
+        _ => bail!("Invalid weight {:?}", field),
+    }
+}
+
+/// Parses an FST in text format whose labels and states may be symbols.
+///
+/// # Format:
+///
+/// Tr format: `src dest ilabel olabel [weight]`, or `src dest label [weight]` for acceptors.
+///
+/// Final state format: `state [weight]`
+///
+/// The fields are separated by tabulations or spaces and empty lines are ignored. The errors
+/// report the line and the column of the faulty field.
+///
+/// ## Example:
+/// ```text
+/// 0   1   hello   HELLO   0.5
+/// 1   2   world   WORLD
+/// 2
+/// ```
+pub fn parse_text_fst_with_config<W: SerializableSemiring>(
+    fst_string: &str,
+    config: &TextFstParseConfig,
+) -> Result<ParsedSymbolicTextFst<W>> {
+    let mut isymt = config.isymt.clone();
+    let mut osymt = if config.acceptor {
+        None
+    } else {
+        config.osymt.clone()
+    };
+    let mut ssymt = config.ssymt.clone();
+    let mut parsed_fst = ParsedTextFst::default();
+
+    for (line_idx, line) in fst_string.lines().enumerate() {
+        let fields: Vec<(usize, &str)> = line
+            .split(&['\t', ' '][..])
+            .scan(0, |offset, field| {
+                let column = *offset + 1;
+                *offset += field.chars().count() + 1;
+                Some((column, field))
+            })
+            .filter(|(_, field)| !field.is_empty())
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let num_labels = if config.acceptor { 1 } else { 2 };
+        let mut states = SymbolResolver {
+            symt: ssymt.as_mut(),
+            allow_unknown_symbols: config.allow_unknown_symbols,
+            name: "state",
+        };
+        let mut current_column = fields[0].0;
+        let mut row = || -> Result<()> {
+            let state = states.resolve(fields[0].1)?;
+            if parsed_fst.start_state.is_none() {
+                parsed_fst.start_state = Some(state);
+            }
+            if fields.len() <= 2 {
+                let weight = match fields.get(1) {
+                    Some((column, field)) => {
+                        current_column = *column;
+                        if field.eq_ignore_ascii_case("Infinity") {
+                            return Ok(());
+                        }
+                        Some(parse_weight(field)?)
+                    }
+                    None => None,
+                };
+                parsed_fst.final_states.push(FinalState { state, weight });
+            } else if fields.len() == num_labels + 2 || fields.len() == num_labels + 3 {
+                current_column = fields[1].0;
+                let nextstate = states.resolve(fields[1].1)?;
+                current_column = fields[2].0;
+                let ilabel = SymbolResolver {
+                    symt: isymt.as_mut(),
+                    allow_unknown_symbols: config.allow_unknown_symbols,
+                    name: "input",
+                }
+                .resolve(fields[2].1)?;
+                let olabel = if config.acceptor {
+                    ilabel
+                } else {
+                    current_column = fields[3].0;
+                    SymbolResolver {
+                        symt: osymt.as_mut(),
+                        allow_unknown_symbols: config.allow_unknown_symbols,
+                        name: "output",
+                    }
+                    .resolve(fields[3].1)?
+                };
+                let weight = match fields.get(num_labels + 2) {
+                    Some((column, field)) => {
+                        current_column = *column;
+                        Some(parse_weight(field)?)
+                    }
+                    None => None,
+                };
+                parsed_fst.transitions.push(Transition {
+                    state,
+                    ilabel,
+                    olabel,
+                    weight,
+                    nextstate,
+                });
+            } else {
+                current_column = fields[fields.len() - 1].0;
+                bail!("Unexpected number of fields : {}", fields.len());
+            }
+            Ok(())
+        };
+        row().map_err(|e| {
+            e.context(Error::TextParse {
+                line: line_idx + 1,
+                column: current_column,
+            })
+        })?;
+    }
+
+    if config.acceptor {
+        osymt = isymt.clone();
+    }
+    Ok(ParsedSymbolicTextFst {
+        parsed_fst,
+        isymt,
+        osymt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, Fst, SerializableFst};
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    fn symt(symbols: &[&str]) -> Arc<SymbolTable> {
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(symbols.iter().copied());
+        Arc::new(symt)
+    }
+
+    #[test]
+    fn test_parse_text_fst_with_symbols() -> Result<()> {
+        let config = TextFstParseConfig::new(
+            Some(symt(&["hello", "world"])),
+            Some(symt(&["HELLO", "WORLD"])),
+            None,
+        );
+        let parsed = parse_text_fst_with_config::<TropicalWeight>(
+            "0 1 hello HELLO 0.5\n1\t2\tworld\tWORLD\n\n2\n",
+            &config,
+        )?;
+        assert_eq!(
+            parsed.parsed_fst,
+            ParsedTextFst {
+                transitions: vec![
+                    Transition::new(0, 1, 1, Some(TropicalWeight::new(0.5)), 1),
+                    Transition::new(1, 2, 2, None, 2),
+                ],
+                final_states: vec![FinalState::new(2, None)],
+                start_state: Some(0),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_text_fst_with_symbols_acceptor_and_states() -> Result<()> {
+        let config = TextFstParseConfig::new(
+            Some(symt(&["a", "b"])),
+            None,
+            Some(Arc::new(SymbolTable::from_text_string(
+                "start\t0\nend\t1\n",
+            )?)),
+        )
+        .with_acceptor(true);
+        let parsed =
+            parse_text_fst_with_config::<TropicalWeight>("start end b 1.5\nend 0.5\n", &config)?;
+        assert_eq!(
+            parsed.parsed_fst.transitions,
+            vec![Transition::new(0, 2, 2, Some(TropicalWeight::new(1.5)), 1)]
+        );
+        assert_eq!(
+            parsed.parsed_fst.final_states,
+            vec![FinalState::new(1, Some(TropicalWeight::new(0.5)))]
+        );
+        assert_eq!(parsed.osymt, config.isymt);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_text_fst_with_unknown_symbols() -> Result<()> {
+        let config = TextFstParseConfig::new(Some(symt(&["a"])), Some(symt(&["a"])), None);
+        let err = parse_text_fst_with_config::<TropicalWeight>("0 1 a a\n1 2 a x\n", &config)
+            .unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("line 2, column 7"), "{}", message);
+        assert!(
+            message.contains("Unknown output symbol \"x\""),
+            "{}",
+            message
+        );
+
+        let config = config.with_allow_unknown_symbols(true);
+        let parsed = parse_text_fst_with_config::<TropicalWeight>("0 1 a a\n1 2 b x\n", &config)?;
+        assert_eq!(parsed.parsed_fst.transitions[1].ilabel, 2);
+        assert_eq!(parsed.parsed_fst.transitions[1].olabel, 2);
+        assert_eq!(parsed.isymt.unwrap().get_symbol(2), Some("b"));
+        assert_eq!(parsed.osymt.unwrap().get_label("x"), Some(2));
+        assert_eq!(config.isymt.unwrap().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_text_fst_with_config_invalid_weight() {
+        let config = TextFstParseConfig::default();
+        let err =
+            parse_text_fst_with_config::<TropicalWeight>("0\t1\t2\t3\tfoo\n", &config).unwrap_err();
+        assert!(format!("{:#}", err).contains("line 1, column 9"));
+        let res = parse_text_fst_with_config::<TropicalWeight>("0\t1\t2\t3\n1\n", &config);
+        assert_eq!(
+            res.unwrap().parsed_fst.final_states,
+            vec![FinalState::new(1, None)]
+        );
+    }
+
+    #[test]
+    fn test_from_text_string_with_config_keep_symbols() -> Result<()> {
+        let config = TextFstParseConfig::new(Some(symt(&["hello"])), Some(symt(&["HELLO"])), None)
+            .with_keep_isymbols(true)
+            .with_keep_osymbols(true);
+        let fst: VectorFst<TropicalWeight> =
+            VectorFst::from_text_string_with_config("0 1 hello HELLO 0.5\n1\n", &config)?;
+        assert_eq!(fst.num_trs(0)?, 1);
+        assert_eq!(fst.final_weight(1)?, Some(TropicalWeight::one()));
+        assert_eq!(fst.input_symbols(), config.isymt.as_ref());
+        assert_eq!(fst.output_symbols(), config.osymt.as_ref());
+
+        let config = config.with_keep_isymbols(false);
+        let fst: VectorFst<TropicalWeight> =
+            VectorFst::from_text_string_with_config("0 1 hello HELLO 0.5\n1\n", &config)?;
+        assert!(fst.input_symbols().is_none());
+        assert_eq!(fst.output_symbols(), config.osymt.as_ref());
+
+        let config = config.with_keep_isymbols(true).with_keep_osymbols(false);
+        let fst: VectorFst<TropicalWeight> =
+            VectorFst::from_text_string_with_config("0 1 hello HELLO 0.5\n1\n", &config)?;
+        assert_eq!(fst.input_symbols(), config.isymt.as_ref());
+        assert!(fst.output_symbols().is_none());
+        Ok(())
+    }
+}
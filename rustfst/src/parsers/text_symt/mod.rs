@@ -1,2 +0,0 @@
-pub mod nom_parser;
-pub mod parsed_text_symt;
@@ -104,6 +104,8 @@ impl Arbitrary for VectorFst<TropicalWeight> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "serde")]
+    use crate::fst_impls::ConstFst;
     use crate::fst_traits::ExpandedFst;
     use proptest::prelude::*;
 
@@ -113,4 +115,19 @@ mod tests {
             prop_assert!(fst.num_states() > 0);
         }
     }
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn test_proptest_vector_fst_serde_round_trip(fst in any::<VectorFst<TropicalWeight>>()) {
+            let json = serde_json::to_string(&fst).unwrap();
+            let parsed: VectorFst<TropicalWeight> = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(&parsed, &fst);
+
+            let const_fst = ConstFst::from(fst);
+            let json = serde_json::to_string(&const_fst).unwrap();
+            let parsed: ConstFst<TropicalWeight> = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(parsed, const_fst);
+        }
+    }
 }
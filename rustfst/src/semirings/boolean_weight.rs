@@ -1,7 +1,9 @@
 use anyhow::Result;
 
 use crate::semirings::{CompleteSemiring, ReverseBack, Semiring, SemiringProperties, StarSemiring};
+use crate::Error;
 use std::borrow::Borrow;
+use std::fmt;
 
 use super::{DivideType, WeaklyDivisibleSemiring};
 /// Boolean semiring: (&, |, false, true).
@@ -69,7 +71,12 @@ impl ReverseBack<BooleanWeight> for BooleanWeight {
     }
 }
 
-display_semiring!(BooleanWeight);
+impl fmt::Display for BooleanWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The formatting options are not forwarded as a precision would truncate the boolean.
+        write!(f, "{}", self.value())
+    }
+}
 
 impl CompleteSemiring for BooleanWeight {}
 
@@ -82,7 +89,7 @@ impl StarSemiring for BooleanWeight {
 impl WeaklyDivisibleSemiring for BooleanWeight {
     fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
         if !rhs.value {
-            bail!("Division by 0")
+            bail!(Error::DivisionByZero)
         }
         Ok(()) // x / true == true
     }
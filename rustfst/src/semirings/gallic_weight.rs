@@ -20,24 +20,44 @@ use crate::Label;
 
 /// Product of StringWeightLeft and an arbitrary weight.
 #[derive(PartialOrd, PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct GallicWeightLeft<W>(ProductWeight<StringWeightLeft, W>)
 where
     W: Semiring;
 
 /// Product of StringWeightRight and an arbitrary weight.
 #[derive(PartialOrd, PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct GallicWeightRight<W>(ProductWeight<StringWeightRight, W>)
 where
     W: Semiring;
 
 /// Product of StringWeighRestrict and an arbitrary weight.
 #[derive(PartialOrd, PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct GallicWeightRestrict<W>(ProductWeight<StringWeightRestrict, W>)
 where
     W: Semiring;
 
 /// Product of StringWeightRestrict and an arbitrary weight.
 #[derive(PartialOrd, PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct GallicWeightMin<W>(ProductWeight<StringWeightRestrict, W>)
 where
     W: Semiring;
@@ -323,6 +343,11 @@ impl<W: Semiring> UnionWeightOption<GallicWeightRestrict<W>>
 
 /// UnionWeight of GallicWeightRestrict.
 #[derive(Debug, PartialOrd, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct GallicWeight<W>(
     pub UnionWeight<GallicWeightRestrict<W>, GallicUnionWeightOption<GallicWeightRestrict<W>>>,
 )
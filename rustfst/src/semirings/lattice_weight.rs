@@ -0,0 +1,527 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::bytes::complete::tag;
+use nom::combinator::opt;
+use nom::multi::{count, separated_list0};
+use nom::sequence::preceded;
+use nom::IResult;
+
+use crate::parsers::nom_utils::{num, NomCustomError};
+use crate::parsers::{parse_bin_i32, write_bin_i32};
+use crate::semirings::{
+    DivideType, ProductWeight, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    TropicalWeight, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::{Error, Label};
+
+/// Kaldi lattice semiring: a pair of costs (graph cost, acoustic cost).
+///
+/// `plus` keeps the weight with the lowest total cost (ties are broken by keeping the lowest
+/// graph cost) and `times` adds the costs component-wise.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash, Default, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct LatticeWeight {
+    value: (TropicalWeight, TropicalWeight),
+}
+
+impl LatticeWeight {
+    /// Graph cost.
+    pub fn value1(&self) -> f32 {
+        *self.value.0.value()
+    }
+
+    /// Acoustic cost.
+    pub fn value2(&self) -> f32 {
+        *self.value.1.value()
+    }
+
+    /// Sum of the graph and acoustic costs.
+    pub fn cost(&self) -> f32 {
+        self.value1() + self.value2()
+    }
+
+    /// Orders the weights by total cost then by graph cost. `Ordering::Less` means that
+    /// `self` is the best weight.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        let (c1, c2) = (self.cost(), other.cost());
+        if c1 < c2 {
+            Ordering::Less
+        } else if c1 > c2 {
+            Ordering::Greater
+        } else if self.value1() < other.value1() {
+            Ordering::Less
+        } else if self.value1() > other.value1() {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+impl Semiring for LatticeWeight {
+    type Type = (TropicalWeight, TropicalWeight);
+    type ReverseWeight = LatticeWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: (TropicalWeight::zero(), TropicalWeight::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            value: (TropicalWeight::one(), TropicalWeight::one()),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if self.compare(rhs.borrow()) == Ordering::Greater {
+            *self = *rhs.borrow();
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if self.is_zero() || rhs.borrow().is_zero() {
+            *self = Self::zero();
+        } else {
+            self.value.0.times_assign(rhs.borrow().value.0)?;
+            self.value.1.times_assign(rhs.borrow().value.1)?;
+        }
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        self.value.0.approx_equal(rhs.borrow().value.0, delta)
+            && self.value.1.approx_equal(rhs.borrow().value.1, delta)
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<LatticeWeight> for LatticeWeight {
+    fn reverse_back(&self) -> Result<LatticeWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<LatticeWeight> for LatticeWeight {
+    fn as_ref(&self) -> &LatticeWeight {
+        self
+    }
+}
+
+impl WeaklyDivisibleSemiring for LatticeWeight {
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        if rhs.is_zero() {
+            bail!(Error::DivisionByZero)
+        }
+        if !self.is_zero() {
+            self.value.0.divide_assign(&rhs.value.0, divide_type)?;
+            self.value.1.divide_assign(&rhs.value.1, divide_type)?;
+        }
+        Ok(())
+    }
+}
+
+impl WeightQuantize for LatticeWeight {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.value.0.quantize_assign(delta)?;
+        self.value.1.quantize_assign(delta)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for LatticeWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value.0, self.value.1)
+    }
+}
+
+impl SerializableSemiring for LatticeWeight {
+    fn weight_type() -> String {
+        "lattice4".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight_1) = TropicalWeight::parse_binary(i)?;
+        let (i, weight_2) = TropicalWeight::parse_binary(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value.0.write_binary(file)?;
+        self.value.1.write_binary(file)?;
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight_1) = TropicalWeight::parse_text(i)?;
+        let (i, _) = tag(",")(i)?;
+        let (i, weight_2) = TropicalWeight::parse_text(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+}
+
+impl From<(f32, f32)> for LatticeWeight {
+    fn from(t: (f32, f32)) -> Self {
+        Self::new((t.0.into(), t.1.into()))
+    }
+}
+
+impl From<ProductWeight<TropicalWeight, TropicalWeight>> for LatticeWeight {
+    fn from(w: ProductWeight<TropicalWeight, TropicalWeight>) -> Self {
+        Self::new(w.take_value())
+    }
+}
+
+impl From<LatticeWeight> for ProductWeight<TropicalWeight, TropicalWeight> {
+    fn from(w: LatticeWeight) -> Self {
+        Self::new(w.take_value())
+    }
+}
+
+/// Kaldi compact lattice semiring: a `LatticeWeight` and a string of labels.
+///
+/// `plus` keeps the weight with the best `LatticeWeight`, ties are broken by keeping the
+/// shortest string. `times` multiplies the lattice weights and concatenates the strings.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct CompactLatticeWeight {
+    value: (LatticeWeight, Vec<Label>),
+}
+
+impl CompactLatticeWeight {
+    pub fn weight(&self) -> &LatticeWeight {
+        &self.value.0
+    }
+
+    pub fn string(&self) -> &[Label] {
+        self.value.1.as_slice()
+    }
+
+    /// Orders the weights by `LatticeWeight`, then by string length and finally by
+    /// decreasing lexicographic order of the strings. `Ordering::Less` means that `self`
+    /// is the best weight.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        self.weight()
+            .compare(other.weight())
+            .then_with(|| self.value.1.len().cmp(&other.value.1.len()))
+            .then_with(|| other.value.1.cmp(&self.value.1))
+    }
+}
+
+impl Semiring for CompactLatticeWeight {
+    type Type = (LatticeWeight, Vec<Label>);
+    type ReverseWeight = CompactLatticeWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: (LatticeWeight::zero(), vec![]),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            value: (LatticeWeight::one(), vec![]),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if self.compare(rhs.borrow()) == Ordering::Greater {
+            *self = rhs.borrow().clone();
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        if self.is_zero() || rhs.is_zero() {
+            *self = Self::zero();
+        } else {
+            self.value.0.times_assign(rhs.value.0)?;
+            self.value.1.extend_from_slice(&rhs.value.1);
+        }
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        self.value.0.approx_equal(rhs.borrow().value.0, delta)
+            && self.value.1 == rhs.borrow().value.1
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value = value
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.0.is_zero()
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        let mut string = self.value.1.clone();
+        string.reverse();
+        Ok(Self::new((self.value.0, string)))
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<CompactLatticeWeight> for CompactLatticeWeight {
+    fn reverse_back(&self) -> Result<CompactLatticeWeight> {
+        self.reverse()
+    }
+}
+
+impl AsRef<CompactLatticeWeight> for CompactLatticeWeight {
+    fn as_ref(&self) -> &CompactLatticeWeight {
+        self
+    }
+}
+
+impl WeaklyDivisibleSemiring for CompactLatticeWeight {
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        if rhs.is_zero() {
+            bail!(Error::DivisionByZero)
+        }
+        if self.is_zero() {
+            return Ok(());
+        }
+        self.value.0.divide_assign(&rhs.value.0, divide_type)?;
+        let (s1, s2) = (&self.value.1, &rhs.value.1);
+        self.value.1 = match divide_type {
+            DivideType::DivideLeft => {
+                if !s1.starts_with(s2) {
+                    bail!(
+                        "CompactLatticeWeight : {:?} is not a prefix of {:?}",
+                        s2,
+                        s1
+                    )
+                }
+                s1[s2.len()..].to_vec()
+            }
+            DivideType::DivideRight => {
+                if !s1.ends_with(s2) {
+                    bail!(
+                        "CompactLatticeWeight : {:?} is not a suffix of {:?}",
+                        s2,
+                        s1
+                    )
+                }
+                s1[..s1.len() - s2.len()].to_vec()
+            }
+            DivideType::DivideAny => {
+                bail!(Error::UnsupportedDivision { divide_type })
+            }
+        };
+        Ok(())
+    }
+}
+
+impl WeightQuantize for CompactLatticeWeight {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.value.0.quantize_assign(delta)
+    }
+}
+
+impl fmt::Display for CompactLatticeWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},", self.value.0)?;
+        for (idx, label) in self.value.1.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "_")?;
+            }
+            write!(f, "{}", label)?;
+        }
+        Ok(())
+    }
+}
+
+impl SerializableSemiring for CompactLatticeWeight {
+    fn weight_type() -> String {
+        "compactlattice44".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight) = LatticeWeight::parse_binary(i)?;
+        let (i, n) = parse_bin_i32(i)?;
+        let (i, labels) = count(parse_bin_i32, n as usize)(i)?;
+        let string = labels.into_iter().map(|l| l as Label).collect();
+        Ok((i, Self::new((weight, string))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value.0.write_binary(file)?;
+        write_bin_i32(file, self.value.1.len() as i32)?;
+        for label in self.value.1.iter() {
+            write_bin_i32(file, *label as i32)?;
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight) = LatticeWeight::parse_text(i)?;
+        let (i, string) = opt(preceded(tag(","), separated_list0(tag("_"), num)))(i)?;
+        Ok((i, Self::new((weight, string.unwrap_or_default()))))
+    }
+}
+
+impl From<(LatticeWeight, Vec<Label>)> for CompactLatticeWeight {
+    fn from(t: (LatticeWeight, Vec<Label>)) -> Self {
+        Self::new(t)
+    }
+}
+
+test_semiring_serializable!(
+    tests_lattice_weight_serializable,
+    LatticeWeight,
+    LatticeWeight::one() LatticeWeight::zero() LatticeWeight::from((0.3, 1.5)) LatticeWeight::from((-1.2, 0.0))
+);
+
+test_semiring_serializable!(
+    tests_compact_lattice_weight_serializable,
+    CompactLatticeWeight,
+    CompactLatticeWeight::one() CompactLatticeWeight::from((LatticeWeight::from((0.3, 1.5)), vec![1, 2, 3])) CompactLatticeWeight::from((LatticeWeight::from((2.0, 0.5)), vec![4]))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::weight_convert;
+    use crate::algorithms::weight_converters::SimpleWeightConverter;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, MutableFst};
+    use crate::Tr;
+
+    #[test]
+    fn test_lattice_weight_plus_times() -> Result<()> {
+        let w1 = LatticeWeight::from((1.0, 2.0));
+        let w2 = LatticeWeight::from((2.5, 0.0));
+        let w3 = LatticeWeight::from((2.0, 1.0));
+
+        assert_eq!(w1.plus(w2)?, w2);
+        assert_eq!(w1.plus(w3)?, w1);
+        assert_eq!(w3.plus(w1)?, w1);
+        assert_eq!(w1.plus(LatticeWeight::zero())?, w1);
+        assert_eq!(w1.times(w2)?, LatticeWeight::from((3.5, 2.0)));
+        assert_eq!(w1.times(LatticeWeight::zero())?, LatticeWeight::zero());
+        assert_eq!(
+            w1.times(w2)?.divide(&w2, DivideType::DivideLeft)?,
+            LatticeWeight::from((1.0, 2.0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_lattice_weight_plus_times() -> Result<()> {
+        let w1 = CompactLatticeWeight::from((LatticeWeight::from((1.0, 2.0)), vec![1, 2]));
+        let w2 = CompactLatticeWeight::from((LatticeWeight::from((1.0, 2.0)), vec![3]));
+        let w3 = CompactLatticeWeight::from((LatticeWeight::from((0.5, 0.0)), vec![4, 5, 6]));
+
+        assert_eq!(w1.plus(&w2)?, w2);
+        assert_eq!(w1.plus(&w3)?, w3);
+        assert_eq!(
+            w1.times(&w2)?,
+            CompactLatticeWeight::from((LatticeWeight::from((2.0, 4.0)), vec![1, 2, 3]))
+        );
+        assert!(w1.times(CompactLatticeWeight::zero())?.is_zero());
+
+        let prefix = CompactLatticeWeight::from((LatticeWeight::from((0.5, 1.0)), vec![1]));
+        assert_eq!(
+            w1.divide(&prefix, DivideType::DivideLeft)?,
+            CompactLatticeWeight::from((LatticeWeight::from((0.5, 1.0)), vec![2]))
+        );
+        assert!(w2.divide(&prefix, DivideType::DivideLeft).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_from_product_weight() -> Result<()> {
+        let mut fst = VectorFst::<ProductWeight<TropicalWeight, TropicalWeight>>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, (TropicalWeight::new(0.5), TropicalWeight::one()))?;
+        fst.add_tr(
+            s0,
+            Tr::new(
+                1,
+                2,
+                (TropicalWeight::new(1.0), TropicalWeight::new(2.0)),
+                s1,
+            ),
+        )?;
+
+        let mut converter = SimpleWeightConverter {};
+        let lattice: VectorFst<LatticeWeight> = weight_convert(&fst, &mut converter)?;
+        assert_eq!(
+            lattice.get_trs(s0)?[0].weight,
+            LatticeWeight::from((1.0, 2.0))
+        );
+        assert_eq!(
+            lattice.final_weight(s1)?,
+            Some(LatticeWeight::from((0.5, 0.0)))
+        );
+
+        let back: VectorFst<ProductWeight<TropicalWeight, TropicalWeight>> =
+            weight_convert(&lattice, &mut converter)?;
+        assert_eq!(back.get_trs(s0)?[0].weight, fst.get_trs(s0)?[0].weight);
+        assert_eq!(back.final_weight(s1)?, fst.final_weight(s1)?);
+        Ok(())
+    }
+}
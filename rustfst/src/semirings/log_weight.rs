@@ -23,7 +23,16 @@ use crate::KDELTA;
 
 /// Log semiring: (log(e^-x + e^-y), +, inf, 0).
 #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct LogWeight {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::semirings::utils_float::serde_f32")
+    )]
     value: OrderedFloat<f32>,
 }
 
@@ -36,6 +36,18 @@ macro_rules! test_semiring_serializable {
 
                 Ok(())
             }
+
+            #[cfg(feature = "serde")]
+            #[test]
+            fn test_serializable_serde() -> Result<()> {
+                for weight in &[ $( $weight ),* ] {
+                    let serialization = serde_json::to_string(weight)?;
+                    let weight_deserialized: $semiring = serde_json::from_str(&serialization)?;
+                    assert_eq!(&weight_deserialized, weight);
+                }
+
+                Ok(())
+            }
         }
 
     };
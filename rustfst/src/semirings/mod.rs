@@ -7,6 +7,7 @@ mod macros;
 mod boolean_weight;
 mod gallic_weight;
 mod integer_weight;
+mod lattice_weight;
 mod log_weight;
 mod power_weight;
 mod probability_weight;
@@ -23,6 +24,7 @@ pub use self::gallic_weight::{
     GallicWeight, GallicWeightLeft, GallicWeightMin, GallicWeightRestrict, GallicWeightRight,
 };
 pub use self::integer_weight::IntegerWeight;
+pub use self::lattice_weight::{CompactLatticeWeight, LatticeWeight};
 pub use self::log_weight::LogWeight;
 pub use self::probability_weight::ProbabilityWeight;
 pub use self::product_weight::ProductWeight;
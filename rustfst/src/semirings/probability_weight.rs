@@ -16,11 +16,20 @@ use crate::semirings::{
     CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
     StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
 };
-use crate::KDELTA;
+use crate::{Error, KDELTA};
 
 /// Probability semiring: (x, +, 0.0, 1.0).
 #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct ProbabilityWeight {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::semirings::utils_float::serde_f32")
+    )]
     value: OrderedFloat<f32>,
 }
 
@@ -128,7 +137,7 @@ impl WeaklyDivisibleSemiring for ProbabilityWeight {
     fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
         // May panic if rhs.value == 0.0
         if rhs.value.0 == 0.0 {
-            bail!("Division by 0")
+            bail!(Error::DivisionByZero)
         }
         self.value.0 /= rhs.value.0;
         Ok(())
@@ -12,6 +12,7 @@ use std::io::Write;
 
 bitflags! {
     /// Properties verified by the Semiring.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct SemiringProperties: u32 {
         /// For all a, b, c: Times(c, Plus(a, b)) = Plus(Times(c, a), Times(c, b)).
         const LEFT_SEMIRING =  0b00001;
@@ -79,7 +80,7 @@ pub trait ReverseBack<W> {
 }
 
 /// Determines direction of division.
-#[derive(Copy, Clone, PartialOrd, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq)]
 pub enum DivideType {
     /// Left division.
     DivideLeft,
@@ -149,8 +150,8 @@ macro_rules! display_semiring {
         use std::fmt;
         impl fmt::Display for $semiring {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{}", self.value())?;
-                Ok(())
+                // Forwards the formatting options, e.g the precision of the float weights.
+                fmt::Display::fmt(self.value(), f)
             }
         }
     };
@@ -79,6 +79,39 @@ impl From<Vec<Label>> for StringWeightVariant {
     }
 }
 
+/// The labels are serialized as a sequence and `Infinity` as the string `Infinity`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for StringWeightVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            StringWeightVariant::Infinity => serializer.serialize_str("Infinity"),
+            StringWeightVariant::Labels(l) => serializer.collect_seq(l),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StringWeightVariant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Labels(Vec<Label>),
+            Symbol(String),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Labels(l) => Ok(StringWeightVariant::Labels(l)),
+            Repr::Symbol(s) if s.eq_ignore_ascii_case("infinity") => {
+                Ok(StringWeightVariant::Infinity)
+            }
+            Repr::Symbol(s) => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&s),
+                &"a sequence of labels or \"Infinity\"",
+            )),
+        }
+    }
+}
+
 struct StringWeightVariantIterator<'a> {
     v: &'a StringWeightVariant,
     idx: usize,
@@ -16,22 +16,37 @@ use crate::semirings::{
     DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
     WeaklyDivisibleSemiring, WeightQuantize,
 };
-use crate::Label;
+use crate::{Error, Label};
 
 /// String semiring: (identity, ., Infinity, Epsilon)
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct StringWeightRestrict {
     pub(crate) value: StringWeightVariant,
 }
 
 /// String semiring: (longest_common_prefix, ., Infinity, Epsilon)
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct StringWeightLeft {
     pub(crate) value: StringWeightVariant,
 }
 
 /// String semiring: (longest_common_suffix, ., Infinity, Epsilon)
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct StringWeightRight {
     pub(crate) value: StringWeightVariant,
 }
@@ -342,7 +357,7 @@ fn divide_right(w1: &StringWeightVariant, w2: &StringWeightVariant) -> StringWei
 impl WeaklyDivisibleSemiring for StringWeightLeft {
     fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
         if divide_type != DivideType::DivideLeft {
-            bail!("Only left division is defined.");
+            bail!(Error::UnsupportedDivision { divide_type });
         }
         self.value = divide_left(&self.value, &rhs.value);
         Ok(())
@@ -352,7 +367,7 @@ impl WeaklyDivisibleSemiring for StringWeightLeft {
 impl WeaklyDivisibleSemiring for StringWeightRight {
     fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
         if divide_type != DivideType::DivideRight {
-            bail!("Only right division is defined.");
+            bail!(Error::UnsupportedDivision { divide_type });
         }
         self.value = divide_right(&self.value, &rhs.value);
         Ok(())
@@ -364,7 +379,7 @@ impl WeaklyDivisibleSemiring for StringWeightRestrict {
         self.value = match divide_type {
             DivideType::DivideLeft => divide_left(&self.value, &rhs.value),
             DivideType::DivideRight => divide_right(&self.value, &rhs.value),
-            DivideType::DivideAny => bail!("Only explicit left or right division is defined."),
+            DivideType::DivideAny => bail!(Error::UnsupportedDivision { divide_type }),
         };
         Ok(())
     }
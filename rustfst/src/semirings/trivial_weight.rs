@@ -1,6 +1,7 @@
 use anyhow::Result;
 
 use crate::semirings::{CompleteSemiring, ReverseBack, Semiring, SemiringProperties, StarSemiring};
+use crate::Error;
 use std::borrow::Borrow;
 
 use super::WeaklyDivisibleSemiring;
@@ -77,7 +78,7 @@ impl StarSemiring for TrivialWeight {
 
 impl WeaklyDivisibleSemiring for TrivialWeight {
     fn divide_assign(&mut self, _rhs: &Self, _divide_type: super::DivideType) -> Result<()> {
-        bail!("Division by 0")
+        bail!(Error::DivisionByZero)
     }
 }
 
@@ -24,7 +24,16 @@ use crate::KDELTA;
 
 /// Tropical semiring: (min, +, inf, 0).
 #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct TropicalWeight {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::semirings::utils_float::serde_f32")
+    )]
     value: OrderedFloat<f32>,
 }
 
@@ -1,3 +1,78 @@
 pub(crate) fn float_approx_equal(w1: f32, w2: f32, delta: f32) -> bool {
     (w1 - w2).abs() <= delta
 }
+
+/// Serde representation of the float weights : a number, or in human-readable formats one of
+/// the strings `Infinity`, `-Infinity` and `NaN` for the values that JSON can't represent.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_f32 {
+    use std::fmt;
+
+    use ordered_float::OrderedFloat;
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &OrderedFloat<f32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let value = value.0;
+        if !serializer.is_human_readable() || value.is_finite() {
+            serializer.serialize_f32(value)
+        } else if value.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if value == f32::INFINITY {
+            serializer.serialize_str("Infinity")
+        } else {
+            serializer.serialize_str("-Infinity")
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OrderedFloat<f32>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(F32Visitor).map(OrderedFloat)
+        } else {
+            deserializer.deserialize_f32(F32Visitor).map(OrderedFloat)
+        }
+    }
+
+    struct F32Visitor;
+
+    impl<'de> Visitor<'de> for F32Visitor {
+        type Value = f32;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a number, \"Infinity\", \"-Infinity\" or \"NaN\"")
+        }
+
+        fn visit_f32<E: de::Error>(self, v: f32) -> Result<f32, E> {
+            Ok(v)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<f32, E> {
+            Ok(v as f32)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<f32, E> {
+            Ok(v as f32)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<f32, E> {
+            Ok(v as f32)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<f32, E> {
+            if v.eq_ignore_ascii_case("infinity") || v.eq_ignore_ascii_case("inf") {
+                Ok(f32::INFINITY)
+            } else if v.eq_ignore_ascii_case("-infinity") || v.eq_ignore_ascii_case("-inf") {
+                Ok(f32::NEG_INFINITY)
+            } else if v.eq_ignore_ascii_case("nan") {
+                Ok(f32::NAN)
+            } else {
+                Err(E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+    }
+}
@@ -1,23 +1,89 @@
 use std::fmt;
-use std::fs::{read, File};
+use std::fs::{read, read_to_string, File};
 use std::io::{BufWriter, LineWriter, Write};
 use std::path::Path;
 
 use anyhow::{Context, Result};
 use itertools::Itertools;
 
-use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
-use crate::parsers::nom_utils::NomCustomError;
-use crate::parsers::text_symt::parsed_text_symt::ParsedTextSymt;
+use crate::parsers::bin_fst::bin_reader::BinReader;
+use crate::parsers::bin_symt::nom_parser::{read_symbol_table_bin, write_bin_symt};
 use crate::{Label, EPS_SYMBOL};
-use std::collections::hash_map::{Entry, RandomState};
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// A symbol table stores a bidirectional mapping between transition labels and "symbols" (strings).
 #[derive(Debug, Clone)]
 pub struct SymbolTable<H: BuildHasher = RandomState> {
     bimap: BiHashMapString<H>,
+    name: Option<String>,
+}
+
+static COMPAT_SYMBOLS_CHECK: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables, for the whole process, the check of the symbol tables in the
+/// algorithms combining two FSTs, like the `--fst_compat_symbols` flag of OpenFST. When
+/// enabled, `compose`, `compose_with_config` and `concat` fail if the symbol tables of their inputs are not
+/// compatible according to `compat_symbols`. Disabled by default.
+pub fn set_compat_symbols_check(enabled: bool) {
+    COMPAT_SYMBOLS_CHECK.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the check of the symbol tables is enabled with `set_compat_symbols_check`.
+pub fn compat_symbols_check() -> bool {
+    COMPAT_SYMBOLS_CHECK.load(Ordering::Relaxed)
+}
+
+/// Returns whether two symbol tables are compatible, i.e have the same labeled checksum.
+/// A missing symbol table is compatible with any other.
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate rustfst; fn main() {
+/// # use rustfst::{compat_symbols, SymbolTable};
+/// let symt = symt!["a", "b"];
+/// assert!(compat_symbols(Some(&symt), Some(&symt!["a", "b"])));
+/// assert!(!compat_symbols(Some(&symt), Some(&symt!["b", "a"])));
+/// assert!(compat_symbols(Some(&symt), None));
+/// # }
+/// ```
+pub fn compat_symbols<H: BuildHasher>(
+    symt1: Option<&SymbolTable<H>>,
+    symt2: Option<&SymbolTable<H>>,
+) -> bool {
+    match (symt1, symt2) {
+        (Some(symt1), Some(symt2)) => symt1.labeled_checksum() == symt2.labeled_checksum(),
+        _ => true,
+    }
+}
+
+/// Fails if the symbol tables are not compatible and the check is enabled by `enabled` or
+/// `set_compat_symbols_check`. `what` describes the symbol tables in the error.
+pub(crate) fn check_compat_symbols(
+    symt1: Option<&SymbolTable>,
+    symt2: Option<&SymbolTable>,
+    enabled: bool,
+    what: &str,
+) -> Result<()> {
+    if (enabled || compat_symbols_check()) && !compat_symbols(symt1, symt2) {
+        bail!("Incompatible symbol tables : {}", what);
+    }
+    Ok(())
+}
+
+/// Offset basis and prime of the 64 bits FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+fn fnv1a<'a, I: IntoIterator<Item = &'a u8>>(mut hash: u64, bytes: I) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
 }
 
 impl Default for SymbolTable {
@@ -45,29 +111,54 @@ impl SymbolTable {
     pub fn empty() -> Self {
         SymbolTable {
             bimap: BiHashMapString::new(),
+            name: None,
         }
     }
 
-    fn from_parsed_symt_text(parsed_symt_text: ParsedTextSymt) -> Result<Self> {
-        let mut bimap = BiHashMapString::new();
-        for (symbol, label) in parsed_symt_text.pairs.into_iter() {
-            let inserted_label = bimap.get_id_or_insert(symbol);
-            if inserted_label as Label != label {
-                bail!("The SymbolTable should contain labels with increasing ids and no hole. Expected {} and got {}", inserted_label, label)
+    /// Parses a SymbolTable in text format : one symbol and its label per line, separated by
+    /// spaces or tabs. The labels don't need to be contiguous and can be negative.
+    pub fn from_text_string(symt_string: &str) -> Result<Self> {
+        let mut symt = SymbolTable::empty();
+        for (line_number, line) in symt_string.lines().enumerate() {
+            let mut fields = line.split(&[' ', '\t'][..]).filter(|f| !f.is_empty());
+            let (symbol, key) = match (fields.next(), fields.next(), fields.next()) {
+                (None, _, _) => continue,
+                (Some(symbol), Some(key), None) => (symbol, key),
+                _ => bail!(
+                    "Error while parsing text symt, line {} : {:?}",
+                    line_number + 1,
+                    line
+                ),
+            };
+            let label = key.parse().ok().and_then(label_from_key).ok_or_else(|| {
+                format_err!(
+                    "Error while parsing text symt, invalid label on line {} : {:?}",
+                    line_number + 1,
+                    line
+                )
+            })?;
+            let inserted_label = symt.add_symbol_with_label(symbol, label).with_context(|| {
+                format!("Error while parsing text symt, line {}", line_number + 1)
+            })?;
+            if inserted_label != label {
+                bail!(
+                    "Error while parsing text symt, duplicate symbol on line {} : {:?}",
+                    line_number + 1,
+                    line
+                );
             }
         }
-
-        Ok(SymbolTable { bimap })
-    }
-
-    pub fn from_text_string(symt_string: &str) -> Result<Self> {
-        let parsed_symt = ParsedTextSymt::from_string(symt_string)?;
-        Self::from_parsed_symt_text(parsed_symt)
+        Ok(symt)
     }
 
     pub fn read_text<P: AsRef<Path>>(path_text_symt: P) -> Result<Self> {
-        let parsed_symt = ParsedTextSymt::from_path(path_text_symt)?;
-        Self::from_parsed_symt_text(parsed_symt)
+        let symt_string = read_to_string(path_text_symt.as_ref()).with_context(|| {
+            format!(
+                "Can't open SymbolTable text file : {:?}",
+                path_text_symt.as_ref()
+            )
+        })?;
+        Self::from_text_string(&symt_string)
     }
 
     pub fn read<P: AsRef<Path>>(path_bin_symt: P) -> Result<Self> {
@@ -78,18 +169,15 @@ impl SymbolTable {
             )
         })?;
 
-        let (_, symt) = parse_symbol_table_bin(&data).map_err(|e| {
-            e.map(|e_inner| match e_inner {
-                NomCustomError::Nom(_, k) => {
-                    format_err!("Error while parsing binary SymbolTable. Error kind {:?}", k)
-                }
-                NomCustomError::SymbolTableError(e) => {
-                    format_err!("Error while parsing symbolTable from binary : {}", e)
-                }
-            })
-        })?;
+        Self::load(&data)
+    }
 
-        Ok(symt)
+    /// Loads a SymbolTable from a buffer in binary format. The errors are `FstReadError`
+    /// reporting the offset of the invalid data.
+    pub fn load(data: &[u8]) -> Result<Self> {
+        let mut reader = BinReader::new(data);
+        read_symbol_table_bin(&mut reader)
+            .map_err(|e| anyhow::Error::new(e).context("Error while parsing binary SymbolTable"))
     }
 }
 
@@ -97,13 +185,43 @@ impl<H: BuildHasher> SymbolTable<H> {
     pub fn with_hasher(hasher_builder: H) -> Self {
         let mut bimap = BiHashMapString::with_hasher(hasher_builder);
         bimap.get_id_or_insert(EPS_SYMBOL);
-        Self { bimap }
+        Self { bimap, name: None }
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    /// Name of the symbol table, stored in the binary format. The tables read from a binary
+    /// file always have a name, the ones written without a name get `rustfst_symboltable`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = Some(name.into());
+    }
+
+    /// Checksum of the symbols, in the order in which they were added, as an hexadecimal string.
+    /// It doesn't depend on the name of the table.
+    pub fn checksum(&self) -> String {
+        let hash = self.symbols().fold(FNV_OFFSET_BASIS, |hash, symbol| {
+            fnv1a(hash, symbol.as_bytes().iter().chain(&[0]))
+        });
+        format!("{:016x}", hash)
+    }
+
+    /// Checksum of the (label, symbol) pairs as an hexadecimal string. Two tables with the same
+    /// labeled checksum map the same labels to the same symbols. It doesn't depend on the
+    /// name of the table.
+    pub fn labeled_checksum(&self) -> String {
+        let hash = self.iter().fold(FNV_OFFSET_BASIS, |hash, (label, symbol)| {
+            let label = (label as u64).to_le_bytes();
+            fnv1a(hash, label.iter().chain(symbol.as_bytes()).chain(&[0]))
+        });
+        format!("{:016x}", hash)
+    }
+
     /// Adds a symbol to the symbol table. The corresponding label is returned.
     ///
     /// # Examples
@@ -123,7 +241,54 @@ impl<H: BuildHasher> SymbolTable<H> {
     /// # }
     /// ```
     pub fn add_symbol(&mut self, sym: impl Into<String>) -> Label {
-        self.bimap.get_id_or_insert(sym.into()) as Label
+        self.bimap.get_id_or_insert(sym.into())
+    }
+
+    /// Adds a symbol with a given label, which can be above the labels already in the table or
+    /// leave holes. If the symbol is already present, its label is returned. Fails if the label
+    /// is already used by another symbol.
+    ///
+    /// The symbols added with their index as label, e.g with `add_symbol`, are stored densely.
+    /// The labels of the others are stored in a map, see `dense_key_limit`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # #[macro_use] extern crate rustfst; fn main() -> anyhow::Result<()> {
+    /// # use rustfst::SymbolTable;
+    /// let mut symt = symt!["a"];
+    /// assert_eq!(symt.add_symbol_with_label("z", 100)?, 100);
+    /// assert_eq!(symt.get_symbol(100), Some("z"));
+    /// assert_eq!(symt.dense_key_limit(), 2);
+    ///
+    /// // The next label is after the largest one.
+    /// assert_eq!(symt.add_symbol("b"), 101);
+    /// assert!(symt.add_symbol_with_label("c", 1).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_symbol_with_label(&mut self, sym: impl Into<String>, label: Label) -> Result<Label> {
+        self.bimap.insert_with_id(sym, label)
+    }
+
+    /// Number of symbols, added first, whose label is their position in the table. Their
+    /// labels are not stored, only the ones of the following symbols.
+    pub fn dense_key_limit(&self) -> usize {
+        self.bimap.dense_key_limit()
+    }
+
+    /// Label given to the next symbol added with `add_symbol` : one more than the largest
+    /// non-negative label of the table.
+    pub fn available_key(&self) -> Label {
+        self.bimap.available_id()
+    }
+
+    pub(crate) fn set_available_key(&mut self, label: Label) {
+        self.bimap.set_available_id(label);
+    }
+
+    /// Label of the `n`-th symbol added to the table, like `GetNthKey` in OpenFST.
+    pub fn get_nth_key(&self, n: usize) -> Option<Label> {
+        self.bimap.nth_id(n)
     }
 
     pub fn add_symbols<S: Into<String>, P: IntoIterator<Item = S>>(&mut self, symbols: P) {
@@ -160,7 +325,7 @@ impl<H: BuildHasher> SymbolTable<H> {
     /// # }
     /// ```
     pub fn get_label(&self, sym: impl AsRef<str>) -> Option<Label> {
-        self.bimap.get_id(sym).map(|it| it as Label)
+        self.bimap.get_id(sym)
     }
 
     /// Given a label, returns the symbol corresponding.
@@ -177,7 +342,7 @@ impl<H: BuildHasher> SymbolTable<H> {
     /// # }
     /// ```
     pub fn get_symbol(&self, label: Label) -> Option<&str> {
-        self.bimap.get_string(label as usize)
+        self.bimap.get_string(label)
     }
 
     /// Given a symbol, returns whether it is present in the table.
@@ -227,8 +392,8 @@ impl<H: BuildHasher> SymbolTable<H> {
     ///
     /// # }
     /// ```
-    pub fn labels(&self) -> impl Iterator<Item = Label> {
-        self.bimap.iter_ids().map(|it| it as Label)
+    pub fn labels(&self) -> impl Iterator<Item = Label> + '_ {
+        self.bimap.iter_ids()
     }
 
     /// An iterator on all the symbols stored in the `SymbolTable`.
@@ -253,10 +418,11 @@ impl<H: BuildHasher> SymbolTable<H> {
     /// An iterator on all the labels stored in the `SymbolTable`.
     /// The iterator element is `(&'a Label, &'a Symbol)`.
     pub fn iter(&self) -> impl Iterator<Item = (Label, &str)> {
-        self.bimap.iter().map(|(label, sym)| (label as Label, sym))
+        self.bimap.iter()
     }
 
-    /// Adds another SymbolTable to this table.
+    /// Adds another SymbolTable to this table. Use `algorithms::merge_symbol_table` to also get
+    /// the relabeling of the labels of `other`.
     pub fn add_table(&mut self, other: &SymbolTable) {
         for symbol in other.symbols() {
             self.add_symbol(symbol);
@@ -292,19 +458,89 @@ impl<H: BuildHasher> SymbolTable<H> {
 
 impl<H: BuildHasher> fmt::Display for SymbolTable<H> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for (label, symbol) in self.iter().sorted_by_key(|k| k.0) {
-            writeln!(f, "{}\t{}", symbol, label)?;
+        for (key, symbol) in self
+            .iter()
+            .map(|(label, symbol)| (key_from_label(label), symbol))
+            .sorted_by_key(|k| k.0)
+        {
+            writeln!(f, "{}\t{}", symbol, key)?;
         }
         Ok(())
     }
 }
 
+/// Two symbol tables are equal if they map the same labels to the same symbols, whatever their
+/// names.
 impl<H: BuildHasher> PartialEq for SymbolTable<H> {
     fn eq(&self, other: &Self) -> bool {
         self.bimap.eq(&other.bimap)
     }
 }
 
+/// Serde representation of a `SymbolTable` : `{"name": ..., "symbols": [...], "labels": [...]}`
+/// where the label of each symbol is its index in the list, unless the labels are listed. The
+/// name is omitted if the table has none and the labels if they are the indices of the symbols.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerdeSymbolTable<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<std::borrow::Cow<'a, str>>,
+    symbols: Vec<std::borrow::Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    labels: Option<Vec<Label>>,
+}
+
+#[cfg(feature = "serde")]
+impl<H: BuildHasher> serde::Serialize for SymbolTable<H> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = self.name().map(std::borrow::Cow::Borrowed);
+        let symbols = self.symbols().map(std::borrow::Cow::Borrowed).collect();
+        let labels = if self.dense_key_limit() == self.len() {
+            None
+        } else {
+            Some(self.labels().collect())
+        };
+        serde::Serialize::serialize(
+            &SerdeSymbolTable {
+                name,
+                symbols,
+                labels,
+            },
+            serializer,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SymbolTable {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serde_symt: SerdeSymbolTable = serde::Deserialize::deserialize(deserializer)?;
+        let mut symt = SymbolTable::empty();
+        symt.name = serde_symt.name.map(|name| name.into_owned());
+        symt.reserve(serde_symt.symbols.len());
+        let labels = match serde_symt.labels {
+            Some(labels) if labels.len() != serde_symt.symbols.len() => {
+                return Err(serde::de::Error::custom(format!(
+                    "The SymbolTable has {} symbols but {} labels",
+                    serde_symt.symbols.len(),
+                    labels.len()
+                )))
+            }
+            Some(labels) => labels,
+            None => (0..serde_symt.symbols.len() as Label).collect(),
+        };
+        for (symbol, label) in serde_symt.symbols.into_iter().zip(labels) {
+            if symt.bimap.insert_with_id(symbol.as_ref(), label).ok() != Some(label) {
+                return Err(serde::de::Error::custom(format!(
+                    "Duplicate symbol {:?} or label {} in the SymbolTable",
+                    symbol, label
+                )));
+            }
+        }
+        Ok(symt)
+    }
+}
+
 /// Creates a `SymbolTable` containing the arguments.
 /// ```
 /// # #[macro_use] extern crate rustfst; fn main() {
@@ -329,74 +565,181 @@ macro_rules! symt {
     };
 }
 
+/// Converts a key of an OpenFST symbol table to a label. The negative keys are stored as their
+/// two's complement on 32 bits, as the negative labels of the binary FSTs.
+pub(crate) fn label_from_key(key: i64) -> Option<Label> {
+    if key < 0 {
+        if key >= i64::from(i32::MIN) {
+            Some(key as i32 as Label)
+        } else {
+            None
+        }
+    } else {
+        Label::try_from(key).ok()
+    }
+}
+
+/// Inverse of `label_from_key` : the labels above `i32::MAX` are negative keys.
+pub(crate) fn key_from_label(label: Label) -> i64 {
+    if is_negative_label(label) {
+        i64::from(label as i32)
+    } else {
+        label as i64
+    }
+}
+
+fn is_negative_label(label: Label) -> bool {
+    label as u64 > i32::MAX as u64
+}
+
+/// Bidirectional mapping between labels and symbols. Like in OpenFST, the symbols are stored
+/// in the order of insertion and the labels of the first symbols, as long as they are equal to
+/// their index, are not stored at all : this dense part only needs the `Vec` of the symbols.
+/// The labels of the following symbols are stored in a map.
 #[derive(Clone, Debug, Default)]
 pub(crate) struct BiHashMapString<H: BuildHasher = RandomState> {
-    string_to_id: HashMap<String, usize, H>,
-    id_to_string: Vec<String>,
+    string_to_index: HashMap<String, usize, H>,
+    index_to_string: Vec<String>,
+    /// The symbols with an index below this limit have a label equal to their index.
+    dense_key_limit: usize,
+    /// Labels of the symbols with an index above `dense_key_limit`.
+    sparse_index_to_label: Vec<Label>,
+    sparse_label_to_index: HashMap<Label, usize>,
+    /// Label given to the next symbol added without a label.
+    available_label: Label,
 }
 
 impl<H: BuildHasher> PartialEq for BiHashMapString<H> {
     fn eq(&self, other: &Self) -> bool {
-        self.string_to_id.eq(&other.string_to_id) && self.id_to_string.eq(&other.id_to_string)
+        self.len() == other.len() && self.iter().all(|(id, v)| other.get_string(id) == Some(v))
     }
 }
 
 impl BiHashMapString {
     pub fn new() -> Self {
-        Self {
-            string_to_id: HashMap::new(),
-            id_to_string: Vec::new(),
-        }
+        Self::with_hasher(RandomState::new())
     }
 }
 
 impl<H: BuildHasher> BiHashMapString<H> {
     pub fn with_hasher(hash_builder: H) -> Self {
         Self {
-            string_to_id: HashMap::with_hasher(hash_builder),
-            id_to_string: Vec::new(),
+            string_to_index: HashMap::with_hasher(hash_builder),
+            index_to_string: Vec::new(),
+            dense_key_limit: 0,
+            sparse_index_to_label: Vec::new(),
+            sparse_label_to_index: HashMap::new(),
+            available_label: 0,
         }
     }
 
     pub fn len(&self) -> usize {
-        self.id_to_string.len()
+        self.index_to_string.len()
+    }
+
+    pub fn dense_key_limit(&self) -> usize {
+        self.dense_key_limit
+    }
+
+    pub fn available_id(&self) -> Label {
+        self.available_label
+    }
+
+    pub fn set_available_id(&mut self, label: Label) {
+        self.available_label = label;
     }
 
     pub fn reserve(&mut self, additional: usize) {
-        self.string_to_id.reserve(additional);
-        self.id_to_string.reserve(additional);
-    }
-
-    pub fn get_id_or_insert(&mut self, v: impl Into<String>) -> usize {
-        match self.string_to_id.entry(v.into()) {
-            Entry::Occupied(e) => *e.get(),
-            Entry::Vacant(e) => {
-                let n = self.id_to_string.len();
-                self.id_to_string.push(e.key().clone());
-                e.insert(n);
-                n
-            }
+        self.string_to_index.reserve(additional);
+        self.index_to_string.reserve(additional);
+    }
+
+    pub fn get_id_or_insert(&mut self, v: impl Into<String>) -> Label {
+        let v = v.into();
+        if let Some(label) = self.get_id(&v) {
+            return label;
         }
+        let label = self.available_label;
+        self.insert(v, label);
+        label
     }
 
-    pub fn get_id(&self, v: impl AsRef<str>) -> Option<usize> {
-        self.string_to_id.get(v.as_ref()).cloned()
+    /// Inserts the symbol `v` with the label `id`. If the symbol is already present, its label
+    /// is returned. Fails if the label is already used by another symbol.
+    pub fn insert_with_id(&mut self, v: impl Into<String>, id: Label) -> Result<Label> {
+        let v = v.into();
+        if let Some(label) = self.get_id(&v) {
+            return Ok(label);
+        }
+        if let Some(symbol) = self.get_string(id) {
+            bail!(
+                "Can't add the symbol {:?} with the label {} which is already used by the symbol {:?}",
+                v,
+                id,
+                symbol
+            );
+        }
+        self.insert(v, id);
+        Ok(id)
+    }
+
+    fn insert(&mut self, v: String, id: Label) {
+        let index = self.index_to_string.len();
+        if index == self.dense_key_limit && id as usize == index {
+            self.dense_key_limit += 1;
+        } else {
+            self.sparse_index_to_label.push(id);
+            self.sparse_label_to_index.insert(id, index);
+        }
+        if !is_negative_label(id) && id >= self.available_label {
+            self.available_label = id + 1;
+        }
+        self.string_to_index.insert(v.clone(), index);
+        self.index_to_string.push(v);
     }
 
-    pub fn get_string(&self, id: usize) -> Option<&str> {
-        self.id_to_string.get(id).map(|s| s.as_str())
+    fn index_to_id(&self, index: usize) -> Label {
+        if index < self.dense_key_limit {
+            index as Label
+        } else {
+            self.sparse_index_to_label[index - self.dense_key_limit]
+        }
     }
 
-    pub fn iter_ids(&self) -> impl Iterator<Item = usize> {
-        0..self.id_to_string.len()
+    pub fn get_id(&self, v: impl AsRef<str>) -> Option<Label> {
+        self.string_to_index
+            .get(v.as_ref())
+            .map(|index| self.index_to_id(*index))
+    }
+
+    pub fn get_string(&self, id: Label) -> Option<&str> {
+        let index = if (id as usize) < self.dense_key_limit {
+            id as usize
+        } else {
+            *self.sparse_label_to_index.get(&id)?
+        };
+        Some(self.index_to_string[index].as_str())
+    }
+
+    /// Label of the `n`-th symbol inserted.
+    pub fn nth_id(&self, n: usize) -> Option<Label> {
+        if n < self.len() {
+            Some(self.index_to_id(n))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter_ids(&self) -> impl Iterator<Item = Label> + '_ {
+        (0..self.len()).map(move |index| self.index_to_id(index))
     }
 
     pub fn iter_strings(&self) -> impl Iterator<Item = &str> {
-        self.id_to_string.iter().map(|s| s.as_str())
+        self.index_to_string.iter().map(|s| s.as_str())
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (usize, &str)> {
-        self.id_to_string.iter().map(|s| s.as_str()).enumerate()
+    pub fn iter(&self) -> impl Iterator<Item = (Label, &str)> {
+        self.iter_ids().zip(self.iter_strings())
     }
 }
 
@@ -461,4 +804,97 @@ mod tests {
         assert_eq!(symt1.get_label("b"), Some(2));
         assert_eq!(symt1.get_label("c"), Some(3));
     }
+
+    #[test]
+    fn test_symt_checksums() {
+        let mut symt = symt!["a", "b"];
+        let checksum = symt.checksum();
+        let labeled_checksum = symt.labeled_checksum();
+        assert_ne!(checksum, labeled_checksum);
+
+        symt.set_name("words");
+        assert_eq!(symt.name(), Some("words"));
+        assert_eq!(symt.checksum(), checksum);
+        assert_eq!(symt.labeled_checksum(), labeled_checksum);
+
+        let other = symt!["b", "a"];
+        assert_ne!(other.checksum(), checksum);
+        assert_ne!(other.labeled_checksum(), labeled_checksum);
+        assert!(compat_symbols(Some(&symt), Some(&symt!["a", "b"])));
+        assert!(!compat_symbols(Some(&symt), Some(&other)));
+        assert!(compat_symbols(None, Some(&other)));
+        assert!(compat_symbols::<RandomState>(None, None));
+    }
+
+    #[test]
+    fn test_symt_name_binary_round_trip() -> Result<()> {
+        let mut symt = symt!["a", "b"];
+        symt.set_name("words");
+        let mut data = vec![];
+        write_bin_symt(&mut data, &symt)?;
+        let loaded = SymbolTable::load(&data)?;
+        assert_eq!(loaded, symt);
+        assert_eq!(loaded.name(), Some("words"));
+
+        let mut data = vec![];
+        write_bin_symt(&mut data, &symt!["a"])?;
+        assert_eq!(
+            SymbolTable::load(&data)?.name(),
+            Some("rustfst_symboltable")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_symt_sparse_labels() -> Result<()> {
+        let mut symt = symt!["a"];
+        assert_eq!(symt.add_symbol_with_label("c", 10)?, 10);
+        assert_eq!(symt.add_symbol_with_label("a", 5)?, 1);
+        assert!(symt.add_symbol_with_label("d", 10).is_err());
+        assert_eq!(symt.add_symbol("e"), 11);
+        assert_eq!(
+            symt.add_symbol_with_label("neg", -3i32 as Label)?,
+            -3i32 as Label
+        );
+        assert_eq!(symt.add_symbol("f"), 12);
+
+        assert_eq!(symt.len(), 6);
+        assert_eq!(symt.dense_key_limit(), 2);
+        assert_eq!(symt.get_symbol(10), Some("c"));
+        assert_eq!(symt.get_symbol(2), None);
+        assert_eq!(symt.get_label("neg"), Some(-3i32 as Label));
+        assert_eq!(symt.get_nth_key(4), Some(-3i32 as Label));
+        assert_eq!(symt.get_nth_key(6), None);
+        assert_eq!(
+            symt.labels().collect_vec(),
+            vec![0, 1, 10, 11, -3i32 as Label, 12]
+        );
+        assert_eq!(
+            symt.text()?,
+            "neg\t-3\n<eps>\t0\na\t1\nc\t10\ne\t11\nf\t12\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_symt_sparse_labels_round_trip() -> Result<()> {
+        let symt = SymbolTable::from_text_string("<eps>\t0\na 3\n\nb\t-1\nc\t1\n")?;
+        assert_eq!(symt.len(), 4);
+        assert_eq!(symt.dense_key_limit(), 1);
+        assert_eq!(symt.available_key(), 4);
+        assert_eq!(SymbolTable::from_text_string(&symt.text()?)?, symt);
+
+        let mut data = vec![];
+        write_bin_symt(&mut data, &symt)?;
+        let loaded = SymbolTable::load(&data)?;
+        assert_eq!(loaded, symt);
+        assert_eq!(loaded.labels().collect_vec(), symt.labels().collect_vec());
+        assert_eq!(loaded.available_key(), 4);
+
+        assert!(SymbolTable::from_text_string("a\t1\nb\t1\n").is_err());
+        assert!(SymbolTable::from_text_string("a\t1\na\t2\n").is_err());
+        assert!(SymbolTable::from_text_string("a\tb\n").is_err());
+        assert!(SymbolTable::from_text_string("a\t1\t2\n").is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,69 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::{format_err, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::disambiguate;
+use crate::fst_traits::{AllocableFst, MutableFst, SerializableFst};
+use crate::semirings::SerializableSemiring;
+use crate::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
+use crate::tests_openfst::utils::test_isomorphic_fst;
+use crate::tests_openfst::FstTestData;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DisambiguateOperationResult {
+    result_path: String,
+}
+
+pub struct DisambiguateTestData<W, F>
+where
+    F: SerializableFst<W>,
+    W: SerializableSemiring,
+{
+    result: Result<F>,
+    w: PhantomData<W>,
+}
+
+impl DisambiguateOperationResult {
+    pub fn parse<W, F, P>(&self, dir_path: P) -> DisambiguateTestData<W, F>
+    where
+        F: SerializableFst<W>,
+        W: SerializableSemiring,
+        P: AsRef<Path>,
+    {
+        DisambiguateTestData {
+            result: match self.result_path.as_str() {
+                "error" => Err(format_err!("lol")),
+                _ => F::read(dir_path.as_ref().join(&self.result_path)),
+            },
+            w: PhantomData,
+        }
+    }
+}
+
+pub fn test_disambiguate<W, F>(test_data: &FstTestData<W, F>) -> Result<()>
+where
+    F: SerializableFst<W> + MutableFst<W> + AllocableFst<W> + Display,
+    W: SerializableSemiring + WeaklyDivisibleSemiring + WeightQuantize,
+{
+    let fst_res: Result<F> = disambiguate(&test_data.raw);
+
+    match (&test_data.disambiguate.result, fst_res) {
+        (Ok(fst_expected), Ok(ref fst_disambiguated)) => {
+            test_isomorphic_fst(fst_expected, fst_disambiguated, "Disambiguate");
+        }
+        (Ok(_fst_expected), Err(e)) => {
+            panic!("Disambiguate fail. Got Err. Expected Ok : {:?}", e)
+        }
+        (Err(_), Ok(fst_disambiguated)) => panic!(
+            "Disambiguate fail. Got Ok. Expected Err, \n{}",
+            fst_disambiguated
+        ),
+        (Err(_), Err(_)) => {
+            // Ok
+        }
+    };
+    Ok(())
+}
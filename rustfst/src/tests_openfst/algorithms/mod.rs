@@ -4,6 +4,7 @@ pub mod concat;
 pub mod condense;
 pub mod connect;
 pub mod determinize;
+pub mod disambiguate;
 pub mod encode;
 pub mod factor_weight_gallic;
 pub mod factor_weight_identity;
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use path_abs::{PathAbs, PathMut};
+
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::{ExpandedFst, SerializableFst};
+use crate::semirings::{LogWeight, TropicalWeight};
+use crate::{FstReadError, KDELTA};
+
+// FST stored in each file of `rustfst-tests-data/bin-formats`, see `generate.py`.
+static FST_TEXT: &str = "0\t1\t1\t2\t0.5\n0\t2\t3\t0\t1\n1\t2\t0\t4\t0.25\n2\t1.5\n";
+
+fn fixture(name: &str) -> Result<PathBuf> {
+    let mut path = PathAbs::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap())?;
+    path.append("rustfst-tests-data")?;
+    path.append("bin-formats")?;
+    path.append(name)?;
+    Ok(path.as_path().to_path_buf())
+}
+
+fn expected_fst() -> Result<VectorFst<TropicalWeight>> {
+    VectorFst::from_text_string(FST_TEXT)
+}
+
+#[test]
+fn test_read_vector_fst_versions() -> Result<()> {
+    let fst = VectorFst::<TropicalWeight>::read(fixture("vector_v2.fst")?)?;
+    assert!(fst.approx_equal(&expected_fst()?, KDELTA));
+
+    let fst = VectorFst::<LogWeight>::read(fixture("vector_v2_log.fst")?)?;
+    assert!(fst.approx_equal(&VectorFst::<LogWeight>::from_text_string(FST_TEXT)?, KDELTA));
+    Ok(())
+}
+
+#[test]
+fn test_read_const_fst_versions() -> Result<()> {
+    let expected = expected_fst()?;
+    for name in &[
+        "const_v1_aligned.fst",
+        "const_v1_aligned_no_flag.fst",
+        "const_v2.fst",
+    ] {
+        let fst = ConstFst::<TropicalWeight>::read(fixture(name)?)?;
+        assert!(fst.approx_equal(&expected, KDELTA), "{}", name);
+        let fst = VectorFst::<TropicalWeight>::read_from_const(fixture(name)?)?;
+        assert!(fst.approx_equal(&expected, KDELTA), "{}", name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_const_fst_store_aligned() -> Result<()> {
+    let fst: ConstFst<TropicalWeight> = expected_fst()?.into();
+    let mut data = vec![];
+    fst.store_aligned(&mut data)?;
+    // Same bytes as the file written by OpenFST, except for the properties at 33..41.
+    let openfst_data = std::fs::read(fixture("const_v1_aligned.fst")?)?;
+    assert_eq!(data.len(), openfst_data.len());
+    assert_eq!(data[..33], openfst_data[..33]);
+    assert_eq!(data[41..], openfst_data[41..]);
+    assert_eq!(ConstFst::<TropicalWeight>::load(&data)?, fst);
+
+    let mut data = vec![];
+    fst.store(&mut data)?;
+    assert_eq!(ConstFst::<TropicalWeight>::load(&data)?, fst);
+    Ok(())
+}
+
+#[test]
+fn test_read_big_endian_fst() -> Result<()> {
+    let err = VectorFst::<TropicalWeight>::read(fixture("vector_v2_big_endian.fst")?).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<FstReadError>(),
+        Some(&FstReadError::BigEndian { offset: 0 })
+    );
+    let err = ConstFst::<TropicalWeight>::read(fixture("const_v2_big_endian.fst")?).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<FstReadError>(),
+        Some(&FstReadError::BigEndian { offset: 0 })
+    );
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::symbol_table::SymbolTable;
 use crate::trs::Trs;
 use crate::Semiring;
 
+mod bin_formats;
 pub mod const_fst_bin_deserializer;
 pub mod const_fst_bin_serializer;
 pub mod const_fst_text_deserialization;
@@ -69,6 +69,7 @@ use self::algorithms::{
     compose::test_compose,
     connect::test_connect,
     determinize::{test_determinize, DeterminizeOperationResult, DeterminizeTestData},
+    disambiguate::{test_disambiguate, DisambiguateOperationResult, DisambiguateTestData},
     encode::{test_encode, test_encode_decode, EncodeOperationResult, EncodeTestData},
     inverse::test_invert,
     minimize::{test_minimize, MinimizeOperationResult, MinimizeTestData},
@@ -153,6 +154,7 @@ pub struct ParsedFstTestData {
     state_map_tr_sum: FstOperationResult,
     state_map_tr_unique: FstOperationResult,
     determinize: Vec<DeterminizeOperationResult>,
+    disambiguate: DisambiguateOperationResult,
     minimize: Vec<MinimizeOperationResult>,
     tr_sort_ilabel: FstOperationResult,
     tr_sort_olabel: FstOperationResult,
@@ -210,6 +212,7 @@ where
     pub state_map_tr_sum: F,
     pub state_map_tr_unique: F,
     pub determinize: Vec<DeterminizeTestData<W, F>>,
+    pub disambiguate: DisambiguateTestData<W, F>,
     pub minimize: Vec<MinimizeTestData<W, F>>,
     pub tr_sort_ilabel: F,
     pub tr_sort_olabel: F,
@@ -281,6 +284,7 @@ where
                 .iter()
                 .map(|v| v.parse(absolute_path_folder))
                 .collect(),
+            disambiguate: data.disambiguate.parse(absolute_path_folder),
             minimize: data
                 .minimize
                 .iter()
@@ -529,6 +533,12 @@ macro_rules! test_fst {
                 Ok(())
             }
 
+            #[test]
+            fn test_disambiguate_openfst() -> Result<()> {
+                do_run!(test_disambiguate, $fst_name);
+                Ok(())
+            }
+
             #[test]
             fn test_encode_decode_openfst() -> Result<()> {
                 do_run!(test_encode_decode, $fst_name);
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::SymbolTable;
+
+/// What to write in place of a label or a state missing from the symbol table used to write it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MissingSymbolPolicy {
+    /// Fail with an error.
+    Error,
+    /// Write the numeric value of the label or state.
+    Numeric,
+    /// Write this symbol, like the `--missing_symbol` flag of `fstprint`.
+    Symbol(String),
+}
+
+/// Struct to configure how an FST is written in text format. Mirrors the options of the
+/// `fstprint` binary of OpenFST. The default config writes numeric labels and states, as
+/// `SerializableFst::text` does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextWriteConfig {
+    /// Write the labels with the input and output symbol tables attached to the FST, if any.
+    pub use_symbols: bool,
+    /// Write a single label per transition if the FST is an acceptor.
+    pub acceptor: bool,
+    /// Write transition weights and final weights equal to Weight::ONE.
+    pub show_weight_one: bool,
+    /// Symbol table used to write the states. If `None`, the state ids are written.
+    pub ssymbols: Option<Arc<SymbolTable>>,
+    /// What to write for the labels and states missing from the symbol tables.
+    pub missing_symbol_policy: MissingSymbolPolicy,
+}
+
+impl Default for TextWriteConfig {
+    fn default() -> Self {
+        Self {
+            use_symbols: false,
+            acceptor: false,
+            show_weight_one: true,
+            ssymbols: None,
+            missing_symbol_policy: MissingSymbolPolicy::Error,
+        }
+    }
+}
+
+impl TextWriteConfig {
+    pub fn with_use_symbols(self, use_symbols: bool) -> Self {
+        Self {
+            use_symbols,
+            ..self
+        }
+    }
+
+    pub fn with_acceptor(self, acceptor: bool) -> Self {
+        Self { acceptor, ..self }
+    }
+
+    pub fn with_show_weight_one(self, show_weight_one: bool) -> Self {
+        Self {
+            show_weight_one,
+            ..self
+        }
+    }
+
+    pub fn with_ssymbols(self, ssymbols: Option<Arc<SymbolTable>>) -> Self {
+        Self { ssymbols, ..self }
+    }
+
+    pub fn with_missing_symbol_policy(self, missing_symbol_policy: MissingSymbolPolicy) -> Self {
+        Self {
+            missing_symbol_policy,
+            ..self
+        }
+    }
+}